@@ -1,15 +1,10 @@
-use std::fs::File;
-use std::io::Read;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
-fn process(row: &str) {
+// distance to the end and to the first place visited twice
+fn process(row: &str) -> (i32, Option<i32>) {
     // current position
     let mut x = 0i32;
     let mut y = 0i32;
@@ -17,6 +12,7 @@ fn process(row: &str) {
     let mut dx = 0i32;
     let mut dy = 1i32;
     let mut visits = Vec::new();
+    let mut first_double = None;
 
     for op in row.split(", ") {
         let rotation = op.chars().next().unwrap();
@@ -31,18 +27,25 @@ fn process(row: &str) {
         for _ in 0..travel {
             x += dx;
             y += dy;
-            if visits.contains(&(x, y)) {
-                println!("double {} {} dist {}", x, y, x.abs() + y.abs());
+            if visits.contains(&(x, y)) && first_double.is_none() {
+                first_double = Some(x.abs() + y.abs());
             }
             visits.push((x, y));
         }
     }
-    println!("x {}, y {} dist {}", x, y, x.abs() + y.abs());
+    (x.abs() + y.abs(), first_double)
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
     for row in src.split("\n") {
-        if row != "" { process(row); }
+        if row != "" {
+            let (dist, first_double) = process(row);
+            answer::part1(dist);
+            if let Some(d) = first_double {
+                answer::part2(d);
+            }
+        }
     }
 }
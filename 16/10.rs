@@ -1,19 +1,13 @@
-use std::fs::File;
-use std::io::Read;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::mem::swap;
 //
 // rustc -L foo/deps 10.rs
 extern crate regex;
 use regex::Regex;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 #[derive(Clone, Debug)]
 enum Dest {
     Bot(usize),
@@ -132,7 +126,8 @@ fn evaluate(bots: &mut Vec<Bot>, outs: &mut Vec<usize>) {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
     let mut bots = Vec::new();
     let mut outs = Vec::new();
     for row in src.trim().split("\n") {
@@ -145,5 +140,10 @@ fn main() {
     for (i, o) in outs.iter().enumerate() {
         println!("output {} has value {}", i, o);
     }
+    // the sample has different chips
+    if let Some(i) = bots.iter().position(|b| b.lo == 17 && b.hi == 61) {
+        answer::part1(i);
+    }
+    answer::part2(outs.iter().take(3).product::<usize>());
 }
 
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::collections::vec_deque::VecDeque;
 
 // type encodes also the name, which is an arbitrary character i chose here manually for just
@@ -38,8 +40,7 @@ fn valid(state: &ObjectState) -> bool {
 type Encoded = u32;
 const FLOOR_BITS: Encoded = 2;
 const FLOOR_MASK: Encoded = 3;
-// XXX XXX HOX SWAP THIS 2 and 5 for sample and input (lol 7)
-const N_ELEMENTS: usize = 7;
+// the number of elements is half the number of objects; 7 at most to fit in the visited bitmap
 
 // 2-bit field helpers; the whole puzzle is probably a very neat bit twiddling trick, but it's
 // so much easier to read (and initially write) this way. Hopefully this will get optimized.
@@ -53,8 +54,8 @@ fn _read_elevator(state: Encoded) -> Encoded {
     read_field(state, 0)
 }
 
-fn read_microchip(state: Encoded, idx: usize) -> Encoded {
-    read_field(state, 1 + N_ELEMENTS + idx)
+fn read_microchip(state: Encoded, nelements: usize, idx: usize) -> Encoded {
+    read_field(state, 1 + nelements + idx)
 }
 
 fn read_generator(state: Encoded, idx: usize) -> Encoded {
@@ -74,8 +75,8 @@ fn write_elevator(state: Encoded, new: Encoded) -> Encoded {
     write_field(state, 0, new)
 }
 
-fn _write_microchip(state: Encoded, idx: usize, new: Encoded) -> Encoded {
-    write_field(state, 1 + N_ELEMENTS + idx, new)
+fn _write_microchip(state: Encoded, nelements: usize, idx: usize, new: Encoded) -> Encoded {
+    write_field(state, 1 + nelements + idx, new)
 }
 
 fn _write_generator(state: Encoded, idx: usize, new: Encoded) -> Encoded {
@@ -90,10 +91,10 @@ fn _name_index_sample(ch: char) -> usize {
     }
 }
 
-fn _obj_index_sample(o: &Object) -> usize {
+fn _obj_index_sample(o: &Object, nelements: usize) -> usize {
     match *o {
         Generator(ch) => name_index(ch),
-        Microchip(ch) => N_ELEMENTS + name_index(ch)
+        Microchip(ch) => nelements + name_index(ch)
     }
 }
 
@@ -126,10 +127,10 @@ fn name_index_input(ch: char) -> usize {
     }
 }
 
-fn obj_index_input(o: &Object) -> usize {
+fn obj_index_input(o: &Object, nelements: usize) -> usize {
     match *o {
         Generator(ch) => name_index(ch),
-        Microchip(ch) => N_ELEMENTS + name_index(ch)
+        Microchip(ch) => nelements + name_index(ch)
     }
 }
 
@@ -159,8 +160,8 @@ fn name_index(ch: char) -> usize {
     name_index_input(ch)
 }
 
-fn obj_index(o: &Object) -> usize {
-    obj_index_input(o)
+fn obj_index(o: &Object, nelements: usize) -> usize {
+    obj_index_input(o, nelements)
 }
 
 fn name_at(pos: usize) -> char {
@@ -172,11 +173,12 @@ fn obj_at(pos: usize, nobjs: usize) -> Object {
 }
 
 fn encode(objs: &ObjectState, elevator: usize) -> Encoded {
+    let nelements = objs.iter().map(|floor| floor.len()).sum::<usize>() / 2;
     let mut code = elevator as Encoded; // lowest bits
     for (i, floor) in objs.iter().enumerate() {
         for obj in floor {
             // shift floor index into position, skipping elevator
-            code = write_field(code, 1 + obj_index(obj), i as Encoded);
+            code = write_field(code, 1 + obj_index(obj, nelements), i as Encoded);
             //code |= (i as Encoded) << (obj_index(obj) + 1) as Encoded * FLOOR_BITS;
         }
     }
@@ -208,12 +210,12 @@ impl PartialEq for Node {
 }
 
 // invalid if: for any chip { no matching gen exists and another gen exists in this floor }
-fn valid_encoded(state: Encoded) -> bool {
+fn valid_encoded(state: Encoded, nelements: usize) -> bool {
     //println!("valid? {:064b}", state);
-    for i in 0..N_ELEMENTS {
-        let floor = read_microchip(state, i);
+    for i in 0..nelements {
+        let floor = read_microchip(state, nelements, i);
         if read_generator(state, i) != floor {
-            for j in 0..N_ELEMENTS {
+            for j in 0..nelements {
                 if j != i && read_generator(state, j) == floor {
                     return false;
                 }
@@ -233,9 +235,9 @@ fn print(state: Encoded, nobjects: usize) {
 
 // validate, see if not exist yet, add to nodes, enqueue
 // // validate, see if not exist yet, add to nodes, enqueue
-fn try_enqueue(nodes: &mut Vec<Node>, visited: &mut Vec<u64>, queue: &mut VecDeque<Node>, state: Encoded, parent: &Node) {
+fn try_enqueue(nodes: &mut Vec<Node>, visited: &mut Vec<u64>, queue: &mut VecDeque<Node>, state: Encoded, parent: &Node, nelements: usize) {
     let next = Node { state: state, distance: parent.distance + 1/*, parent: parent.idx, idx: nodes.len()*/ };
-    if /* !nodes.contains(&next)*/ (visited[(state as usize) / 64] & (1 << (state % 64)) == 0) && valid_encoded(state) {
+    if /* !nodes.contains(&next)*/ (visited[(state as usize) / 64] & (1 << (state % 64)) == 0) && valid_encoded(state, nelements) {
         //println!("push {:064b}", next.state);
         print(state, 4);
         if false {
@@ -251,7 +253,7 @@ fn try_enqueue(nodes: &mut Vec<Node>, visited: &mut Vec<u64>, queue: &mut VecDeq
 fn search(start: &ObjectState, end: &ObjectState) -> usize {
     let nobjects = start.iter().map(|row| row.len()).sum::<usize>();
     let nelements = nobjects / 2;
-    assert!(nelements == N_ELEMENTS); // lazy hack
+    assert!(nelements <= 7);
     let is_gen = |id| id < nelements; // gen indices come first in the bits
     let root = Node { state: encode(start, 0), distance: 0/*, parent: 0, idx: 0*/ };
     let end = Node { state: encode(end, 0), distance: 0/*, parent: 0, idx: 0*/ };
@@ -269,14 +271,13 @@ fn search(start: &ObjectState, end: &ObjectState) -> usize {
         ii += 1;
         if ii % 100000 == 0 { println!("{} {}", ii, queue.len()); }
         let cur = current.state;
-        if cur & !3 == end.state & !3 { println!("fffound {:?}\n", current); break; }
+        if cur & !3 == end.state & !3 { println!("fffound {:?}\n", current); return current.distance as usize; }
         //println!("current {:064b}", cur);
         print(cur, nobjects);
         // State changes: elevator up or down, takes one or two objects with it.
         // If two, then they're a) both chips, b) both gens, or c) matching chip and gen.
         // State after they're moved must be valid.
         let elevator = cur & 3;
-        // the write_field indices here require that N_ELEMENTS matches the input
         for i in 0..nobjects {
             let objfloor = read_field(cur, 1 + i);
             if objfloor != elevator { continue; }
@@ -285,12 +286,12 @@ fn search(start: &ObjectState, end: &ObjectState) -> usize {
             if elevator > 0 {
                 try_enqueue(&mut nodes, &mut visited, &mut queue,
                             write_field(write_elevator(cur, elevator - 1),
-                            1 + i, elevator - 1), &current);
+                            1 + i, elevator - 1), &current, nelements);
             }
             if elevator < 3 {
                 try_enqueue(&mut nodes, &mut visited, &mut queue,
                             write_field(write_elevator(cur, elevator + 1),
-                            1 + i, elevator + 1), &current);
+                            1 + i, elevator + 1), &current, nelements);
             }
             // move another with this, maybe
             for j in i+1..nobjects {
@@ -309,7 +310,7 @@ fn search(start: &ObjectState, end: &ObjectState) -> usize {
                                         write_elevator(cur, elevator - 1),
                                         1 + i, elevator - 1),
                                     1 + j, elevator - 1),
-                                &current);
+                                &current, nelements);
                     }
                     if elevator < 3 {
                         try_enqueue(&mut nodes, &mut visited, &mut queue,
@@ -318,7 +319,7 @@ fn search(start: &ObjectState, end: &ObjectState) -> usize {
                                         write_elevator(cur, elevator + 1),
                                         1 + i, elevator + 1),
                                     1 + j, elevator + 1),
-                                &current);
+                                &current, nelements);
                     }
                 }
             }
@@ -402,7 +403,7 @@ fn mainsample() {
         vec![Generator('L')],
         vec![],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![Microchip('L')],
@@ -410,7 +411,7 @@ fn mainsample() {
         vec![Microchip('H'), Generator('H'), Generator('L')],
         vec![],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![Microchip('L')],
@@ -418,7 +419,7 @@ fn mainsample() {
         vec![Generator('H'), Generator('L')],
         vec![],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![Microchip('H'), Microchip('L')],
@@ -426,7 +427,7 @@ fn mainsample() {
         vec![Generator('H'), Generator('L')],
         vec![],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![],
@@ -434,7 +435,7 @@ fn mainsample() {
         vec![Generator('H'), Generator('L')],
         vec![],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![],
@@ -442,7 +443,7 @@ fn mainsample() {
         vec![Microchip('H'), Microchip('L'), Generator('H'), Generator('L')],
         vec![],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![],
@@ -450,7 +451,7 @@ fn mainsample() {
         vec![Generator('H'), Generator('L')],
         vec![Microchip('H'), Microchip('L')],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![],
@@ -458,7 +459,7 @@ fn mainsample() {
         vec![Microchip('H'), Generator('H'), Generator('L')],
         vec![Microchip('L')],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![],
@@ -466,7 +467,7 @@ fn mainsample() {
         vec![Microchip('H')],
         vec![Microchip('L'), Generator('H'), Generator('L')],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![],
@@ -474,7 +475,7 @@ fn mainsample() {
         vec![Microchip('L'), Microchip('H')],
         vec![Generator('H'), Generator('L')],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 
     let step: ObjectState = [
         vec![],
@@ -482,7 +483,7 @@ fn mainsample() {
         vec![],
         vec![Microchip('L'), Microchip('H'), Generator('H'), Generator('L')],
     ];
-    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0)));
+    println!("{} {:064b} {}", encode(&step, 0), encode(&step, 0), valid_encoded(encode(&step, 0), 2));
 }
 
 fn main_partone() {
@@ -510,15 +511,16 @@ fn main_partone() {
     ];
     println!("{} {} {}", valid(&state), valid(&end), valid(&inva));
     println!("{:064b} {:064b} {:064b}", encode(&state, 0), encode(&end, 0), encode(&inva, 0));
-    println!("{}", search(&state, &end));
+    answer::part1(search(&state, &end));
 }
 
 // 2b some extra items not listed on the record
 fn main() {
+    let _session = answer::start();
     if false {
-        main_partone();
         mainsample();
     }
+    main_partone();
     let state: ObjectState = [
         vec![Generator('T'), Microchip('T'), Generator('P'), Generator('S'), Generator('E'), Microchip('E'), Generator('D'), Microchip('D')],
         vec![Microchip('P'), Microchip('S')],
@@ -543,5 +545,5 @@ fn main() {
     ];
     println!("{} {} {}", valid(&state), valid(&end), valid(&inva));
     println!("{:064b} {:064b} {:064b}", encode(&state, 0), encode(&end, 0), encode(&inva, 0));
-    println!("{}", search(&state, &end));
+    answer::part2(search(&state, &end));
 }
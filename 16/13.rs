@@ -1,16 +1,9 @@
-use std::fs::File;
-use std::io::Read;
-
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::collections::vec_deque::VecDeque;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 type Coord = i16;
 type Depth = u32;
 type State = u32;
@@ -87,11 +80,12 @@ fn search_steps(x0: Coord, y0: Coord, fav: Coord, max: usize) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     // sample
     println!("{}", search(1, 1, 7, 4, 10));
     // input
-    let favorite = readfile(&std::env::args().nth(1).unwrap()).trim().parse::<Coord>().unwrap();
-    println!("{}", search(1, 1, 31, 39, favorite));
+    let favorite = input::read_to_string().trim().parse::<Coord>().unwrap();
+    answer::part1(search(1, 1, 31, 39, favorite));
     // second half
-    println!("{}", search_steps(1, 1, favorite, 50));
+    answer::part2(search_steps(1, 1, favorite, 50));
 }
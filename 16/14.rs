@@ -1,19 +1,13 @@
-use std::fs::File;
-use std::io::Read;
-
 // rustc -L foo/deps 14.rs
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+
 extern crate crypto;
 use crypto::md5::Md5;
 use crypto::digest::Digest;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 type HashResult = [u8; 16];
 
 trait HashSource {
@@ -134,13 +128,14 @@ fn index_64th<T: HashSource>(salt: &str, source: T) -> usize {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
 
     assert!(index_64th("abc", HashSourceMd5 {}) == 22728);
-    println!("{}", index_64th(src.trim(), HashSourceMd5 {}));
+    answer::part1(index_64th(src.trim(), HashSourceMd5 {}));
 
     assert!(index_64th("abc", HashSourceMd52016 {}) == 22551);
-    println!("{}", index_64th(src.trim(), HashSourceMd52016 {}));
+    answer::part2(index_64th(src.trim(), HashSourceMd52016 {}));
 }
 
 
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // rustc -L foo/deps 15.rs
@@ -36,10 +38,11 @@ fn first_time(discs: &Vec<(u32, u32)>) -> u32 {
 }
 
 fn main() {
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().map(Result::unwrap);
+    let _session = answer::start();
+    let input = input::reader().lines().map(Result::unwrap);
     let mut discs = input.map(parse_disc).collect::<Vec<_>>();
-    println!("{}", first_time(&discs));
+    answer::part1(first_time(&discs));
 
     discs.push((11, 0));
-    println!("{}", first_time(&discs));
+    answer::part2(first_time(&discs));
 }
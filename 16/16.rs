@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn checksum(source: &str) -> String {
@@ -24,10 +26,11 @@ fn dragon(input: &str, disk_size: usize) -> String {
 }
 
 fn main() {
+    let _session = answer::start();
     // oh dear, so many ways to fail
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().next().unwrap().unwrap();
+    let input = input::reader().lines().next().unwrap().unwrap();
 
     println!("{}", dragon(&input, 20));
-    println!("{}", dragon(&input, 272));
-    println!("{}", dragon(&input, 35651584));
+    answer::part1(dragon(&input, 272));
+    answer::part2(dragon(&input, 35651584));
 }
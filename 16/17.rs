@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // rustc -L foo/deps 17.rs
@@ -100,14 +102,15 @@ fn path(passcode: &str, find_longest: bool) -> String {
 }
 
 fn main() {
+    let _session = answer::start();
     // path("hijkl");
     assert!(path("ihgpwlah", false) == "DDRRRD");
     assert!(path("kglvqrro", false) == "DDUDRLRRUDRD");
     assert!(path("ulqzkmiv", false) == "DRURDRUDDLLDLUURRDULRLDUUDDDRR");
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().next().unwrap().unwrap();
-    println!("{}", path(&input, false));
+    let input = input::reader().lines().next().unwrap().unwrap();
+    answer::part1(path(&input, false));
     assert!(path("ihgpwlah", true).len() == 370);
     assert!(path("kglvqrro", true).len() == 492);
     assert!(path("ulqzkmiv", true).len() == 830);
-    println!("{}", path(&input, true).len());
+    answer::part2(path(&input, true).len());
 }
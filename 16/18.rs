@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn next_tile(three: &[u8]) -> char {
@@ -39,9 +41,10 @@ fn safe_tiles(first_row: &str, rows: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(safe_tiles(".^^.^.^^^^", 10) == 38);
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().next().unwrap().unwrap();
-    println!("{}", safe_tiles(&input, 40));
-    println!("{}", safe_tiles(&input, 400000));
+    let input = input::reader().lines().next().unwrap().unwrap();
+    answer::part1(safe_tiles(&input, 40));
+    answer::part2(safe_tiles(&input, 400000));
 }
 
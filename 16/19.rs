@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 #[derive(Debug)]
@@ -56,11 +58,12 @@ fn winner2(num_elves: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     winner(10); // debugged these two on paper
     winner(15);
     assert!(winner(5) == 3);
     assert!(winner2(5) == 2);
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().next().unwrap().unwrap();
-    println!("{}", winner(input.parse().unwrap()));
-    println!("{}", winner2(input.parse().unwrap()));
+    let input = input::reader().lines().next().unwrap().unwrap();
+    answer::part1(winner(input.parse().unwrap()));
+    answer::part2(winner2(input.parse().unwrap()));
 }
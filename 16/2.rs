@@ -1,15 +1,8 @@
-use std::fs::File;
-use std::io::Read;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
-#[allow(dead_code)]
 fn process(row: &str, x0: i32, y0: i32) -> (i32, i32) {
     let mut x = x0;
     let mut y = y0;
@@ -23,8 +16,6 @@ fn process(row: &str, x0: i32, y0: i32) -> (i32, i32) {
             _ => unreachable!()
         }
     }
-    println!("x {}, y {} key {}", x, y, y * 3 + x + 1);
-
     (x, y)
 }
 
@@ -46,20 +37,39 @@ fn process_correct(row: &str, x0: i32, y0: i32) -> (i32, i32) {
         }
         // println!("op {} x {} y {}", op, x, y);
     }
+    (x, y)
+}
+
+fn square_key((x, y): (i32, i32)) -> char {
+    std::char::from_digit((y * 3 + x + 1) as u32, 10).unwrap()
+}
+
+fn diamond_key((x, y): (i32, i32)) -> char {
     let keys = "__1__X234X56789XABCX__D__";
     let idx = y * 5 + x;
-    let key = keys.chars().nth(idx as usize).unwrap();
-    println!("x {}, y {} key {}", x, y, key);
-
-    (x, y)
+    keys.chars().nth(idx as usize).unwrap()
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
+    let mut xy = (1i32, 1i32);
+    let mut code = String::new();
+    for row in src.split("\n") {
+        if row != "" {
+            xy = process(row, xy.0, xy.1);
+            code.push(square_key(xy));
+        }
+    }
+    answer::part1(code);
+
     let mut xy = (0i32, 2i32);
+    let mut code = String::new();
     for row in src.split("\n") {
         if row != "" {
             xy = process_correct(row, xy.0, xy.1);
+            code.push(diamond_key(xy));
         }
     }
+    answer::part2(code);
 }
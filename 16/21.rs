@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 use std::mem::swap;
 
@@ -92,7 +94,8 @@ fn scramble(state: &str, input: &str, forward: bool) -> String {
 }
 
 fn main() {
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().map(Result::unwrap).collect::<Vec<_>>();
+    let _session = answer::start();
+    let input = input::reader().lines().map(Result::unwrap).collect::<Vec<_>>();
     //let mut sample = "abcde".to_owned();
     let mut puzzle = "abcdefgh".to_owned();
     let mut inverse = "fbgdceah".to_owned();
@@ -101,11 +104,11 @@ fn main() {
         puzzle = scramble(&puzzle, &inp, true);
     }
     //println!("{}", sample);
-    println!("{}", puzzle);
+    answer::part1(puzzle);
     for inp in input.iter().rev() {
         inverse = scramble(&inverse, &inp, false);
     }
-    println!("{}", inverse);
+    answer::part2(inverse);
 }
 
 
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // rustc -L foo/deps 22 .rs
@@ -40,7 +42,8 @@ fn movedata(map: &mut Vec<Node>, dest: usize, src: usize) {
 
 
 fn main() {
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().map(Result::unwrap);
+    let _session = answer::start();
+    let input = input::reader().lines().map(Result::unwrap);
     let nodes = input.filter_map(parse).collect::<Vec<_>>();
 
     let mut viable = 0;
@@ -51,7 +54,7 @@ fn main() {
             }
         }
     }
-    println!("viable {}", viable);
+    answer::part1(viable);
 
     let w = nodes.iter().map(|n| n.x).max().unwrap() + 1; // starts from 0
     let h = nodes.iter().map(|n| n.y).max().unwrap() + 1;
@@ -105,5 +108,5 @@ fn main() {
         movedata(&mut map, pos(data_dst.0, data_dst.1), pos(data_src.0, data_src.1));
     }
 
-    println!("{} steps", route.len() - 1);
+    answer::part2(route.len() - 1);
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::vec_deque::VecDeque;
@@ -97,12 +99,13 @@ fn search(g: &GridGraph, root: NodeId, goals: &[NodeId]) -> Vec<Depth> {
 }
 
 fn main() {
+    let _session = answer::start();
     // my input has just seven places to visit. number of permutations is 7! = 5040, cheap enough
     // to try them all, with some preprocessing involved first so we get the distances between them
     // into a graph for easy access
 
     // parse the input for a map graph
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines().map(Result::unwrap).collect::<Vec<_>>();
+    let input = input::reader().lines().map(Result::unwrap).collect::<Vec<_>>();
 
     // find the number of relevant locations where the exposed wires are located
     let row_numbers = |row: &String| row.chars().filter(|&c| c >= '1' && c <= '9').collect::<Vec<_>>();
@@ -143,7 +146,11 @@ fn main() {
                 }
                 steps
             }).min();
-        println!("{:?}", min_path);
+        if go_back_home {
+            answer::part2(min_path.unwrap());
+        } else {
+            answer::part1(min_path.unwrap());
+        }
     }
     // alternatively, could just bfs in a graph that has the visited places in the state too, with
     // a vector of all marked as visited as the goal node
@@ -1,13 +1,7 @@
-use std::fs::File;
-use std::io::Read;
-
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 
 fn process(row: &str) -> usize {
     let mut nums: Vec<_> = row.split(" ").filter_map(|x| x.parse::<u32>().ok()).collect();
@@ -23,9 +17,10 @@ fn good_tri(a: u32, b: u32, c: u32) -> u32 {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
     let n = src.trim().split("\n").map(process).sum::<usize>();
-    println!("{}", n);
+    answer::part1(n);
 
     // from 101 301 501 102 302 502 103 303 503
     // to   101 102 103 301 302 303 501 502 503
@@ -45,6 +40,6 @@ fn main() {
         n += good_tri(tris[1], tris[4], tris[7]);
         n += good_tri(tris[2], tris[5], tris[8]);
     }
-    println!("{}", n);
+    answer::part2(n);
 }
 
@@ -1,21 +1,15 @@
-use std::fs::File;
-use std::io::Read;
-
 // rustc -L foo/deps 4.rs
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+
 extern crate regex;
 use regex::Regex;
 
 use std::collections::HashMap;
 use std::cmp::Ordering;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 // 0 for non-real rooms
 fn sector_id(row: &str) -> u32 {
     // aaaaa-bbb-z-y-x-123[abxyz]
@@ -33,7 +27,7 @@ fn sector_id(row: &str) -> u32 {
 
     // found this manually, ha
     if decrypted == "northpoleobjectstorage" {
-        println!("{}", sector_id);
+        answer::part2(sector_id);
     }
 
     // first collect alphas into map
@@ -65,9 +59,10 @@ fn sector_id(row: &str) -> u32 {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
     let sum = src.trim().split("\n").map(sector_id).sum::<u32>();
-    println!("{}", sum);
+    answer::part1(sum);
 }
 
 
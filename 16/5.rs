@@ -1,19 +1,13 @@
-use std::fs::File;
-use std::io::Read;
-
 // rustc -L foo/deps 5.rs
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+
 extern crate crypto;
 use crypto::md5::Md5;
 use crypto::digest::Digest;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 fn password(door_id: &str) -> String {
     let mut password = String::new();
     let mut i = 0;
@@ -73,13 +67,14 @@ fn password_part2(door_id: &str) -> String {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
 
     assert!(password("abc") == "18f47a30");
-    println!("{}", password(src.trim()));
+    answer::part1(password(src.trim()));
 
     assert!(password_part2("abc") == "05ace8e3");
-    println!("{}", password_part2(src.trim()));
+    answer::part2(password_part2(src.trim()));
 }
 
 
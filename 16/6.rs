@@ -1,16 +1,9 @@
-use std::fs::File;
-use std::io::Read;
-
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::collections::HashMap;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 fn message(rows: Vec<&str>, part_one: bool) -> String {
     let mut allcounts = vec![HashMap::new(); rows[0].len()];
 
@@ -41,10 +34,11 @@ fn message(rows: Vec<&str>, part_one: bool) -> String {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
 
-    println!("{}", message(src.trim().split("\n").collect(), true));
-    println!("{}", message(src.trim().split("\n").collect(), false));
+    answer::part1(message(src.trim().split("\n").collect(), true));
+    answer::part2(message(src.trim().split("\n").collect(), false));
 }
 
 
@@ -1,16 +1,9 @@
-use std::fs::File;
-use std::io::Read;
-
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::str;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 // Autonomous Bridge Bypass Annotation
 fn has_abba(s: &str) -> bool {
     let bs = s.as_bytes();
@@ -98,11 +91,12 @@ fn support_ssl(row: &str) -> u32 {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
     let sum = src.trim().split("\n").map(support_tls).sum::<u32>();
-    println!("tls {}", sum);
+    answer::part1(sum);
     let sum = src.trim().split("\n").map(support_ssl).sum::<u32>();
-    println!("ssl {}", sum);
+    answer::part2(sum);
 }
 
 
@@ -1,18 +1,12 @@
-use std::fs::File;
-use std::io::Read;
-
 // rustc -L foo/deps 8.rs
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+
 extern crate regex;
 use regex::Regex;
 
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
-
 fn update(input: &str, mut screen: [u64; 6]) -> [u64; 6] {
     println!("{}", input);
     let re_rect = Regex::new(r"rect (\d+)x(\d)").unwrap();
@@ -50,28 +44,30 @@ fn update(input: &str, mut screen: [u64; 6]) -> [u64; 6] {
         }
     }
 
+    screen
+}
+
+fn render(screen: &[u64; 6]) -> String {
+    let mut picture = String::new();
     for row in screen.iter() {
         for i in 0..50 {
-            if row & (1u64 << i) != 0 {
-                print!("#");
-            } else {
-                print!(".");
-            }
+            picture.push(if row & (1u64 << i) != 0 { '#' } else { '.' });
         }
-        println!("");
+        picture.push('\n');
     }
-
-    screen
+    picture
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
     let mut screen = [0u64; 6];
     for row in src.trim().split("\n") {
         screen = update(row, screen);
     }
     let ones: u32 = screen.iter().map(|x| x.count_ones()).sum();
-    println!("{}", ones);
+    answer::part1(ones);
+    answer::part2(render(&screen));
 }
 
 
@@ -1,13 +1,7 @@
-use std::fs::File;
-use std::io::Read;
-
-fn readfile(name: &str) -> String {
-    let mut f = File::open(name).unwrap();
-    let mut s = String::new();
-    f.read_to_string(&mut s).unwrap();
-
-    s
-}
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 
 fn decompressed_length(mut input: &str) -> usize {
     let mut len = 0;
@@ -53,9 +47,10 @@ fn decompressed_length_v2b(mut input: &str) -> usize {
 }
 
 fn main() {
-    let src = readfile(&std::env::args().nth(1).unwrap());
+    let _session = answer::start();
+    let src = input::read_to_string();
     for row in src.trim().split("\n") {
-        println!("v1 {}", decompressed_length(row));
-        println!("v2 {}", decompressed_length_v2b(row));
+        answer::part1(decompressed_length(row));
+        answer::part2(decompressed_length_v2b(row));
     }
 }
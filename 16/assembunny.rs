@@ -0,0 +1,221 @@
+// The assembunny machine of days 12, 23 and 25. The programs spend nearly all their time in a few
+// tight inc/dec/jnz loops that add or multiply registers, so those get recognized and run as one
+// fused step. tgl can rewrite anything, so the fused regions are worked out again after each tgl.
+#![allow(dead_code)]
+
+pub type Value = i64;
+pub type Reg = usize;
+// signed so that jumps can go before the start
+pub type Pc = i64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Argument {
+    Immediate(Value),
+    Register(Reg),
+}
+use Argument::*;
+
+// an instruction made invalid by a tgl, like "cpy 1 2", keeps its arguments and gets skipped; a
+// later tgl may make it valid again
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instruction {
+    Cpy(Argument, Argument), // first goes to second
+    Inc(Argument),
+    Dec(Argument),
+    Jnz(Argument, Argument), // if first { pc += second }
+    Tgl(Argument),
+    Out(Argument),
+}
+use Instruction::*;
+
+pub fn parse(line: &str) -> Instruction {
+    let ops = line.split(' ').collect::<Vec<_>>();
+
+    let arg = |word: usize| {
+        if let Ok(not_reg_but_number) = ops[word].parse::<Value>() {
+            Immediate(not_reg_but_number)
+        } else {
+            Register((ops[word].as_bytes()[0] - b'a') as Reg)
+        }
+    };
+
+    match ops[0] {
+        "cpy" => Cpy(arg(1), arg(2)),
+        "inc" => Inc(arg(1)),
+        "dec" => Dec(arg(1)),
+        "jnz" => Jnz(arg(1), arg(2)),
+        "tgl" => Tgl(arg(1)),
+        "out" => Out(arg(1)),
+        _ => panic!("bad instruction {}", line),
+    }
+}
+
+pub fn parse_program(src: &str) -> Vec<Instruction> {
+    src.lines().map(str::trim).filter(|line| !line.is_empty()).map(parse).collect()
+}
+
+fn toggle(instruction: Instruction) -> Instruction {
+    match instruction {
+        // one-arg insns
+        Inc(x) => Dec(x),
+        Dec(x) | Tgl(x) | Out(x) => Inc(x),
+        // two-arg
+        Jnz(x, y) => Cpy(x, y),
+        Cpy(x, y) => Jnz(x, y),
+    }
+}
+
+// a loop run in one go; each covers a few instructions starting where it's found
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Fused {
+    // inc dst; dec src; jnz src -2 (or dec first): dst += src, src = 0
+    Add { dst: Reg, src: Reg },
+    // cpy factor tmp; an add loop of tmp into dst; dec count; jnz count -5:
+    // dst += factor * count, tmp = 0, count = 0
+    Mul { dst: Reg, factor: Argument, tmp: Reg, count: Reg },
+}
+
+impl Fused {
+    fn len(&self) -> usize {
+        match self {
+            Fused::Add { .. } => 3,
+            Fused::Mul { .. } => 6,
+        }
+    }
+}
+
+fn add_loop(code: &[Instruction]) -> Option<Fused> {
+    match *code.get(..3)? {
+        [Inc(Register(dst)), Dec(Register(src)), Jnz(Register(cmp), Immediate(-2))]
+        | [Dec(Register(src)), Inc(Register(dst)), Jnz(Register(cmp), Immediate(-2))]
+            if cmp == src && dst != src => Some(Fused::Add { dst, src }),
+        _ => None,
+    }
+}
+
+fn mul_loop(code: &[Instruction]) -> Option<Fused> {
+    let (factor, tmp) = match *code.first()? {
+        Cpy(factor, Register(tmp)) => (factor, tmp),
+        _ => return None,
+    };
+    let (dst, src) = match add_loop(&code[1..])? {
+        Fused::Add { dst, src } => (dst, src),
+        _ => unreachable!(),
+    };
+    let count = match *code.get(4..6)? {
+        [Dec(Register(count)), Jnz(Register(cmp), Immediate(-5))] if cmp == count => count,
+        _ => return None,
+    };
+    let distinct = src == tmp && dst != count && tmp != count
+        && !matches!(factor, Register(r) if r == dst || r == tmp || r == count);
+    if distinct { Some(Fused::Mul { dst, factor, tmp, count }) } else { None }
+}
+
+pub struct Machine {
+    pub regs: [Value; 4],
+    pub pc: Pc,
+    program: Vec<Instruction>,
+    // by the first instruction of each recognized loop
+    fused: Vec<Option<Fused>>,
+}
+
+impl Machine {
+    pub fn new(program: &[Instruction], regs: [Value; 4]) -> Self {
+        let mut machine = Machine {
+            regs,
+            pc: 0,
+            program: program.to_vec(),
+            fused: Vec::new(),
+        };
+        machine.optimize();
+        machine
+    }
+
+    fn optimize(&mut self) {
+        self.fused = (0..self.program.len())
+            .map(|i| mul_loop(&self.program[i..]).or_else(|| add_loop(&self.program[i..])))
+            .collect();
+    }
+
+    pub fn halted(&self) -> bool {
+        self.pc < 0 || self.pc >= self.program.len() as Pc
+    }
+
+    fn value(&self, arg: Argument) -> Value {
+        match arg {
+            Immediate(val) => val,
+            Register(reg) => self.regs[reg],
+        }
+    }
+
+    // a loop whose counter isn't positive would run for a very long time or never end the normal
+    // way, so that's left to the plain instructions
+    fn run_fused(&mut self, fused: Fused) -> bool {
+        match fused {
+            Fused::Add { dst, src } if self.regs[src] > 0 => {
+                self.regs[dst] += self.regs[src];
+                self.regs[src] = 0;
+            },
+            Fused::Mul { dst, factor, tmp, count } if self.value(factor) > 0 && self.regs[count] > 0 => {
+                self.regs[dst] += self.value(factor) * self.regs[count];
+                self.regs[tmp] = 0;
+                self.regs[count] = 0;
+            },
+            _ => return false,
+        }
+        self.pc += fused.len() as Pc;
+        true
+    }
+
+    // one instruction or fused loop; returns what an out instruction sent
+    pub fn step(&mut self) -> Option<Value> {
+        let pc = self.pc as usize;
+        if let Some(fused) = self.fused[pc] {
+            if self.run_fused(fused) {
+                return None;
+            }
+        }
+
+        let mut output = None;
+        match self.program[pc] {
+            Cpy(src, Register(dst)) => self.regs[dst] = self.value(src),
+            Inc(Register(reg)) => self.regs[reg] += 1,
+            Dec(Register(reg)) => self.regs[reg] -= 1,
+            Jnz(cmp, off) => {
+                if self.value(cmp) != 0 {
+                    self.pc += self.value(off);
+                    return None;
+                }
+            },
+            Tgl(off) => {
+                let i = self.pc + self.value(off);
+                if i >= 0 && (i as usize) < self.program.len() {
+                    self.program[i as usize] = toggle(self.program[i as usize]);
+                    self.optimize();
+                }
+            },
+            Out(val) => output = Some(self.value(val)),
+            // toggled into something that doesn't make sense
+            Cpy(_, Immediate(_)) | Inc(Immediate(_)) | Dec(Immediate(_)) => (),
+        }
+        self.pc += 1;
+
+        output
+    }
+
+    pub fn run(&mut self) {
+        while !self.halted() {
+            self.step();
+        }
+    }
+
+    // None if it halts first
+    pub fn next_output(&mut self) -> Option<Value> {
+        while !self.halted() {
+            if let Some(out) = self.step() {
+                return Some(out);
+            }
+        }
+        None
+    }
+}
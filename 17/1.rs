@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn solve(input: &str, offset: usize) -> u32 {
@@ -18,18 +20,19 @@ fn solve_b(input: &str) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(solve_a("1122") == 3);
     assert!(solve_a("1111") == 4);
     assert!(solve_a("1234") == 0);
     assert!(solve_a("91212129") == 9);
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input = input::reader()
         .lines().next().unwrap().unwrap();
-    println!("{}", solve_a(&input));
+    answer::part1(solve_a(&input));
 
     assert!(solve_b("1212") == 6);
     assert!(solve_b("1221") == 0);
     assert!(solve_b("123425") == 4);
     assert!(solve_b("123123") == 12);
     assert!(solve_b("12131415") == 4);
-    println!("{}", solve_b(&input));
+    answer::part2(solve_b(&input));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // to nicely reverse slices such that the reversed areas wrap, a buffer that has a duplicate mirror
@@ -75,6 +77,7 @@ fn dense_hash(input: &str) -> String {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(cycled_rev(&mut [0, 1, 2, 3, 4, 0, 1, 2, 3, 4], 3, 4)
             == [4, 3, 2, 1, 0, 4, 3, 2, 1, 0]);
     assert!(cycled_rev(&mut [0, 1, 2, 3, 4, 0, 1, 2, 3, 4], 4, 3)
@@ -83,17 +86,17 @@ fn main() {
             == [2, 1, 0, 3, 4, 2, 1, 0, 3, 4]);
     assert!(solve_a(&mut [3, 4, 1, 5], 5) == 12);
 
-    let input_line = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input_line = input::reader()
         .lines().next().unwrap().unwrap();
 
     let lengths_part_a = input_line.clone().split(",")
         .map(|n| n.parse::<usize>().unwrap()).collect::<Vec<_>>();
-    println!("{}", solve_a(&lengths_part_a, 256));
+    answer::part1(solve_a(&lengths_part_a, 256));
 
     assert!(dense_hash("") == "a2582a3a0e66e6e86e3812dcb672a272");
     assert!(dense_hash("AoC 2017") == "33efeb34ea91902bb2f59c9920caa6cd");
     assert!(dense_hash("1,2,3") == "3efbe78a8d82f29979031a4aa0b16a9d");
     assert!(dense_hash("1,2,4") == "63960835bcdc130f0b66d7ff4f6a5a8e");
 
-    println!("{}", dense_hash(&input_line));
+    answer::part2(dense_hash(&input_line));
 }
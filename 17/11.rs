@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn dist(x: i32, y: i32) -> i32 {
@@ -29,12 +31,15 @@ fn steps_away(path: &[&str]) -> (i32, i32) {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(steps_away(&"ne,ne,ne".split(",").collect::<Vec<_>>()).0 == 3);
     assert!(steps_away(&"ne,ne,sw,sw".split(",").collect::<Vec<_>>()).0 == 0);
     assert!(steps_away(&"ne,ne,s,s".split(",").collect::<Vec<_>>()).0 == 2);
     assert!(steps_away(&"se,sw,se,sw,sw".split(",").collect::<Vec<_>>()).0 == 3);
-    let line = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let line = input::reader()
         .lines().next().unwrap().unwrap();
     let path = line.split(",").collect::<Vec<_>>();
-    println!("{:?}", steps_away(&path));
+    let (dist, maxdist) = steps_away(&path);
+    answer::part1(dist);
+    answer::part2(maxdist);
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 #[derive(Clone, Debug)]
@@ -39,9 +41,10 @@ fn number_of_groups(nodes: &[Node]) -> usize {
 }
 
 fn main() {
-    let nodes = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let nodes = input::reader()
         .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
     println!("{:?}", nodes);
-    println!("{}", zero_group_count(&nodes));
-    println!("{}", number_of_groups(&nodes));
+    answer::part1(zero_group_count(&nodes));
+    answer::part2(number_of_groups(&nodes));
 }
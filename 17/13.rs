@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 #[derive(Debug)]
@@ -40,8 +42,9 @@ fn safe_time(firewall: &[Layer]) -> u32 {
 }
 
 fn main() {
-    let firewall = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let firewall = input::reader()
         .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
-    println!("{}", severity(&firewall));
-    println!("{}", safe_time(&firewall));
+    answer::part1(severity(&firewall));
+    answer::part2(safe_time(&firewall));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // some copypasta from 10.rs. ugh
@@ -156,10 +158,12 @@ fn region_count(key: &str) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(used_squares("flqrgnkx") == 8108);
     assert!(region_count("flqrgnkx") == 1242);
 
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input = input::reader()
         .lines().next().unwrap().unwrap();
-    println!("{} {}", used_squares(&input), region_count(&input));
+    answer::part1(used_squares(&input));
+    answer::part2(region_count(&input));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn matches_in_pairs(a0: u64, b0: u64, num: usize) -> usize {
@@ -15,15 +17,17 @@ fn picky_matches_in_pairs(a0: u64, b0: u64, num: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(matches_in_pairs(65, 8921, 5) == 1);
     assert!(matches_in_pairs(65, 8921, 40*1000*1000) == 588);
     assert!(picky_matches_in_pairs(65, 8921, 5*1000*1000) == 309);
 
-    let mut input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let mut input = input::reader()
         .lines();
     // "Generator A starts with <number>"
     let a = input.next().unwrap().unwrap().split(" ").skip(4).next().unwrap().parse::<u64>().unwrap();
     let b = input.next().unwrap().unwrap().split(" ").skip(4).next().unwrap().parse::<u64>().unwrap();
 
-    println!("{} {}", matches_in_pairs(a, b, 40*1000*1000), picky_matches_in_pairs(a, b, 5*1000*1000));
+    answer::part1(matches_in_pairs(a, b, 40*1000*1000));
+    answer::part2(picky_matches_in_pairs(a, b, 5*1000*1000));
 }
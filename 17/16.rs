@@ -1,5 +1,10 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/cycle.rs"]
+mod cycle;
+use cycle::{History, Repeat};
 use std::io::BufRead;
 
 extern crate regex;
@@ -51,6 +56,7 @@ fn dance<'a>(programs: &'a mut [char], moves: &[&str]) -> &'a [char] {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut sample = ['a', 'b', 'c', 'd', 'e'];
     assert!(spin(&mut sample, 1) == ['e', 'a', 'b', 'c', 'd']);
     assert!(exchange(&mut sample, 3, 4) == ['e', 'a', 'b', 'd', 'c']);
@@ -58,25 +64,33 @@ fn main() {
     sample = ['a', 'b', 'c', 'd', 'e'];
     assert!(dance(&mut sample, &["s1", "x3/4", "pe/b"]) == ['b', 'a', 'e', 'd', 'c']);
 
-    let line = &BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    // 1, 2, 3, 4, 5, 3, 4, 5, ... settles after two steps into a loop of three
+    let settle = |&x: &i32| if x < 5 { x + 1 } else { 3 };
+    let history = History::run(1, settle);
+    assert_eq!(history.repeat, Some(Repeat { prefix: 2, period: 3 }));
+    assert_eq!(*history.extrapolate_to(1000000), 5);
+    assert_eq!(cycle::brent(1, settle), Repeat { prefix: 2, period: 3 });
+
+    let line = &input::reader()
         .lines().next().unwrap().unwrap();
     let moves = line.split(',').collect::<Vec<_>>();
     let orig_programs = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p'];
     let mut programs = orig_programs.clone();
-    let mut history = Vec::new();
-    // start from 1, not 0: comparison is after dancing "round" number of times
-    for round in 1.. {
-        history.push(programs.clone());
-        println!("{:02} {}", round - 1, programs.iter().collect::<String>());
-
-        dance(&mut programs, &moves);
-
-        if programs == orig_programs {
+    let mut history = History::new();
+    // the dance is a permutation, so it comes back to the start
+    loop {
+        let round = history.len();
+        if let Some(repeat) = history.feed(programs) {
             println!("cycle len: {} remainder: {} result: {}",
-                     round,
-                     1000000000 % round,
-                     history[1000000000 % round].iter().collect::<String>());
+                     repeat.period,
+                     1000000000 % repeat.period,
+                     history.extrapolate_to(1000000000).iter().collect::<String>());
             break;
         }
+        println!("{:02} {}", round, programs.iter().collect::<String>());
+
+        dance(&mut programs, &moves);
     }
+    answer::part1(history.extrapolate_to(1).iter().collect::<String>());
+    answer::part2(history.extrapolate_to(1000000000).iter().collect::<String>());
 }
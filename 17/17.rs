@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn short_circuit_value(step: usize) -> usize {
@@ -35,10 +37,11 @@ fn more_angry_value(step: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(short_circuit_value(3) == 638);
 
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input = input::reader()
         .lines().next().unwrap().unwrap().parse().unwrap();
-    println!("{}", short_circuit_value(input));
-    println!("{}", more_angry_value(input));
+    answer::part1(short_circuit_value(input));
+    answer::part2(more_angry_value(input));
 }
@@ -1,21 +1,20 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/regmachine.rs"]
+mod regmachine;
 use std::io::BufRead;
 
 use std::collections::vec_deque::VecDeque;
+use regmachine::{Arg, Effect, Execute, Machine, Parser, Pc, Stop};
 
 // Can't index a vector of registers with char, and indexing a mut hashmap is annoying, so the a-z
 // range is modified to just numbers.
 type Reg = usize;
 type Val = i64;
 
-// some instructions can take either a register or an immediate value
-#[derive(Debug)]
-enum Arg {
-    RegisterArg(Reg),
-    ValueArg(Val)
-}
-use Arg::*;
+const REGS: &str = "abcdefghijklmnopqrstuvwxyz";
 
 #[derive(Debug)]
 enum Instruction {
@@ -29,192 +28,119 @@ enum Instruction {
 }
 use Instruction::*;
 
-fn parse_line(line: &str) -> Instruction {
-    let words = line.split(" ").collect::<Vec<_>>();
-
-    let read_reg = |idx: usize| (words[idx].as_bytes()[0] - b'a') as Reg;
-    let read_arg = |idx: usize| {
-        if let Ok(number) = words[idx].parse::<Val>() {
-            ValueArg(number)
-        } else {
-            RegisterArg(read_reg(idx))
-        }
-    };
+fn parser() -> Parser<'static, Instruction> {
+    use regmachine::{alt, arg, op, pair, reg};
+    alt(vec![
+        op("snd", arg(REGS), Snd),
+        op("set", pair(reg(REGS), arg(REGS)), |(r, a)| Set(r, a)),
+        op("add", pair(reg(REGS), arg(REGS)), |(r, a)| Add(r, a)),
+        op("mul", pair(reg(REGS), arg(REGS)), |(r, a)| Mul(r, a)),
+        op("mod", pair(reg(REGS), arg(REGS)), |(r, a)| Mod(r, a)),
+        op("rcv", reg(REGS), Rcv),
+        op("jgz", pair(arg(REGS), arg(REGS)), |(cmp, off)| Jgz(cmp, off)),
+    ])
+}
 
-    match words[0] {
-        "snd" => Snd(read_arg(1)),
-        "set" => Set(read_reg(1), read_arg(2)),
-        "add" => Add(read_reg(1), read_arg(2)),
-        "mul" => Mul(read_reg(1), read_arg(2)),
-        "mod" => Mod(read_reg(1), read_arg(2)),
-        "rcv" => Rcv(read_reg(1)),
-        "jgz" => Jgz(read_arg(1), read_arg(2)),
-        _ => unreachable!()
+// the rest is the same in both interpretations
+fn execute_common(instruction: &Instruction, regs: &mut [Val]) -> Effect<Val> {
+    match *instruction {
+        Set(dst, src) => regs[dst] = src.value(regs),
+        Add(dst, src) => regs[dst] += src.value(regs),
+        Mul(dst, src) => regs[dst] *= src.value(regs),
+        Mod(dst, src) => regs[dst] %= src.value(regs),
+        Jgz(cmp, off) => if cmp.value(regs) > 0 {
+            return Effect::Jump(off.value(regs));
+        },
+        Snd(_) | Rcv(_) => unreachable!(),
     }
+    Effect::Next
 }
 
 struct SoundMachine {
-    pc: usize,
     regs: Vec<Val>,
     last_snd: Val,
-    last_rcv: Val,
 }
 
-fn execute_sound(machine: &mut SoundMachine, program: &[Instruction]) {
-    match program[machine.pc] {
-        Snd(RegisterArg(snd)) =>
-            machine.last_snd = machine.regs[snd],
-        Snd(ValueArg(snd)) =>
-            machine.last_snd = snd,
-        Set(dst, RegisterArg(src)) =>
-            machine.regs[dst] = machine.regs[src],
-        Set(dst, ValueArg(val)) =>
-            machine.regs[dst] = val,
-        Add(dst, RegisterArg(src)) =>
-            machine.regs[dst] += machine.regs[src],
-        Add(dst, ValueArg(val)) =>
-            machine.regs[dst] += val,
-        Mul(dst, RegisterArg(src)) =>
-            machine.regs[dst] *= machine.regs[src],
-        Mul(dst, ValueArg(val)) =>
-            machine.regs[dst] *= val,
-        Mod(dst, RegisterArg(src)) =>
-            machine.regs[dst] %= machine.regs[src],
-        Mod(dst, ValueArg(val)) =>
-            machine.regs[dst] %= val,
-        Rcv(reg) =>
-            if machine.regs[reg] != 0 { machine.last_rcv = machine.last_snd },
-        Jgz(ref cmp, ref off) => {
-            let cmp = match *cmp {
-                RegisterArg(reg) => machine.regs[reg],
-                ValueArg(arg) => arg
-            };
-            let off = match *off {
-                RegisterArg(reg) => machine.regs[reg],
-                ValueArg(arg) => arg
-            };
-            if cmp > 0 {
-                machine.pc = (machine.pc as Val + off) as usize;
-                return;
-            }
+// a recovered frequency is an event
+impl Execute<SoundMachine> for Instruction {
+    type Event = Val;
+
+    fn execute(&self, machine: &mut SoundMachine, _pc: Pc) -> Effect<Val> {
+        match *self {
+            Snd(snd) => machine.last_snd = snd.value(&machine.regs),
+            Rcv(reg) => if machine.regs[reg] != 0 {
+                return Effect::Emit(machine.last_snd);
+            },
+            _ => return execute_common(self, &mut machine.regs),
         }
+        Effect::Next
     }
-
-    machine.pc += 1;
 }
 
 fn first_rcv(program: &[Instruction]) -> Val {
-    // char iteration is kludgy unfortunately
-    let regs = vec![0; (b'z' - b'a' + 1) as usize];
-    let mut machine = SoundMachine { pc: 0, regs: regs, last_snd: 0, last_rcv: 0 };
-    while machine.pc < program.len() && machine.last_rcv == 0 {
-        execute_sound(&mut machine, program);
+    let mut machine = Machine::new(program, SoundMachine { regs: vec![0; REGS.len()], last_snd: 0 });
+    match machine.run() {
+        Stop::Event(recovered) => recovered,
+        _ => 0,
     }
-
-    machine.last_rcv
 }
 
 struct DuetMachine {
-    pc: usize,
     regs: Vec<Val>,
     recv_queue: VecDeque<Val>,
     send_count: usize
 }
 
-fn execute_duet(machine: &mut DuetMachine, program: &[Instruction]) -> Option<Val> {
-    let mut sent_value = None;
-
-    match program[machine.pc] {
-        Snd(RegisterArg(snd)) => {
-            machine.send_count += 1;
-            sent_value = Some(machine.regs[snd]);
-        },
-        Snd(ValueArg(snd)) => {
-            machine.send_count += 1;
-            sent_value = Some(snd);
-        },
-        Set(dst, RegisterArg(src)) =>
-            machine.regs[dst] = machine.regs[src],
-        Set(dst, ValueArg(val)) =>
-            machine.regs[dst] = val,
-        Add(dst, RegisterArg(src)) =>
-            machine.regs[dst] += machine.regs[src],
-        Add(dst, ValueArg(val)) =>
-            machine.regs[dst] += val,
-        Mul(dst, RegisterArg(src)) =>
-            machine.regs[dst] *= machine.regs[src],
-        Mul(dst, ValueArg(val)) =>
-            machine.regs[dst] *= val,
-        Mod(dst, RegisterArg(src)) =>
-            machine.regs[dst] %= machine.regs[src],
-        Mod(dst, ValueArg(val)) =>
-            machine.regs[dst] %= val,
-        Rcv(dst) =>
-            machine.regs[dst] = machine.recv_queue.pop_front().unwrap(),
-        Jgz(ref cmp, ref off) => {
-            let cmp = match *cmp {
-                RegisterArg(reg) => machine.regs[reg],
-                ValueArg(arg) => arg
-            };
-            let off = match *off {
-                RegisterArg(reg) => machine.regs[reg],
-                ValueArg(arg) => arg
-            };
-            if cmp > 0 {
-                machine.pc = (machine.pc as Val + off) as usize;
-                return None;
-            }
+// a sent value is an event, and receiving with nothing in the queue blocks
+impl Execute<DuetMachine> for Instruction {
+    type Event = Val;
+
+    fn execute(&self, machine: &mut DuetMachine, _pc: Pc) -> Effect<Val> {
+        match *self {
+            Snd(snd) => {
+                machine.send_count += 1;
+                return Effect::Emit(snd.value(&machine.regs));
+            },
+            Rcv(dst) => match machine.recv_queue.pop_front() {
+                Some(value) => machine.regs[dst] = value,
+                None => return Effect::Block,
+            },
+            _ => return execute_common(self, &mut machine.regs),
         }
+        Effect::Next
     }
-
-    machine.pc += 1;
-
-    sent_value
-}
-
-fn can_continue(mach: &DuetMachine, program: &[Instruction]) -> bool {
-    if let Rcv(_) = program[mach.pc] {
-        mach.recv_queue.len() > 0
-    } else {
-        true
-    }
-}
-
-fn duet_finished(mach_a: &DuetMachine, mach_b: &DuetMachine, program: &[Instruction]) -> bool {
-    !can_continue(mach_a, program) && !can_continue(mach_b, program)
 }
 
 fn duet_sendcount(program: &[Instruction]) -> usize {
-    let regs_a = vec![0; (b'z' - b'a' + 1) as usize];
-    let mut regs_b = vec![0; (b'z' - b'a' + 1) as usize];
-    regs_b[(b'p' - b'a') as usize] = 1; // pid
-
-    let mut mach_a = DuetMachine { pc: 0, regs: regs_a,
-        recv_queue: VecDeque::new(), send_count: 0 };
-    let mut mach_b = DuetMachine { pc: 0, regs: regs_b,
-        recv_queue: VecDeque::new(), send_count: 0 };
-
-    // Alternatively, exec could return None if blocked, or Some(None) / Some(Some(message))
-    // but this is just what I came up first
-    while !duet_finished(&mach_a, &mach_b, program) {
-        if can_continue(&mach_a, &program) {
-            if let Some(message) = execute_duet(&mut mach_a, program) {
-                mach_b.recv_queue.push_back(message);
+    let duet_machine = |pid| {
+        let mut regs = vec![0; REGS.len()];
+        regs[REGS.find('p').unwrap()] = pid;
+        Machine::new(program, DuetMachine { regs, recv_queue: VecDeque::new(), send_count: 0 })
+    };
+    let mut machines = [duet_machine(0), duet_machine(1)];
+
+    // each runs until it's stuck, and it's over when neither had anything to say
+    loop {
+        let mut sent = false;
+        for i in 0..2 {
+            while let Stop::Event(message) = machines[i].run() {
+                machines[1 - i].state.recv_queue.push_back(message);
+                sent = true;
             }
         }
-        if can_continue(&mach_b, &program) {
-            if let Some(message) = execute_duet(&mut mach_b, program) {
-                mach_a.recv_queue.push_back(message);
-            }
+        if !sent {
+            break;
         }
     }
 
-    mach_b.send_count
+    machines[1].state.send_count
 }
 
 fn main() {
-    let program = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
-        .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
-    println!("{}", first_rcv(&program));
-    println!("{}", duet_sendcount(&program));
+    let _session = answer::start();
+    let parser = parser();
+    let program = input::reader()
+        .lines().map(|x| regmachine::parse_line(&parser, &x.unwrap())).collect::<Vec<_>>();
+    answer::part1(first_rcv(&program));
+    answer::part2(duet_sendcount(&program));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn path_letters(map: &[String]) -> (String, usize) {
@@ -57,7 +59,10 @@ fn path_letters(map: &[String]) -> (String, usize) {
 }
 
 fn main() {
-    let map = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let map = input::reader()
         .lines().map(|x| x.unwrap()).collect::<Vec<String>>();
-    println!("{:?}", path_letters(&map));
+    let (path, steps) = path_letters(&map);
+    answer::part1(path);
+    answer::part2(steps);
 }
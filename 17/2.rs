@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn line_checksum_a(line: &str) -> u32 {
@@ -21,10 +23,11 @@ fn line_checksum_b(line: &str) -> u32 {
 }
 
 fn main() {
-    let input_lines = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let input_lines = input::reader()
         .lines().map(|x| x.unwrap()).collect::<Vec<_>>();
     let sum = input_lines.iter().map(|l| line_checksum_a(&l)).sum::<u32>();
-    println!("{}", sum);
+    answer::part1(sum);
     let sum = input_lines.iter().map(|l| line_checksum_b(&l)).sum::<u32>();
-    println!("{}", sum);
+    answer::part2(sum);
 }
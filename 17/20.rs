@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 extern crate regex;
@@ -206,8 +208,9 @@ fn collision_winners(universe: &mut Vec<Point>) -> usize {
 }
 
 fn main() {
-    let mut universe = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let mut universe = input::reader()
         .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
-    println!("{:?}", zeroest_particle(&mut universe.clone()));
-    println!("{:?}", collision_winners(&mut universe));
+    answer::part1(zeroest_particle(&mut universe.clone()));
+    answer::part2(collision_winners(&mut universe));
 }
@@ -4,8 +4,10 @@
 // rustc --cfg 'csimode="fancy"'
 // see further below for experiments.
 
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // Square block of pixels, implemented as an array. A vector-based block with runtime-deduceable
@@ -322,8 +324,9 @@ fn on_pixels_after(rules: &[Rule], iterations: usize) -> usize {
 }
 
 fn main() {
-    let rules = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let rules = input::reader()
         .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
-    println!("{:?}", on_pixels_after(&rules, 5));
-    println!("{:?}", on_pixels_after(&rules, 18));
+    answer::part1(on_pixels_after(&rules, 5));
+    answer::part2(on_pixels_after(&rules, 18));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::HashMap;
@@ -95,8 +97,9 @@ fn infected_evolved_bursts(source_map: &[String], bursts: usize) -> usize {
 }
 
 fn main() {
-    let map = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let map = input::reader()
         .lines().map(|x| x.unwrap()).collect::<Vec<_>>();
-    println!("{:?}", infected_bursts(&map, 10000));
-    println!("{:?}", infected_evolved_bursts(&map, 10000000));
+    answer::part1(infected_bursts(&map, 10000));
+    answer::part2(infected_evolved_bursts(&map, 10000000));
 }
@@ -1,19 +1,19 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/regmachine.rs"]
+mod regmachine;
 use std::io::BufRead;
 
+use regmachine::{Arg, Effect, Execute, Machine, Parser, Pc};
+
 // Can't index a vector of registers with char, and indexing a mut hashmap is annoying, so the a-h
 // range is modified to just numbers.
 type Reg = usize;
 type Val = i64;
 
-// some instructions can take either a register or an immediate value
-#[derive(Debug)]
-enum Arg {
-    RegisterArg(Reg),
-    ValueArg(Val)
-}
-use Arg::*;
+const REGS: &str = "abcdefgh";
 
 #[derive(Debug)]
 enum Instruction {
@@ -24,92 +24,68 @@ enum Instruction {
 }
 use Instruction::*;
 
-fn parse_line(line: &str) -> Instruction {
-    let words = line.split(" ").collect::<Vec<_>>();
-
-    let read_reg = |idx: usize| (words[idx].as_bytes()[0] - b'a') as Reg;
-    let read_arg = |idx: usize| {
-        if let Ok(number) = words[idx].parse::<Val>() {
-            ValueArg(number)
-        } else {
-            RegisterArg(read_reg(idx))
-        }
-    };
-
-    match words[0] {
-        "set" => Set(read_reg(1), read_arg(2)),
-        "sub" => Sub(read_reg(1), read_arg(2)),
-        "mul" => Mul(read_reg(1), read_arg(2)),
-        "jnz" => Jnz(read_arg(1), read_arg(2)),
-        _ => unreachable!()
-    }
+fn parser() -> Parser<'static, Instruction> {
+    use regmachine::{alt, arg, op, pair, reg};
+    alt(vec![
+        op("set", pair(reg(REGS), arg(REGS)), |(r, a)| Set(r, a)),
+        op("sub", pair(reg(REGS), arg(REGS)), |(r, a)| Sub(r, a)),
+        op("mul", pair(reg(REGS), arg(REGS)), |(r, a)| Mul(r, a)),
+        op("jnz", pair(arg(REGS), arg(REGS)), |(cmp, off)| Jnz(cmp, off)),
+    ])
 }
 
 struct Coprocessor {
-    pc: usize,
     regs: Vec<Val>,
     mul_invocations: usize
 }
 
-fn execute_debugmode(machine: &mut Coprocessor, program: &[Instruction]) {
-    match program[machine.pc] {
-        Set(dst, RegisterArg(src)) =>
-            machine.regs[dst] = machine.regs[src],
-        Set(dst, ValueArg(val)) =>
-            machine.regs[dst] = val,
-        Sub(dst, RegisterArg(src)) =>
-            machine.regs[dst] -= machine.regs[src],
-        Sub(dst, ValueArg(val)) =>
-            machine.regs[dst] -= val,
-        Mul(dst, RegisterArg(src)) => {
-            machine.regs[dst] *= machine.regs[src];
-            machine.mul_invocations += 1;
-        },
-        Mul(dst, ValueArg(val)) => {
-            machine.regs[dst] *= val;
-            machine.mul_invocations += 1;
-        },
-        Jnz(ref cmp, ref off) => {
-            let cmp = match *cmp {
-                RegisterArg(reg) => machine.regs[reg],
-                ValueArg(arg) => arg
-            };
-            let off = match *off {
-                RegisterArg(reg) => machine.regs[reg],
-                ValueArg(arg) => arg
-            };
-            if cmp != 0 {
-                machine.pc = (machine.pc as Val + off) as usize;
-                return;
-            }
+impl Execute<Coprocessor> for Instruction {
+    type Event = ();
+
+    fn execute(&self, machine: &mut Coprocessor, _pc: Pc) -> Effect<()> {
+        let regs = &mut machine.regs;
+        match *self {
+            Set(dst, src) => regs[dst] = src.value(regs),
+            Sub(dst, src) => regs[dst] -= src.value(regs),
+            Mul(dst, src) => {
+                regs[dst] *= src.value(regs);
+                machine.mul_invocations += 1;
+            },
+            Jnz(cmp, off) => if cmp.value(regs) != 0 {
+                return Effect::Jump(off.value(regs));
+            },
         }
+        Effect::Next
     }
-
-    machine.pc += 1;
 }
 
 fn mul_count(program: &[Instruction]) -> usize {
-    // char iteration is kludgy unfortunately
-    let regs = vec![0; (b'h' - b'a' + 1) as usize];
-    let mut machine = Coprocessor { pc: 0, regs: regs, mul_invocations: 0 };
-    while machine.pc < program.len() {
-        execute_debugmode(&mut machine, program);
-    }
+    let mut machine = Machine::new(program, Coprocessor { regs: vec![0; REGS.len()], mul_invocations: 0 });
+    machine.run();
 
-    machine.mul_invocations
+    machine.state.mul_invocations
 }
 
 // lol, crazy slow
 fn reg_h_final(program: &[Instruction]) -> Val {
-    // char iteration is kludgy unfortunately
-    let mut regs = vec![0; (b'h' - b'a' + 1) as usize];
+    let mut regs = vec![0; REGS.len()];
     regs[0] = 1;
-    let mut machine = Coprocessor { pc: 0, regs: regs, mul_invocations: 0 };
-    while machine.pc < program.len() {
-        execute_debugmode(&mut machine, program);
-    }
+    let mut machine = Machine::new(program, Coprocessor { regs: regs, mul_invocations: 0 });
+    machine.run();
+
+    machine.state.regs[7]
+}
 
-    machine.regs[7]
+// where reg_h_final spends its time, which is what led to the translation below
+fn hot_spots(program: &[Instruction]) {
+    let mut regs = vec![0; REGS.len()];
+    regs[0] = 1;
+    let mut machine = Machine::new(program, Coprocessor { regs: regs, mul_invocations: 0 })
+        .with_step_limit(10_000_000);
+    machine.run();
+    for (pc, count) in machine.hot_spots(10) {
+        println!("{:3} {:9} {:?}", pc, count, program[pc]);
+    }
 }
 
 // still crazy slow, like four seconds per major iteration
@@ -215,14 +191,19 @@ fn nonprimes_in_range() -> Val {
 }
 
 fn main() {
-    let program = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
-        .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
-    println!("{}", mul_count(&program));
+    let _session = answer::start();
+    let parser = parser();
+    let program = input::reader()
+        .lines().map(|x| regmachine::parse_line(&parser, &x.unwrap())).collect::<Vec<_>>();
+    answer::part1(mul_count(&program));
     if false {
         println!("{}", reg_h_final(&program));
     }
+    if false {
+        hot_spots(&program);
+    }
     if false {
         println!("{}", problem_translated());
     }
-    println!("{}", nonprimes_in_range());
+    answer::part2(nonprimes_in_range());
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::ops::Add;
@@ -127,8 +129,9 @@ fn strongest_long_bridge(strewn: &[Component]) -> LengthStrength {
 }
 
 fn main() {
-    let compo_strewn = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let compo_strewn = input::reader()
         .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
-    println!("{}", strongest_bridge(&compo_strewn));
-    println!("{:?}", strongest_long_bridge(&compo_strewn));
+    answer::part1(strongest_bridge(&compo_strewn));
+    answer::part2(strongest_long_bridge(&compo_strewn).1);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::collections::HashMap;
 
 enum State {
@@ -52,7 +54,8 @@ fn ones<F>(n: usize, f: F) -> usize
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(ones(6, sample) == 3);
-    println!("{}", ones(12399302, sample));
-    println!("{}", ones(12399302, input));
+    answer::part1(ones(12399302, sample));
+    answer::part2(ones(12399302, input));
 }
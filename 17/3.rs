@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // option 1: bruteforce the grid open until the puzzle input is found. meh.
@@ -99,12 +101,13 @@ fn solve_b(input: i32) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(solve(1) == 0);
     assert!(solve(12) == 3);
     assert!(solve(23) == 2);
     assert!(solve(1024) == 31);
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input = input::reader()
         .lines().next().unwrap().unwrap().parse::<i32>().unwrap();
-    println!("{}", solve(input));
-    println!("{}", solve_b(input));
+    answer::part1(solve(input));
+    answer::part2(solve_b(input));
 }
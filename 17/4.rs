@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::HashSet;
@@ -27,13 +29,14 @@ fn added_security(line: &str) -> bool {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(valid_passphrase("aa bb cc dd ee"));
     assert!(!valid_passphrase("aa bb cc dd aa"));
     assert!(valid_passphrase("aa bb cc dd aaa"));
-    let input_lines = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input_lines = input::reader()
         .lines().map(|x| x.unwrap()).collect::<Vec<_>>();
     let sum = input_lines.iter().map(|l| valid_passphrase(&l)).filter(|&x| x).count();
-    println!("{}", sum);
+    answer::part1(sum);
 
     assert!(added_security("abcde fghij"));
     assert!(!added_security("abcde xyz ecdab"));
@@ -41,5 +44,5 @@ fn main() {
     assert!(added_security("iiii oiii ooii oooi oooo"));
     assert!(!added_security("oiii ioii iioi iiio"));
     let sum = input_lines.iter().map(|l| added_security(&l)).filter(|&x| x).count();
-    println!("{}", sum);
+    answer::part2(sum);
 }
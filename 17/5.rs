@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn steps_to_exit(mut jumps: Vec<i32>) -> i32 {
@@ -29,10 +31,11 @@ fn steps_to_exit_strange(mut jumps: Vec<i32>) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(steps_to_exit(vec![0, 3, 0, 1, -3]) == 5);
     assert!(steps_to_exit_strange(vec![0, 3, 0, 1, -3]) == 10);
-    let input_lines = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input_lines = input::reader()
         .lines().map(|x| x.unwrap().parse::<i32>().unwrap()).collect::<Vec<_>>();
-    println!("{}", steps_to_exit(input_lines.clone()));
-    println!("{}", steps_to_exit_strange(input_lines));
+    answer::part1(steps_to_exit(input_lines.clone()));
+    answer::part2(steps_to_exit_strange(input_lines));
 }
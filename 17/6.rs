@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::HashSet;
@@ -46,6 +48,7 @@ fn redist_cycle_size(mut banks: Vec<u32>) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut sample = vec![0u32, 2, 7, 0];
     redist(&mut sample);
     assert!(sample == vec![2u32, 4, 1, 2]);
@@ -57,8 +60,8 @@ fn main() {
 
     assert!(redist_cycle_count(vec![0, 2, 7, 0]) == 5);
     assert!(redist_cycle_size(vec![0, 2, 7, 0]) == 4);
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input = input::reader()
         .lines().next().unwrap().unwrap().split("\t").map(|n| n.parse::<u32>().unwrap()).collect::<Vec<_>>();
-    println!("{}", redist_cycle_count(input.clone()));
-    println!("{}", redist_cycle_size(input));
+    answer::part1(redist_cycle_count(input.clone()));
+    answer::part2(redist_cycle_size(input));
 }
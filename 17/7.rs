@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::HashMap;
@@ -55,36 +57,43 @@ fn bottom_program_name<'a>(programs: &'a [Node]) -> &'a str {
     bottom_program(programs).name
 }
 
-fn track<'a>(programs: &'a [Node], n: &Node) -> u32 {
+// the deepest unbalanced node is found first; the ones below it are then unbalanced too
+fn track<'a>(programs: &'a [Node], n: &Node, fix: &mut Option<u32>) -> u32 {
     if n.children.len() == 0 {
         return n.weight;
     }
 
     let mut weights = n.children.iter()
         .map(|&ci| &programs[ci])
-        .map(|c| (track(programs, c), c.name, c.weight))
+        .map(|c| (track(programs, c, fix), c.name, c.weight))
         .collect::<Vec<_>>();
 
     assert!(weights.len() > 1); // this seems to be the case
     weights.sort();
 
     if weights[0].0 < weights[1].0 || weights[weights.len() - 1].0 > weights[weights.len() - 2].0 {
+        let lighter = weights[0].2 + weights[1].0 - weights[0].0;
+        let heavier = weights[weights.len() - 1].2 - (weights[weights.len() - 1].0 - weights[weights.len() - 2].0);
         println!("!! {} {:?}", n.name, weights);
-        println!("!! sides {} {}",
-                 weights[0].2 + weights[1].0 - weights[0].0,
-                 weights[weights.len() - 1].2 - (weights[weights.len() - 1].0 - weights[weights.len() - 2].0),
-                 );
+        println!("!! sides {} {}", lighter, heavier);
+        if fix.is_none() {
+            *fix = Some(if weights[0].0 < weights[1].0 { lighter } else { heavier });
+        }
     }
     //println!("{} {}", n.name, n.weight + weights.iter().sum::<u32>());
     n.weight + weights.iter().map(|&(w, _, _)| w).sum::<u32>()
 }
 
-fn b<'a>(programs: &'a [Node]) -> u32 {
-    track(programs, bottom_program(programs))
+// total weight and the fixed weight of the wrong program
+fn b<'a>(programs: &'a [Node]) -> (u32, u32) {
+    let mut fix = None;
+    let total = track(programs, bottom_program(programs), &mut fix);
+    (total, fix.unwrap())
 }
 
 fn main() {
-    let nodespecs = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let nodespecs = input::reader()
         .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
     // get node names without children first, because the children are indices
     let mut nodes = nodespecs.iter()
@@ -102,7 +111,9 @@ fn main() {
     }
     println!("{:?}", nodes);
     // tknk for sample
-    println!("{}", bottom_program_name(&nodes));
+    answer::part1(bottom_program_name(&nodes));
     // sample: ugml has to be 60
-    println!("total weight is {} but it's unrelated", b(&nodes));
+    let (total, fix) = b(&nodes);
+    println!("total weight is {} but it's unrelated", total);
+    answer::part2(fix);
 }
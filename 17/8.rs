@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::str::FromStr;
@@ -88,11 +90,12 @@ fn run(program: &[Instruction], memory: &mut HashMap<String, i32>) -> i32 {
 }
 
 fn main() {
-    let program = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let program = input::reader()
         .lines().map(|x| parse_line(&x.unwrap())).collect::<Vec<_>>();
     let mut memory: HashMap<String, i32> = HashMap::new();
     let highest = run(&program, &mut memory);
     println!("{:?}", memory);
-    println!("{}", memory.iter().map(|(_, &v)| v).max().unwrap());
-    println!("{}", highest);
+    answer::part1(memory.iter().map(|(_, &v)| v).max().unwrap());
+    answer::part2(highest);
 }
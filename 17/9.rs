@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn score_garbage(stream: &str) -> (u32, u32) {
@@ -36,6 +38,7 @@ fn score_garbage(stream: &str) -> (u32, u32) {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(score_garbage("{}").0 == 1);
     assert!(score_garbage("{{{}}}").0 == 6);
     assert!(score_garbage("{{},{}}").0 == 5);
@@ -53,7 +56,9 @@ fn main() {
     assert!(score_garbage("<!!!>>").1 == 0);
     assert!(score_garbage("<{o\"i!a,<{i<a>").1 == 10);
 
-    let input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let input = input::reader()
         .lines().next().unwrap().unwrap();
-    println!("{:?}", score_garbage(&input));
+    let (score, garbage) = score_garbage(&input);
+    answer::part1(score);
+    answer::part2(garbage);
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 use std::collections::HashSet;
 
@@ -20,11 +22,12 @@ fn calibrate(diffs: &[i32]) -> i32 {
 }
 
 fn main() {
-    let parsed_diffs = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let parsed_diffs = input::reader()
         .lines().map(|x| x.unwrap().parse::<i32>().unwrap()).collect::<Vec<_>>();
 
     let x = parsed_diffs.iter().fold(0, |acc, x| acc + x);
-    println!("{}", x);
+    answer::part1(x);
 
-    println!("{}", calibrate(&parsed_diffs));
+    answer::part2(calibrate(&parsed_diffs));
 }
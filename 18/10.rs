@@ -1,5 +1,8 @@
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::fs::File;
-use std::io::BufReader;
 use std::io::BufRead;
 use std::io::Write;
 
@@ -51,9 +54,26 @@ fn dump(world: &[(i32, i32, i32, i32)], i: u32) {
     }
 }
 
+// just the bounding box, unlike the frame dumps
+fn render(world: &[(i32, i32, i32, i32)]) -> String {
+    let x0 = world.iter().map(|&b| b.0).min().unwrap();
+    let x1 = world.iter().map(|&b| b.0).max().unwrap();
+    let y0 = world.iter().map(|&b| b.1).min().unwrap();
+    let y1 = world.iter().map(|&b| b.1).max().unwrap();
+    let mut picture = String::new();
+    for y in y0..=y1 {
+        for x in x0..=x1 {
+            picture.push(if world.iter().any(|&b| b.0 == x && b.1 == y) { '#' } else { '.' });
+        }
+        picture.push('\n');
+    }
+    picture
+}
+
 fn main() {
+    let _session = answer::start();
     let re = Regex::new(r"position=< *([\d\-]+), *([\d\-]+)> velocity=< *([\d\-]+), *([\d\-]+)>").unwrap();
-    let mut bodies = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let mut bodies = input::reader()
         .lines().map(|x| parse_celestial_body(&re, &x.unwrap())).collect::<Vec<_>>();
     let mut bb_prev = std::i32::MAX;
     for i in 1.. {
@@ -72,6 +92,8 @@ fn main() {
             // started to disperse; it's the previous frame
             back(&mut bodies);
             dump(&bodies, i - 1);
+            answer::part1(render(&bodies));
+            answer::part2(i - 1);
             break;
         }
     }
@@ -1,3 +1,6 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+
 fn fuel_cell_power_level(x: i32, y: i32, serial: i32) -> i32 {
     let rack_id = x + 10;
     (rack_id * y + serial) * rack_id / 100 % 10 - 5
@@ -76,6 +79,7 @@ fn largest_total_square(serial: i32) -> (i32, i32, i32, i32) {
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(fuel_cell_power_level(3, 5, 8), 4);
     assert_eq!(fuel_cell_power_level(122, 79, 57), -5);
     assert_eq!(fuel_cell_power_level(217, 196, 39), 0);
@@ -86,9 +90,11 @@ fn main() {
     assert_eq!(powerest_square(42, 3), (30, 21, 61));
 
     let puzzle_input = 9110;
-    println!("{:?}", powerest_square(puzzle_input, 3));
+    let (_, x, y) = powerest_square(puzzle_input, 3);
+    answer::part1(format!("{},{}", x, y));
 
-    println!("{:?}", largest_total_square(puzzle_input));
+    let (_, x, y, sz) = largest_total_square(puzzle_input);
+    answer::part2(format!("{},{},{}", x, y, sz));
     assert_eq!(largest_total_square(18), (113, 90, 269, 16));
     assert_eq!(largest_total_square(42), (119, 232, 251, 12));
 }
@@ -1,5 +1,10 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/cycle.rs"]
+mod cycle;
+use cycle::{History, Repeat};
 use std::io::BufRead;
 
 fn parse_rule(line: &str) -> Option<[u8; 5]> {
@@ -51,30 +56,33 @@ fn sum_planted_pots(mut pots: Vec<u8>, rules: &[[u8; 5]], rounds: usize) -> usiz
         pots.insert(0, b'.');
     }
 
-    let mut prev_score = 0;
-    let mut prev_diff = 0;
-    for i in 0..rounds {
-        let next = spread(&pots, &rules);
-        let current_score = score(&next, pad);
-        let score_diff = current_score - prev_score;
+    // the plants settle into a pattern that glides along, so compare them without the empty ends
+    let mut history = History::new();
+    let mut scores = Vec::new();
+    for _ in 0..rounds {
+        let first = pots.iter().position(|&pot| pot == b'#').unwrap();
+        let last = pots.iter().rposition(|&pot| pot == b'#').unwrap();
+        scores.push(score(&pots, pad) as i64);
+        // println!("{:6} {}", scores.last().unwrap(), String::from_utf8_lossy(&pots));
 
-        // println!("{:6} {:6} {}", current_score, score_diff, String::from_utf8_lossy(&next));
-
-        if score_diff == prev_diff {
-            // converged
-            let iters_to_go = rounds - 1 - i;
-            return current_score + iters_to_go * score_diff;
+        if let Some(repeat) = history.feed(pots[first..=last].to_vec()) {
+            return repeat.extrapolate_to(rounds, |i| scores[i]) as usize;
         }
-        prev_score = current_score;
-        prev_diff = score_diff;
-        pots = next;
+        pots = spread(&pots, &rules);
     }
 
     score(&pots, pad)
 }
 
 fn main() {
-    let mut lines = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    // the running total of 1, 2, 3, 4, 5, 3, 4, 5, ... gains 12 on each lap of three
+    let totals = [0, 1, 3, 6, 10, 15];
+    let repeat = Repeat { prefix: 2, period: 3 };
+    assert_eq!(repeat.extrapolate_to(4, |i| totals[i]), 10);
+    assert_eq!(repeat.extrapolate_to(1000000, |i| totals[i]), 3999994);
+
+    let mut lines = input::reader()
         .lines();
 
     let first = lines.next().unwrap().unwrap();
@@ -84,6 +92,6 @@ fn main() {
     let rules = lines.filter_map(|line| parse_rule(&line.unwrap())).collect::<Vec<_>>();
 
     let pots = init_state_str.bytes().collect::<Vec<_>>();
-    println!("{}", sum_planted_pots(pots.clone(), &rules, 20));
-    println!("{}", sum_planted_pots(pots, &rules, 50000000000));
+    answer::part1(sum_planted_pots(pots.clone(), &rules, 20));
+    answer::part2(sum_planted_pots(pots, &rules, 50000000000));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -178,7 +180,8 @@ fn play_until_end(map: &[Vec<char>], carts: &mut Vec<Cart>) -> (usize, usize) {
 }
 
 fn main() {
-    let mut map = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let mut map = input::reader()
         .lines().map(|x| x.unwrap().chars().collect::<Vec<_>>()).collect::<Vec<_>>();
     let mut carts = Vec::new();
     for (y, row) in map.iter_mut().enumerate() {
@@ -194,7 +197,7 @@ fn main() {
     }
     let map = map; // mutated no more
     let endpos = play_until_crash(&map, &mut carts.clone());
-    println!("a: {},{}", endpos.0, endpos.1);
+    answer::part1(format!("{},{}", endpos.0, endpos.1));
     let endpos = play_until_end(&map, &mut carts);
-    println!("b: {},{}", endpos.0, endpos.1);
+    answer::part2(format!("{},{}", endpos.0, endpos.1));
 }
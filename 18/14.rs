@@ -1,3 +1,6 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+
 fn bake(recipe_count: usize) -> usize {
     let mut recipes = vec![3, 7];
     let mut alice = 0;
@@ -47,16 +50,17 @@ fn bake_more(input: usize, sz: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(bake(9) == 5158916779);
     assert!(bake(5) == 0124515891);
     assert!(bake(18) == 9251071085);
     assert!(bake(2018) == 5941429882);
     let puzzle_input = 165061;
     let puzzle_digits = 6;
-    println!("{:10}", bake(puzzle_input));
+    answer::part1(format!("{:010}", bake(puzzle_input)));
     assert!(bake_more(51589, 5) == 9);
     assert!(bake_more( 1245, 5) == 5);
     assert!(bake_more(92510, 5) == 18);
     assert!(bake_more(59414, 5) == 2018);
-    println!("{}", bake_more(puzzle_input, puzzle_digits));
+    answer::part2(bake_more(puzzle_input, puzzle_digits));
 }
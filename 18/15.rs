@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::HashSet;
@@ -237,7 +239,8 @@ fn elves_ftw(map: &[Vec<char>], units: &Vec<Unit>) -> usize {
 }
 
 fn main() {
-    let mut map = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let mut map = input::reader()
         .lines().map(|x| x.unwrap().chars().collect::<Vec<_>>()).collect::<Vec<_>>();
     let mut units = Vec::new();
     for (y, row) in map.iter_mut().enumerate() {
@@ -252,7 +255,7 @@ fn main() {
         }
     }
 
-    println!("{}", play(&map, &units));
+    answer::part1(play(&map, &units));
 
-    println!("{}", elves_ftw(&map, &units));
+    answer::part2(elves_ftw(&map, &units));
 }
@@ -1,86 +1,35 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 use std::io::Lines;
+#[path = "../lib/regmachine.rs"]
+mod regmachine;
+mod elfcode;
 
 use std::collections::HashSet;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Opcode {
-    Addr,
-    Addi,
-    Mulr,
-    Muli,
-    Banr,
-    Bani,
-    Borr,
-    Bori,
-    Setr,
-    Seti,
-    Gtir,
-    Gtri,
-    Gtrr,
-    Eqir,
-    Eqri,
-    Eqrr,
-}
-use Opcode::*;
-
-const OPCODES: usize = 16;
-
-#[derive(Debug)]
-struct Instruction {
-    opcode: Opcode,
-    // 0, 1, 2, or 3 for below
-    in_a: usize,
-    in_b: usize,
-    out: usize,
-}
+use elfcode::{Instruction, Opcode, OPCODES, step_instruction};
+use regmachine::Machine;
 
 #[derive(Debug, PartialEq)]
-struct Machine {
+struct SampleRegs {
     regs: [u32; 4],
 }
 
-fn step_instruction(machine: &mut Machine, inst: &Instruction) {
-    let ai = inst.in_a as u32;
-    let bi = inst.in_b as u32;
-    let ar = machine.regs[inst.in_a];
-    let br = machine.regs[inst.in_b];
-    let c = &mut machine.regs[inst.out];
-    *c = match inst.opcode {
-        Addr => ar + br,
-        Addi => ar + bi,
-        Mulr => ar * br,
-        Muli => ar * bi,
-        Banr => ar & br,
-        Bani => ar & bi,
-        Borr => ar | br,
-        Bori => ar | bi,
-        Setr => ar,
-        Seti => ai,
-        Gtir => if ai > br { 1 } else { 0 },
-        Gtri => if ar > bi { 1 } else { 0 },
-        Gtrr => if ar > br { 1 } else { 0 },
-        Eqir => if ai == br { 1 } else { 0 },
-        Eqri => if ar == bi { 1 } else { 0 },
-        Eqrr => if ar == br { 1 } else { 0 },
-    };
-}
-
-fn similar_to(op: &Opcode, begin: &Machine, end: &Machine, code: &[usize; 4]) -> bool {
-    let mut mach = Machine { regs: begin.regs };
+fn similar_to(op: &Opcode, begin: &SampleRegs, end: &SampleRegs, code: &[usize; 4]) -> bool {
+    let mut regs = begin.regs;
     let inst = Instruction { opcode: *op, in_a: code[1], in_b: code[2], out: code[3] };
-    step_instruction(&mut mach, &inst);
-    mach == *end
+    step_instruction(&mut regs, &inst);
+    regs == end.regs
 }
 
-fn similar_opcodes(begin: &Machine, end: &Machine, code: &[usize; 4]) -> usize {
-    let ops = [Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori, Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri, Eqrr];
-    ops.iter().filter(|&op| similar_to(op, begin, end, code)).count()
+fn similar_opcodes(begin: &SampleRegs, end: &SampleRegs, code: &[usize; 4]) -> usize {
+    OPCODES.iter().filter(|&op| similar_to(op, begin, end, code)).count()
 }
 
-type Sample = (Machine, Machine, [usize; 4]);
+type Sample = (SampleRegs, SampleRegs, [usize; 4]);
 
 fn behave_like_3_or_more(samples: &[Sample]) -> usize {
     samples.iter().filter(|sample| similar_opcodes(&sample.0, &sample.1, &sample.2) >= 3).count()
@@ -104,13 +53,11 @@ fn unique_match(i: usize, samples: &[Sample], ops_remaining: &HashSet<Opcode>) -
     found
 }
 
-type Coding = [Opcode; OPCODES];
+type Coding = [Opcode; OPCODES.len()];
 
 fn deduce_coding(samples: &[Sample]) -> Coding {
-    let mut ops_remaining: HashSet<Opcode> = [
-        Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori, Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri, Eqrr
-    ].into_iter().collect();
-    let mut found_codes = [None; OPCODES];
+    let mut ops_remaining: HashSet<Opcode> = OPCODES.into_iter().collect();
+    let mut found_codes = [None; OPCODES.len()];
     while !ops_remaining.is_empty() {
         for (i, mapping) in found_codes.iter_mut().enumerate().filter(|(_, m)| m.is_none()) {
             if let Some(op) = unique_match(i, samples, &ops_remaining) {
@@ -120,26 +67,23 @@ fn deduce_coding(samples: &[Sample]) -> Coding {
         }
     }
 
-    let mut unwrapped = [Addr; OPCODES];
+    let mut unwrapped = [Opcode::Addr; OPCODES.len()];
     for (found, ret) in found_codes.iter().zip(unwrapped.iter_mut()) {
         *ret = found.unwrap();
     }
     unwrapped
 }
 
-fn execute(mach: &mut Machine, program: &[Instruction]) {
-    for inst in program {
-        println!("{:?}", mach.regs);
-        step_instruction(mach, inst);
-    }
-}
-
 fn reg_zero(program: &[Instruction]) -> u32 {
-    let mut mach = Machine { regs: [0; 4] };
-    execute(&mut mach, program);
-    println!("{:?}", mach.regs);
+    let mut mach = Machine::new(program, [0u32; 4]);
+    loop {
+        println!("{:?}", mach.state);
+        if mach.step().is_some() {
+            break;
+        }
+    }
 
-    mach.regs[0]
+    mach.state[0]
 }
 
 fn into_quad<T: std::convert::From<u8> + std::marker::Copy + std::str::FromStr>(sp: std::str::Split<'_, &str>) -> [T; 4] {
@@ -153,14 +97,14 @@ fn into_quad<T: std::convert::From<u8> + std::marker::Copy + std::str::FromStr>(
     out
 }
 
-fn parse_machine(line: &str) -> Machine {
+fn parse_machine(line: &str) -> SampleRegs {
     let regs0 = line.split("[").nth(1).unwrap();
     let regs_str = regs0.split("]").nth(0).unwrap();
     let regs = into_quad(regs_str.split(", "));
-    Machine { regs: regs }
+    SampleRegs { regs: regs }
 }
 
-fn parse_sample(inp: &mut Lines<BufReader<File>>) -> Option<Sample> {
+fn parse_sample(inp: &mut Lines<Box<dyn BufRead>>) -> Option<Sample> {
     let line = inp.next().unwrap().unwrap();
     if line == "" {
         // Two extra lines between samples and test program. Skip the other too
@@ -182,7 +126,7 @@ fn parse_sample(inp: &mut Lines<BufReader<File>>) -> Option<Sample> {
     }
 }
 
-fn parse_samples(input: &mut Lines<BufReader<File>>) -> Vec<Sample> {
+fn parse_samples(input: &mut Lines<Box<dyn BufRead>>) -> Vec<Sample> {
     let mut out = Vec::new();
     while let Some(next) = parse_sample(input) {
         out.push(next);
@@ -190,7 +134,7 @@ fn parse_samples(input: &mut Lines<BufReader<File>>) -> Vec<Sample> {
     out
 }
 
-fn parse_program(input: &mut Lines<BufReader<File>>, coding: &Coding) -> Vec<Instruction> {
+fn parse_program(input: &mut Lines<Box<dyn BufRead>>, coding: &Coding) -> Vec<Instruction> {
     let mut program = Vec::new();
     for line in input.map(|lopt| lopt.unwrap()) {
         let instcode = into_quad(line.split(" "));
@@ -205,11 +149,12 @@ fn parse_program(input: &mut Lines<BufReader<File>>, coding: &Coding) -> Vec<Ins
 }
 
 fn main() {
-    let mut input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines();
+    let _session = answer::start();
+    let mut input = input::reader().lines();
     let samples = parse_samples(&mut input);
-    println!("{}", behave_like_3_or_more(&samples));
+    answer::part1(behave_like_3_or_more(&samples));
 
     let coding = deduce_coding(&samples);
     let test_program = parse_program(&mut input, &coding);
-    println!("{}", reg_zero(&test_program));
+    answer::part2(reg_zero(&test_program));
 }
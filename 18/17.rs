@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // x0, x1, y0, y1 (inclusive)
@@ -218,7 +220,8 @@ fn pour(g: &mut Ground) -> (usize, usize) {
 }
 
 fn main() {
-    let veins = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let veins = input::reader()
         .lines().map(|l| parse_line(&l.unwrap())).collect::<Vec<_>>();
     let mut ground = mapscan(&veins);
     if false {
@@ -228,7 +231,8 @@ fn main() {
     if false {
         dunp(&ground);
     }
-    println!("{:?}", score);
+    answer::part1(score.0);
+    answer::part2(score.1);
     if false {
         dump_ppm(&ground);
     }
@@ -1,5 +1,16 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/cycle.rs"]
+mod cycle;
+use cycle::History;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{Map, DIRS8, add};
+#[path = "../lib/life.rs"]
+mod life;
+use life::Life;
 use std::io::BufRead;
 
 const OPEN: char = '.';
@@ -14,8 +25,8 @@ fn parse_line(input: &str) -> Vec<char> {
     input.chars().collect()
 }
 
-fn dunp(map: &[Vec<char>]) {
-    for row in map {
+fn dunp(map: &Map) {
+    for row in &map.0 {
         for tile in row {
             print!("{}", tile);
         }
@@ -24,30 +35,10 @@ fn dunp(map: &[Vec<char>]) {
     println!("");
 }
 
-
-fn neighbourhood(map: &[Vec<char>], x: usize, y: usize) -> (usize, usize) {
-    let mut trees = 0;
-    let mut yards = 0;
-    let default_row = Vec::new();
-    let default_yard = '.';
-    for &(xi, yi) in &[
-        (x - 1, y - 1), (x, y - 1), (x + 1, y - 1),
-        (x - 1, y    ),             (x + 1, y    ),
-        (x - 1, y + 1), (x, y + 1), (x + 1, y + 1)
-    ] {
-        match *map.get(yi).unwrap_or_else(|| &default_row).get(xi).unwrap_or_else(|| &default_yard) {
-            TREE => trees += 1,
-            YARD => yards += 1,
-            OPEN => (),
-            _ => unreachable!()
-        }
-    }
-    (trees, yards)
-}
-
-fn change(map: &[Vec<char>], x: usize, y: usize) -> char {
-    let (trees, yards) = neighbourhood(map, x, y);
-    match map[y][x] {
+fn change(tile: char, neighbours: &[char]) -> char {
+    let trees = neighbours.iter().filter(|&&t| t == TREE).count();
+    let yards = neighbours.iter().filter(|&&t| t == YARD).count();
+    match tile {
         OPEN => if trees >= 3 { TREE } else { OPEN },
         TREE => if yards >= 3 { YARD } else { TREE },
         YARD => if yards >= 1 && trees >= 1 { YARD } else { OPEN },
@@ -55,17 +46,9 @@ fn change(map: &[Vec<char>], x: usize, y: usize) -> char {
     }
 }
 
-fn magic(map: &[Vec<char>], next: &mut [Vec<char>]) {
-    for (y, row) in next.iter_mut().enumerate() {
-        for (x, tile) in row.iter_mut().enumerate() {
-            *tile = change(map, x, y);
-        }
-    }
-}
-
-fn score(map: &[Vec<char>]) -> usize {
-    let trees = map.iter().flat_map(|row| row.iter().filter(|&&tile| tile == TREE)).count();
-    let lumberyards = map.iter().flat_map(|row| row.iter().filter(|&&tile| tile == YARD)).count();
+fn score(map: &Map) -> usize {
+    let trees = map.find_all(TREE).count();
+    let lumberyards = map.find_all(YARD).count();
 
     //println!("{} * {} = {}", trees, lumberyards, trees * lumberyards);
     //println!("");
@@ -73,64 +56,35 @@ fn score(map: &[Vec<char>]) -> usize {
     trees * lumberyards
 }
 
-fn animate(map: &mut Vec<Vec<char>>, n: usize) -> usize {
-    let w = map[0].len();
-    let h = map.len();
-
-    let mut next = vec![vec!['?'; w]; h];
+fn animate(map: &Map, n: usize) -> usize {
+    // outside is open land
+    let mut life = Life::new(map.clone(), OPEN, |p| DIRS8.into_iter().map(move |d| add(p, d)));
 
     for i in 1..=n {
-        magic(map, &mut next);
-        for (ro, ri) in map.iter_mut().zip(next.iter()) {
-            ro.copy_from_slice(ri);
-        }
+        life.step(|_, tile, neighbours| change(tile, neighbours));
         println!("After {} minutes:", i);
-        dunp(map);
+        dunp(&life.world);
         println!("");
     }
 
-    score(map)
+    score(&life.world)
 }
 
-fn cycle_len(v: &Vec<usize>) -> usize {
-    for len in 1.. {
-        let a = v.iter().rev();
-        let b = v.iter().rev().skip(len);
-        if a.zip(b).take(len).all(|(a, b)| a == b) {
-            return len;
-        }
-    }
-    unreachable!()
-}
-
-fn cycledetect(map: &mut Vec<Vec<char>>, n: usize) -> usize {
-    let w = map[0].len();
-    let h = map.len();
-    let stabilize_time = 1000;
-    let mut history = Vec::new();
-
-    let mut next = vec![vec!['?'; w]; h];
-
-    for _ in 0..=stabilize_time {
-        magic(map, &mut next);
-        for (ro, ri) in map.iter_mut().zip(next.iter()) {
-            ro.copy_from_slice(ri);
-        }
-        history.push(score(map));
+fn cycledetect(map: &Map, n: usize) -> usize {
+    let mut life = Life::new(map.clone(), OPEN, |p| DIRS8.into_iter().map(move |d| add(p, d)));
+    let mut history = History::new();
+    while history.feed(life.world.clone()).is_none() {
+        life.step(|_, tile, neighbours| change(tile, neighbours));
     }
-    let cycle = cycle_len(&history);
-    println!("c: {}", cycle);
-    // After big enough a, any f(a) + f(a + b * C) for integer b.
-    // For big n = a + b * C, find reasonable b to get 1000 - C <= n + b * C < 1000
-    let b = (n - stabilize_time + cycle-1) / cycle; // round down to go safely under 1000 if exact match
-    let a = n - b * cycle - 1;
-    history[a]
+    println!("c: {}", history.repeat.unwrap().period);
+    score(history.extrapolate_to(n))
 }
 
 fn main() {
-    let mut map = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
-        .lines().map(|l| parse_line(&l.unwrap())).collect::<Vec<_>>();
+    let _session = answer::start();
+    let map = Map::new(input::reader()
+        .lines().map(|l| parse_line(&l.unwrap())).collect::<Vec<_>>());
     dunp(&map);
-    println!("{}", animate(&mut map.clone(), 10));
-    println!("{}", cycledetect(&mut map, 1000000000));
+    answer::part1(animate(&map, 10));
+    answer::part2(cycledetect(&map, 1000000000));
 }
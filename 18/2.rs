@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn exactly_n(id: &str, n: usize) -> bool {
@@ -35,11 +37,12 @@ fn proto_fabric_boxes(ids: &[String]) -> String {
 }
 
 fn main() {
-    let ids = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let ids = input::reader()
         .lines().map(|x| x.unwrap()).collect::<Vec<_>>();
 
     let twos = ids.iter().filter(|x| exactly_n(x, 2)).count();
     let threes = ids.iter().filter(|x| exactly_n(x, 3)).count();
-    println!("{}", twos * threes);
-    println!("{}", proto_fabric_boxes(&ids));
+    answer::part1(twos * threes);
+    answer::part2(proto_fabric_boxes(&ids));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::HashMap;
@@ -147,7 +149,8 @@ fn faraway_rooms(g: &Graph) -> usize {
 }
 
 fn main() {
-    let guide = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let guide = input::reader()
         .lines().map(|l| l.unwrap().chars().collect::<Vec<char>>()).collect::<Vec<_>>();
     for regex in &guide {
         let mut g = Graph { n: Nodes::new(), e: Edges::new() };
@@ -161,7 +164,7 @@ fn main() {
             }
         }
         render(&g);
-        println!("{}", furthest_room(&g));
-        println!("{}", faraway_rooms(&g));
+        answer::part1(furthest_room(&g));
+        answer::part2(faraway_rooms(&g));
     }
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::collections::HashSet;
 
 #[allow(dead_code)]
@@ -96,16 +98,18 @@ fn l6(r0: i64) {
     }
 }
 
-fn find_cycle() -> i64 {
+// the first value to halt on gives the fewest instructions and the last one before a repeat the most
+fn find_cycle() -> (i64, i64) {
     let mut r2: i64;
     let mut r4: i64 = 0;
     let mut seen = HashSet::new();
     let mut prev = 0;
+    let mut first = None;
 
     for _i in 0.. {
         if seen.contains(&r4) {
             // starts to repeat here; the previous value must be the end of the cycle
-            return prev;
+            return (first.unwrap(), prev);
         } else {
             seen.insert(r4);
         }
@@ -134,10 +138,14 @@ fn find_cycle() -> i64 {
         r4 &= 0xffffff;
         r4 *= 65899;
         r4 &= 0xffffff;
+        first.get_or_insert(r4);
     }
     unreachable!()
 }
 
 fn main() {
-    println!("{}", find_cycle());
+    let _session = answer::start();
+    let (first, last) = find_cycle();
+    answer::part1(first);
+    answer::part2(last);
 }
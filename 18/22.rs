@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 use std::io::Lines;
 
@@ -196,7 +198,7 @@ fn shortest(cave: &Cave) -> usize {
     dists[d_idx(cave.tx, cave.ty, Torch)].unwrap()
 }
 
-fn parse_cave(input: &mut Lines<BufReader<File>>) -> Cave {
+fn parse_cave(input: &mut Lines<Box<dyn BufRead>>) -> Cave {
     /*
      * depth: 11739
      * target: 11,718
@@ -212,9 +214,10 @@ fn parse_cave(input: &mut Lines<BufReader<File>>) -> Cave {
 }
 
 fn main() {
-    let mut input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines();
+    let _session = answer::start();
+    let mut input = input::reader().lines();
     let cave = parse_cave(&mut input);
     println!("{:?}", cave);
-    println!("{}", total_risk(&cave));
-    println!("{}", shortest(&cave));
+    answer::part1(total_risk(&cave));
+    answer::part2(shortest(&cave));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 use std::io::Lines;
 
@@ -140,7 +142,7 @@ fn parse_group(line: &str) -> Group {
     }
 }
 
-fn parse_team(input: &mut Lines<BufReader<File>>) -> Vec<Group> {
+fn parse_team(input: &mut Lines<Box<dyn BufRead>>) -> Vec<Group> {
     let parse_line = |line: String| {
         if line != "" {
             Some(parse_group(&line))
@@ -275,7 +277,7 @@ fn play_immu_optimize(game: &Game) -> i32 {
     }
 }
 
-fn parse_game(input: &mut Lines<BufReader<File>>) -> Game {
+fn parse_game(input: &mut Lines<Box<dyn BufRead>>) -> Game {
     let immu_title = input.next().unwrap().unwrap();
     assert!(immu_title == "Immune System:");
     let immu_army = parse_team(input);
@@ -287,8 +289,9 @@ fn parse_game(input: &mut Lines<BufReader<File>>) -> Game {
 }
 
 fn main() {
-    let mut input = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap()).lines();
+    let _session = answer::start();
+    let mut input = input::reader().lines();
     let game = parse_game(&mut input);
-    println!("{}", play_winner_score(&game));
-    println!("{}", play_immu_optimize(&game));
+    answer::part1(play_winner_score(&game));
+    answer::part2(play_immu_optimize(&game));
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 type Point = (i32, i32, i32, i32);
@@ -82,7 +84,8 @@ fn constellation_count(world: &mut Constellation) -> usize {
 }
 
 fn main() {
-    let mut points = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let mut points = input::reader()
         .lines().map(|l| parse_line(&l.unwrap())).collect::<Vec<_>>();
-    println!("{:?}", constellation_count(&mut points));
+    answer::part1(constellation_count(&mut points));
 }
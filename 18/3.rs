@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 extern crate regex;
@@ -17,8 +19,9 @@ fn parse_line(re: &Regex, line: &str) -> (usize, usize, usize, usize, usize) {
 }
 
 fn main() {
+    let _session = answer::start();
     let re = Regex::new(r"#(\d+) @ (\d+),(\d+): (\d+)x(\d+)").unwrap();
-    let claims = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let claims = input::reader()
         .lines().map(|x| parse_line(&re, &x.unwrap())).collect::<Vec<_>>();
     let mut fabric = vec![0; 1000 * 1000];
     for &c in &claims {
@@ -29,7 +32,7 @@ fn main() {
             }
         }
     }
-    println!("{}", fabric.iter().filter(|&&x| x > 1).count());
+    answer::part1(fabric.iter().filter(|&&x| x > 1).count());
     for &c in &claims {
         let (id, x0, y0, w, h) = c;
         let mut n = 0;
@@ -39,7 +42,7 @@ fn main() {
             }
         }
         if n == w * h {
-            println!("{}", id);
+            answer::part2(id);
         }
     }
 }
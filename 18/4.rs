@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 use std::collections::HashMap;
@@ -99,9 +101,10 @@ fn sneaky_time(log: &[Record], sleepy_man: u32) -> (u32, u32) {
 }
 
 fn main() {
+    let _session = answer::start();
     let re = Regex::new(r"\[(\d\d\d\d)-(\d\d)-(\d\d) (\d\d):(\d\d)\] (Guard #(\d+) begins shift|falls asleep|wakes up)").unwrap();
 
-    let mut scribble = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let mut scribble = input::reader()
         .lines().map(|x| x.unwrap()).collect::<Vec<_>>();
     scribble.sort();
 
@@ -114,12 +117,13 @@ fn main() {
     let sleepy_man = worst_employee(&log);
     let minute = sneaky_time(&log, sleepy_man).0;
 
-    println!("{}", sleepy_man * minute);
+    answer::part1(sleepy_man * minute);
 
     let (_, time, guard) = log.iter().map( |e| {
         let (minute, nap) = sneaky_time(&log, e.guard);
         (nap, minute, e.guard)
     }).max().unwrap();
 
-    println!("{} {} {}", time, guard, time * guard);
+    println!("{} {}", time, guard);
+    answer::part2(time * guard);
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn first_pair(polymer: &str) -> Option<usize> {
@@ -26,15 +28,19 @@ fn react(polymer: &mut String) {
 }
 
 fn main() {
-    let polymer = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let polymer = input::reader()
         .lines().next().unwrap().unwrap();
     let mut orig_reacted = polymer.clone();
     react(&mut orig_reacted);
-    println!("{} {}", orig_reacted.len(), orig_reacted);
+    println!("{}", orig_reacted);
+    answer::part1(orig_reacted.len());
 
-    println!("{:?}", (b'a'..=b'z').map(|c| {
+    let (shortest, unit) = (b'a'..=b'z').map(|c| {
         let mut mutant = polymer.replace(c as char, "").replace((c - 32) as char, "");
         react(&mut mutant);
         (mutant.len(), c as char)
-    }).min().unwrap());
+    }).min().unwrap();
+    println!("without {}", unit);
+    answer::part2(shortest);
 }
@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 fn parse(line: &str) -> (i32, i32) {
@@ -52,7 +54,8 @@ fn friendly_region(coords: &[(i32, i32)], x0: i32, y0: i32, w: i32, h: i32) -> i
 }
 
 fn main() {
-    let coords = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let coords = input::reader()
         .lines().map(|x| parse(&x.unwrap())).collect::<Vec<_>>();
     let x0 = coords.iter().map(|c| c.0).min().unwrap();
     let x1 = coords.iter().map(|c| c.0).max().unwrap();
@@ -66,8 +69,8 @@ fn main() {
     println!("{:?}", counts);
     // hmm, cheating: happened to get the first part right even without checking whether this area
     // is infinite (i.e., on the border of the map just right)
-    println!("{}", counts.iter().max().unwrap());
+    answer::part1(counts.iter().max().unwrap());
     // the region is probably nicely contiguous
     let blotch = friendly_region(&coords, x0, y0, w, h);
-    println!("{}", blotch);
+    answer::part2(blotch);
 }
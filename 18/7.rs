@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // "Step A must be finished before step B can begin."
@@ -92,10 +94,11 @@ fn parallel_order_time(rules: &[(usize, usize)], min_time: usize, worker_count:
 }
 
 fn main() {
-    let rules = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let rules = input::reader()
         .lines().map(|x| parse_rule(&x.unwrap())).collect::<Vec<_>>();
 
-    println!("{}", ideal_order(&rules));
+    answer::part1(ideal_order(&rules));
     // println!("{}", parallel_order_time(&rules, 1, 2)); // also fix 'z' -> 'f'
-    println!("{}", parallel_order_time(&rules, 61, 5));
+    answer::part2(parallel_order_time(&rules, 61, 5));
 }
@@ -1,6 +1,8 @@
 #![recursion_limit="10"]
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 use std::slice::Iter;
 
@@ -64,11 +66,12 @@ fn node_value(v: &mut Iter<u32>) -> u32 {
 }
 
 fn main() {
-    let file = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let file = input::reader()
         .lines().next().unwrap().unwrap();
     let license = file.split(" ").map(|x| x.parse::<u32>().unwrap()).collect::<Vec<_>>();
 
-    println!("{}", metadata_sum(&mut license.iter()));
-    println!("{}", node_value(&mut license.iter()));
+    answer::part1(metadata_sum(&mut license.iter()));
+    answer::part2(node_value(&mut license.iter()));
 }
 
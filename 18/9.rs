@@ -1,5 +1,7 @@
-use std::fs::File;
-use std::io::BufReader;
+#[path = "../lib/input.rs"]
+mod input;
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::BufRead;
 
 // "10 players; last marble is worth 25 points"
@@ -66,13 +68,15 @@ fn play(players: usize, length: usize) -> usize {
 }
 
 fn main() {
-    let games = BufReader::new(File::open(&std::env::args().nth(1).unwrap()).unwrap())
+    let _session = answer::start();
+    let games = input::reader()
         .lines().map(|x| parse_configuration(&x.unwrap())).collect::<Vec<_>>();
 
-    for g in &games {
-        let winning_score = play(g.0, g.1);
+    let scores = games.iter().map(|g| play(g.0, g.1)).collect::<Vec<_>>();
+    for (g, winning_score) in games.iter().zip(&scores) {
         println!("{:?} {}", g, winning_score);
     }
+    answer::part1(scores[0]);
     let winning_score_100 = play(games[0].0, 100 * games[0].1);
-    println!("{}", winning_score_100);
+    answer::part2(winning_score_100);
 }
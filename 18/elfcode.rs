@@ -0,0 +1,135 @@
+// The opcodes of the wrist device from days 16, 19 and 21. Day 16 gets them as numbers and has to
+// work out which is which, the later days have them written out and bind the program counter to a
+// register.
+#![allow(dead_code)]
+
+use crate::regmachine::{self, Effect, Execute, Parser, Pc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Opcode {
+    Addr,
+    Addi,
+    Mulr,
+    Muli,
+    Banr,
+    Bani,
+    Borr,
+    Bori,
+    Setr,
+    Seti,
+    Gtir,
+    Gtri,
+    Gtrr,
+    Eqir,
+    Eqri,
+    Eqrr,
+}
+use Opcode::*;
+
+pub const OPCODES: [Opcode; 16] = [
+    Addr, Addi, Mulr, Muli, Banr, Bani, Borr, Bori, Setr, Seti, Gtir, Gtri, Gtrr, Eqir, Eqri, Eqrr
+];
+
+#[derive(Debug)]
+pub struct OpParseError {}
+
+impl std::str::FromStr for Opcode {
+    type Err = OpParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "addr" => Ok(Addr),
+            "addi" => Ok(Addi),
+            "mulr" => Ok(Mulr),
+            "muli" => Ok(Muli),
+            "banr" => Ok(Banr),
+            "bani" => Ok(Bani),
+            "borr" => Ok(Borr),
+            "bori" => Ok(Bori),
+            "setr" => Ok(Setr),
+            "seti" => Ok(Seti),
+            "gtir" => Ok(Gtir),
+            "gtri" => Ok(Gtri),
+            "gtrr" => Ok(Gtrr),
+            "eqir" => Ok(Eqir),
+            "eqri" => Ok(Eqri),
+            "eqrr" => Ok(Eqrr),
+            _ => Err(Self::Err {})
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub in_a: usize,
+    pub in_b: usize,
+    pub out: usize,
+}
+
+pub fn step_instruction(regs: &mut [u32], inst: &Instruction) {
+    let ai = inst.in_a as u32;
+    let bi = inst.in_b as u32;
+    // or 0: irrelevant for immediate opcodes
+    let ar = *regs.get(inst.in_a).unwrap_or(&0);
+    let br = *regs.get(inst.in_b).unwrap_or(&0);
+    regs[inst.out] = match inst.opcode {
+        Addr => ar + br,
+        Addi => ar + bi,
+        Mulr => ar * br,
+        Muli => ar * bi,
+        Banr => ar & br,
+        Bani => ar & bi,
+        Borr => ar | br,
+        Bori => ar | bi,
+        Setr => ar,
+        Seti => ai,
+        Gtir => if ai > br { 1 } else { 0 },
+        Gtri => if ar > bi { 1 } else { 0 },
+        Gtrr => if ar > br { 1 } else { 0 },
+        Eqir => if ai == br { 1 } else { 0 },
+        Eqri => if ar == bi { 1 } else { 0 },
+        Eqrr => if ar == br { 1 } else { 0 },
+    };
+}
+
+// straight through, no program counter in the registers
+impl<const N: usize> Execute<[u32; N]> for Instruction {
+    type Event = ();
+
+    fn execute(&self, regs: &mut [u32; N], _pc: Pc) -> Effect<()> {
+        step_instruction(regs, self);
+        Effect::Next
+    }
+}
+
+#[derive(Debug)]
+pub struct Cpu {
+    pub regs: [u32; 6],
+    pub ip_reg: usize,
+}
+
+// the instruction pointer lives in a register while an instruction runs, so writing there jumps
+impl Execute<Cpu> for Instruction {
+    type Event = ();
+
+    fn execute(&self, cpu: &mut Cpu, pc: Pc) -> Effect<()> {
+        cpu.regs[cpu.ip_reg] = pc as u32;
+        step_instruction(&mut cpu.regs, self);
+        cpu.regs[cpu.ip_reg] += 1;
+        Effect::Goto(cpu.regs[cpu.ip_reg] as Pc)
+    }
+}
+
+// "addi 1 2 3"
+pub fn parser() -> Parser<'static, Instruction> {
+    use regmachine::{map, num, pair, triple};
+    map(pair(num(), triple(num(), num(), num())),
+        |(opcode, (in_a, in_b, out))| Instruction { opcode, in_a, in_b, out })
+}
+
+// "#ip 3"
+pub fn parse_ip(line: &str) -> usize {
+    assert!(line.as_bytes()[0] == b'#');
+    line.split(' ').nth(1).unwrap().parse().unwrap()
+}
@@ -0,0 +1,509 @@
+// Elfcode to Rust, so that the next puzzle like days 19 and 21 doesn't need translating by hand.
+// Writes to the ip register are the jumps; they split the program in blocks, and the blocks are
+// laid out as nested loops and labeled blocks along the dominator tree, the same way a compiler to
+// a structured target would. If the control flow can't be structured like that, or a jump goes
+// somewhere that can't be listed, the output is a plain loop over a match on the ip instead.
+//
+// transpile [-n a,b,c,ip,d,e] < program
+#[path = "../lib/regmachine.rs"]
+mod regmachine;
+mod elfcode;
+use elfcode::{Instruction, Opcode};
+use elfcode::Opcode::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::env;
+use std::io::{self, BufRead};
+use std::process;
+
+const REGS: usize = 6;
+
+// (a is a register, b is a register or None if not used)
+fn operand_kinds(opcode: Opcode) -> (bool, Option<bool>) {
+    match opcode {
+        Addr | Mulr | Banr | Borr | Gtrr | Eqrr => (true, Some(true)),
+        Addi | Muli | Bani | Bori | Gtri | Eqri => (true, Some(false)),
+        Gtir | Eqir => (false, Some(true)),
+        Setr => (true, None),
+        Seti => (false, None),
+    }
+}
+
+fn is_comparison(opcode: Opcode) -> bool {
+    matches!(opcode, Gtir | Gtri | Gtrr | Eqir | Eqri | Eqrr)
+}
+
+fn eval(opcode: Opcode, a: i64, b: i64) -> i64 {
+    match opcode {
+        Addr | Addi => a + b,
+        Mulr | Muli => a * b,
+        Banr | Bani => a & b,
+        Borr | Bori => a | b,
+        Setr | Seti => a,
+        Gtir | Gtri | Gtrr => (a > b) as i64,
+        Eqir | Eqri | Eqrr => (a == b) as i64,
+    }
+}
+
+struct Names {
+    regs: Vec<String>,
+    ip: usize,
+}
+
+impl Names {
+    // as code; the ip register reads as the address of the instruction, and a register that
+    // doesn't exist as 0 like the interpreter does
+    fn reg(&self, r: usize, pc: usize) -> String {
+        if r == self.ip {
+            pc.to_string()
+        } else if r < REGS {
+            self.regs[r].clone()
+        } else {
+            "0".to_string()
+        }
+    }
+
+    fn operands(&self, inst: &Instruction, pc: usize) -> (String, String) {
+        let (a_reg, b_reg) = operand_kinds(inst.opcode);
+        // masks read better in hex
+        let imm = |n: usize| if matches!(inst.opcode, Bani | Bori) && n >= 0x100 {
+            format!("0x{:x}", n)
+        } else {
+            n.to_string()
+        };
+        let a = if a_reg { self.reg(inst.in_a, pc) } else { imm(inst.in_a) };
+        let b = match b_reg {
+            Some(true) => self.reg(inst.in_b, pc),
+            _ => imm(inst.in_b),
+        };
+        (a, b)
+    }
+
+    fn expr(&self, inst: &Instruction, pc: usize) -> String {
+        let (a, b) = self.operands(inst, pc);
+        match inst.opcode {
+            Addr | Addi => format!("{} + {}", a, b),
+            Mulr | Muli => format!("{} * {}", a, b),
+            Banr | Bani => format!("{} & {}", a, b),
+            Borr | Bori => format!("{} | {}", a, b),
+            Setr | Seti => a,
+            Gtir | Gtri | Gtrr => format!("({} > {}) as i64", a, b),
+            Eqir | Eqri | Eqrr => format!("({} == {}) as i64", a, b),
+        }
+    }
+
+    fn statement(&self, inst: &Instruction, pc: usize) -> String {
+        let out = &self.regs[inst.out];
+        let (a, b) = self.operands(inst, pc);
+        let op = match inst.opcode {
+            Addr | Addi => Some("+"),
+            Mulr | Muli => Some("*"),
+            Banr | Bani => Some("&"),
+            Borr | Bori => Some("|"),
+            _ => None,
+        };
+        let b_is_reg = operand_kinds(inst.opcode).1 == Some(true);
+        match op {
+            Some(op) if a == *out => format!("{} {}= {};", out, op, b),
+            Some(op) if b_is_reg && b == *out => format!("{} {}= {};", out, op, a),
+            _ => format!("{} = {};", out, self.expr(inst, pc)),
+        }
+    }
+}
+
+type Target = Option<usize>;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Exit {
+    Goto(Target),
+    // condition, where to if it holds, where to if not
+    Branch(String, Target, Target),
+    // the next ip is this register plus a constant: the values that land in the program
+    Switch(String, i64, Vec<(i64, usize)>),
+    // the next ip as an expression, for the fallback only
+    Computed(String),
+}
+
+impl Exit {
+    fn targets(&self) -> Vec<usize> {
+        match self {
+            Exit::Goto(t) => t.iter().copied().collect(),
+            Exit::Branch(_, t, f) => t.iter().chain(f).copied().collect(),
+            Exit::Switch(_, _, arms) => arms.iter().map(|&(_, t)| t).collect(),
+            Exit::Computed(_) => Vec::new(),
+        }
+    }
+}
+
+struct Block {
+    end: usize,
+    statements: Vec<String>,
+    exit: Exit,
+}
+
+fn target(ip: i64, len: usize) -> Target {
+    if ip >= 0 && (ip as usize) < len { Some(ip as usize) } else { None }
+}
+
+// where a write to the ip register goes; bools are the registers known to hold a comparison
+fn exit_of(inst: &Instruction, pc: usize, len: usize, names: &Names, bools: &HashMap<usize, String>) -> Exit {
+    let (a_reg, b_reg) = operand_kinds(inst.opcode);
+    let known = |is_reg: bool, v: usize| match is_reg {
+        true if v == names.ip => Ok(pc as i64),
+        true if v >= REGS => Ok(0),
+        true => Err(v),
+        false => Ok(v as i64),
+    };
+    let a = known(a_reg, inst.in_a);
+    let b = b_reg.map_or(Ok(0), |b_reg| known(b_reg, inst.in_b));
+    let linear = match (inst.opcode, a, b) {
+        (_, Ok(a), Ok(b)) => return Exit::Goto(target(eval(inst.opcode, a, b) + 1, len)),
+        (Addr, Err(x), Ok(k)) | (Addr, Ok(k), Err(x)) | (Addi, Err(x), Ok(k)) => Some((x, k)),
+        (Setr, Err(x), _) => Some((x, 0)),
+        _ => None,
+    };
+    match linear {
+        Some((x, k)) => match bools.get(&x) {
+            Some(cond) => Exit::Branch(cond.clone(), target(k + 2, len), target(k + 1, len)),
+            None => {
+                // registers are unsigned in the interpreter
+                let arms = (0..len).map(|t| (t as i64 - k - 1, t)).filter(|&(value, _)| value >= 0).collect();
+                Exit::Switch(names.regs[x].clone(), k + 1, arms)
+            },
+        },
+        None => Exit::Computed(format!("{} + 1", names.expr(inst, pc))),
+    }
+}
+
+// the exits of each ip write, knowing where blocks start
+fn exits(program: &[Instruction], names: &Names, leaders: &BTreeSet<usize>) -> BTreeMap<usize, Exit> {
+    let mut exits = BTreeMap::new();
+    let mut bools: HashMap<usize, String> = HashMap::new();
+    for (pc, inst) in program.iter().enumerate() {
+        if leaders.contains(&pc) {
+            bools.clear();
+        }
+        if inst.out == names.ip {
+            exits.insert(pc, exit_of(inst, pc, program.len(), names, &bools));
+            continue;
+        }
+        // whatever mentions the written register is stale now
+        let written = &names.regs[inst.out];
+        bools.retain(|&r, cond| r != inst.out && !cond.split(|c: char| !c.is_alphanumeric()).any(|w| w == written));
+        if is_comparison(inst.opcode) {
+            let cond = names.expr(inst, pc);
+            let cond = cond.trim_start_matches('(').trim_end_matches(") as i64").to_string();
+            let mentions_itself = cond.split(|c: char| !c.is_alphanumeric()).any(|w| w == written);
+            bools.insert(inst.out, if mentions_itself { format!("{} != 0", written) } else { cond });
+        }
+    }
+    exits
+}
+
+fn blocks(program: &[Instruction], names: &Names) -> BTreeMap<usize, Block> {
+    // a switch has more targets than a branch, and more leaders can turn branches into switches
+    let mut leaders = BTreeSet::from([0]);
+    let exits = loop {
+        let exits = exits(program, names, &leaders);
+        let mut more = leaders.clone();
+        for (&pc, exit) in &exits {
+            more.extend(exit.targets());
+            more.insert(pc + 1);
+        }
+        more.retain(|&pc| pc < program.len());
+        if more == leaders {
+            break exits;
+        }
+        leaders = more;
+    };
+
+    leaders.iter().map(|&start| {
+        let mut statements = Vec::new();
+        let mut pc = start;
+        let exit = loop {
+            if let Some(exit) = exits.get(&pc) {
+                break exit.clone();
+            }
+            statements.push(names.statement(&program[pc], pc));
+            if pc + 1 == program.len() || leaders.contains(&(pc + 1)) {
+                break Exit::Goto(target(pc as i64 + 1, program.len()));
+            }
+            pc += 1;
+        };
+        (start, Block { end: pc, statements, exit })
+    }).collect()
+}
+
+// reverse postorder from the entry, then the immediate dominators as in Cooper, Harvey and Kennedy
+fn dominators(blocks: &BTreeMap<usize, Block>) -> (Vec<usize>, HashMap<usize, usize>) {
+    let mut postorder = Vec::new();
+    let mut visited = HashSet::from([0]);
+    let mut stack = vec![(0, blocks[&0].exit.targets(), 0)];
+    while let Some((node, succs, i)) = stack.last_mut() {
+        if let Some(&next) = succs.get(*i) {
+            *i += 1;
+            if visited.insert(next) {
+                stack.push((next, blocks[&next].exit.targets(), 0));
+            }
+        } else {
+            postorder.push(*node);
+            stack.pop();
+        }
+    }
+    let rpo = postorder.into_iter().rev().collect::<Vec<_>>();
+    let index = rpo.iter().enumerate().map(|(i, &b)| (b, i)).collect::<HashMap<_, _>>();
+
+    let mut preds: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &b in &rpo {
+        for t in blocks[&b].exit.targets() {
+            preds.entry(t).or_default().push(b);
+        }
+    }
+    let mut idom = HashMap::from([(0, 0)]);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &b in &rpo[1..] {
+            let mut new = None;
+            for &p in preds[&b].iter().filter(|p| idom.contains_key(p)) {
+                new = Some(match new {
+                    None => p,
+                    Some(mut q) => {
+                        let mut p = p;
+                        while p != q {
+                            while index[&p] > index[&q] { p = idom[&p]; }
+                            while index[&q] > index[&p] { q = idom[&q]; }
+                        }
+                        p
+                    },
+                });
+            }
+            if idom.get(&b) != new.as_ref() {
+                idom.insert(b, new.unwrap());
+                changed = true;
+            }
+        }
+    }
+    (rpo, idom)
+}
+
+fn dominates(idom: &HashMap<usize, usize>, a: usize, mut b: usize) -> bool {
+    loop {
+        if a == b {
+            return true;
+        }
+        if b == 0 {
+            return false;
+        }
+        b = idom[&b];
+    }
+}
+
+struct Emitter<'a> {
+    blocks: &'a BTreeMap<usize, Block>,
+    index: HashMap<usize, usize>,
+    children: HashMap<usize, Vec<usize>>,
+    headers: HashSet<usize>,
+    merges: HashSet<usize>,
+    out: Vec<String>,
+    depth: usize,
+}
+
+impl Emitter<'_> {
+    fn line(&mut self, s: String) {
+        self.out.push(format!("{}{}", "    ".repeat(self.depth), s));
+    }
+
+    fn tree(&mut self, x: usize) {
+        let mut merges = self.children.get(&x).into_iter().flatten()
+            .filter(|c| self.merges.contains(c)).copied().collect::<Vec<_>>();
+        merges.sort_by_key(|c| self.index[c]);
+        if self.headers.contains(&x) {
+            self.line(format!("'l{}: loop {{", x));
+            self.depth += 1;
+            self.within(x, &merges);
+            self.depth -= 1;
+            self.line("}".to_string());
+        } else {
+            self.within(x, &merges);
+        }
+    }
+
+    // x and the merge points it dominates; the last merge point is outermost so that everything
+    // before it can break out to it
+    fn within(&mut self, x: usize, merges: &[usize]) {
+        match merges.split_last() {
+            None => {
+                let block = &self.blocks[&x];
+                let range = if block.end == x { format!("// {}", x) } else { format!("// {}-{}", x, block.end) };
+                self.line(range);
+                for s in &block.statements {
+                    self.line(s.clone());
+                }
+                self.exit(x);
+            },
+            Some((&y, rest)) => {
+                self.line(format!("'b{}: {{", y));
+                self.depth += 1;
+                self.within(x, rest);
+                self.depth -= 1;
+                self.line("}".to_string());
+                self.tree(y);
+            },
+        }
+    }
+
+    fn branch(&mut self, from: usize, to: Target) {
+        match to {
+            None => self.line("break 'halt;".to_string()),
+            Some(t) if self.index[&t] <= self.index[&from] => self.line(format!("continue 'l{};", t)),
+            Some(t) if self.merges.contains(&t) => self.line(format!("break 'b{};", t)),
+            Some(t) => self.tree(t),
+        }
+    }
+
+    fn exit(&mut self, x: usize) {
+        match self.blocks[&x].exit.clone() {
+            Exit::Goto(t) => self.branch(x, t),
+            Exit::Branch(cond, t, f) => {
+                self.line(format!("if {} {{", cond));
+                self.depth += 1;
+                self.branch(x, t);
+                self.depth -= 1;
+                self.line("} else {".to_string());
+                self.depth += 1;
+                self.branch(x, f);
+                self.depth -= 1;
+                self.line("}".to_string());
+            },
+            Exit::Switch(reg, _, arms) => {
+                self.line(format!("match {} {{", reg));
+                self.depth += 1;
+                for (value, t) in arms {
+                    self.line(format!("{} => {{", value));
+                    self.depth += 1;
+                    self.branch(x, Some(t));
+                    self.depth -= 1;
+                    self.line("},".to_string());
+                }
+                self.line("_ => break 'halt,".to_string());
+                self.depth -= 1;
+                self.line("}".to_string());
+            },
+            Exit::Computed(_) => unreachable!(),
+        }
+    }
+}
+
+fn structured(blocks: &BTreeMap<usize, Block>) -> Option<Vec<String>> {
+    let (rpo, idom) = dominators(blocks);
+    let index = rpo.iter().enumerate().map(|(i, &b)| (b, i)).collect::<HashMap<_, _>>();
+    let mut headers = HashSet::new();
+    let mut forward_preds: HashMap<usize, usize> = HashMap::new();
+    for &b in &rpo {
+        if let Exit::Computed(_) = blocks[&b].exit {
+            return None;
+        }
+        for t in blocks[&b].exit.targets().into_iter().collect::<BTreeSet<_>>() {
+            if index[&t] <= index[&b] {
+                // a loop entered from the middle
+                if !dominates(&idom, t, b) {
+                    return None;
+                }
+                headers.insert(t);
+            } else {
+                *forward_preds.entry(t).or_default() += 1;
+            }
+        }
+    }
+    let mut children: HashMap<usize, Vec<usize>> = HashMap::new();
+    for &b in &rpo[1..] {
+        children.entry(idom[&b]).or_default().push(b);
+    }
+
+    let mut emitter = Emitter {
+        blocks,
+        index,
+        children,
+        headers,
+        merges: forward_preds.into_iter().filter(|&(_, n)| n > 1).map(|(b, _)| b).collect(),
+        out: Vec::new(),
+        depth: 2,
+    };
+    emitter.tree(0);
+    Some(emitter.out)
+}
+
+fn state_machine(blocks: &BTreeMap<usize, Block>) -> Vec<String> {
+    let goto = |t: &Target| match t {
+        Some(t) => format!("ip = {};", t),
+        None => "break 'halt;".to_string(),
+    };
+    let mut out = vec!["        let mut ip = 0;".to_string(), "        loop {".to_string(), "            match ip {".to_string()];
+    for (&start, block) in blocks {
+        out.push(format!("                {} => {{", start));
+        let mut body = block.statements.clone();
+        match &block.exit {
+            Exit::Goto(t) => body.push(goto(t)),
+            Exit::Branch(cond, t, f) => body.push(format!("if {} {{ {} }} else {{ {} }}", cond, goto(t), goto(f))),
+            Exit::Switch(reg, base, _) => body.push(format!("ip = {} + {};", reg, base)),
+            Exit::Computed(expr) => body.push(format!("ip = {};", expr)),
+        }
+        out.extend(body.into_iter().map(|s| format!("                    {}", s)));
+        out.push("                },".to_string());
+    }
+    out.extend(["                _ => break 'halt,", "            }", "        }"].iter().map(|s| s.to_string()));
+    out
+}
+
+fn transpile(program: &[Instruction], names: &Names) -> String {
+    let blocks = blocks(program, names);
+    let body = structured(&blocks).unwrap_or_else(|| state_machine(&blocks));
+    let bind = |r: usize| if r == names.ip { "_".to_string() } else { format!("mut {}", names.regs[r]) };
+    let give = |r: usize| if r == names.ip { "0".to_string() } else { names.regs[r].clone() };
+
+    let mut out = Vec::new();
+    out.push(format!("// transpiled from elfcode with #ip {}; that register isn't kept", names.ip));
+    out.push("#[allow(unused_labels, unused_mut, unused_assignments, unused_variables, unreachable_code)]".to_string());
+    out.push(format!("fn run(regs: [i64; {}]) -> [i64; {}] {{", REGS, REGS));
+    out.push(format!("    let [{}] = regs;", (0..REGS).map(bind).collect::<Vec<_>>().join(", ")));
+    out.push("    'halt: {".to_string());
+    out.extend(body);
+    out.push("    }".to_string());
+    out.push(format!("    [{}]", (0..REGS).map(give).collect::<Vec<_>>().join(", ")));
+    out.push("}".to_string());
+    out.push(String::new());
+    out.push("fn main() {".to_string());
+    out.push("    let r0 = std::env::args().nth(1).map_or(0, |arg| arg.parse().unwrap());".to_string());
+    out.push("    println!(\"{:?}\", run([r0, 0, 0, 0, 0, 0]));".to_string());
+    out.push("}".to_string());
+    out.join("\n") + "\n"
+}
+
+fn usage() -> ! {
+    eprintln!("usage: transpile [-n a,b,c,ip,d,e] < program");
+    process::exit(1);
+}
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let mut regs = (0..REGS).map(|r| format!("r{}", r)).collect::<Vec<_>>();
+    match args.get(1).map(String::as_str) {
+        Some("-n") => {
+            let Some(names) = args.get(2) else { usage() };
+            regs = names.split(',').map(str::to_string).collect();
+            if regs.len() != REGS {
+                eprintln!("need a name for each of the {} registers", REGS);
+                usage();
+            }
+        },
+        Some(_) => usage(),
+        None => (),
+    }
+
+    let mut lines = io::stdin().lock().lines().map(Result::unwrap);
+    let ip = elfcode::parse_ip(&lines.next().unwrap());
+    let parser = elfcode::parser();
+    let program = lines.filter(|l| !l.is_empty()).map(|l| regmachine::parse_line(&parser, &l)).collect::<Vec<_>>();
+
+    print!("{}", transpile(&program, &Names { regs, ip }));
+}
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn fuel_requirements(mass: u32) -> u32 {
@@ -19,6 +21,7 @@ fn total_fuel_requirements(mass: u32) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(fuel_requirements(12), 2);
     assert_eq!(fuel_requirements(14), 2);
     assert_eq!(fuel_requirements(1969), 654);
@@ -32,5 +35,6 @@ fn main() {
         .map(|massline| massline.unwrap().parse().unwrap())
         .map(|mass| (fuel_requirements(mass), total_fuel_requirements(mass)))
         .fold((0, 0), |sum, i| (sum.0 + i.0, sum.1 + i.1));
-    println!("{:?}", all_spacecrafts_up_high);
+    answer::part1(all_spacecrafts_up_high.0);
+    answer::part2(all_spacecrafts_up_high.1);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::cmp::Ordering;
 
@@ -131,6 +133,7 @@ fn pewpew(origin: &(i32, i32), roids: &mut Vec<(i32, i32)>, limit: usize) -> (i3
 }
 
 fn main() {
+    let _session = answer::start();
     let mut roids: Vec<_> = io::stdin().lock().lines().enumerate().flat_map(
         |(y, line)| line.unwrap().into_bytes().into_iter().enumerate().filter_map(
             move |(x, ch)| if ch != b'.' { Some((x as i32, y as i32)) } else { None }
@@ -139,8 +142,10 @@ fn main() {
     .collect();
 
     let (bestval, bestpos) = roids.iter().map(|roid| (visibility(roid, &roids), *roid)).max().unwrap();
-    println!("best {} at {:?}", bestval, bestpos);
+    println!("best at {:?}", bestpos);
+    answer::part1(bestval);
 
     let correct_stardust = pewpew(&bestpos, &mut roids, 200);
-    println!("{:?} {}", correct_stardust, 100 * correct_stardust.0 + correct_stardust.1);
+    println!("{:?}", correct_stardust);
+    answer::part2(100 * correct_stardust.0 + correct_stardust.1);
 }
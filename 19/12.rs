@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -177,10 +179,11 @@ fn parse_moon(line: String) -> Vek {
 }
 
 fn main() {
+    let _session = answer::start();
     let moons: Vec<_> = io::stdin().lock().lines().map(
         |line| parse_moon(line.unwrap())
     ).collect();
 
-    println!("{}", energy_after(moons.clone(), 1000));
-    println!("{}", world_period(moons.clone()));
+    answer::part1(energy_after(moons.clone(), 1000));
+    answer::part2(world_period(moons.clone()));
 }
@@ -1,183 +1,66 @@
-use std::io::{self, BufRead};
-use std::collections::HashMap;
-
-#[derive(Debug, PartialEq, Copy, Clone)]
-enum Tile {
-    Empty,
-    Wall,
-    Block,
-    Paddle,
-    Ball
-}
-use Tile::*;
-
-fn step<'a, 'b, I: Iterator<Item = &'b i64>>(program: &'a mut [i64], ip: usize, base: i64, input: &mut I) -> Option<(usize, i64, Option<i64>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        // short circuit this, the discontinuity is annoying
-        return None;
-    }
-
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 2);
-    assert!(mode1 <= 2);
-    assert!(mode2 <= 2);
-    let immflags = (mode0 == 1, mode1 == 1, mode2 == 1);
-    let relflags = (mode0 == 2, mode1 == 2, mode2 == 2);
-
-    // indirection via closures instead of direct variables because indexing might go off with
-    // arbitrary values: only evaluate when it's known that indexing is okay. Need this to avoid
-    // repetition in the opcode decoding
-
-    let rel0 = if relflags.0 { base } else { 0 };
-    let rel1 = if relflags.1 { base } else { 0 };
-    let rel2 = if relflags.2 { base } else { 0 };
-    let imm0 = || program[ip + 1];
-    let imm1 = || program[ip + 2];
-    let val0 = || if immflags.0 { imm0() } else { program[(imm0() + rel0) as usize ] };
-    let val1 = || if immflags.1 { imm1() } else { program[(imm1() + rel1) as usize ] };
-    // program as input for lifetime; using imm0 here would cause more lifetime trouble
-    let mut0 = |program: &'a mut [i64]| {
-        assert!(!immflags.0); &mut program[(program[ip + 1] + rel0) as usize] };
-    let mut2 = |program: &'a mut [i64]| {
-        assert!(!immflags.2); &mut program[(program[ip + 3] + rel2) as usize] };
-
-    match opcode {
-        1 => {
-            *mut2(program) = val0() + val1();
-            Some((ip + 4, base, None))
-        },
-        2 => {
-            *mut2(program) = val0() * val1();
-            Some((ip + 4, base, None))
-        },
-        3 => {
-            *mut0(program) = *input.next().unwrap();
-            Some((ip + 2, base, None))
-        }
-        4 => {
-            Some((ip + 2, base, Some(val0())))
-        },
-        5 => {
-            if val0() != 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        6 => {
-            if val0() == 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        7 => {
-            *mut2(program) = if val0() < val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        8 => {
-            *mut2(program) = if val0() == val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        9 => {
-            Some((ip + 2, base + val0(), None))
-        },
-        _ => panic!("something went wrong at {}: {}", ip, program[ip])
-    }
-}
-
-fn execute(program: &[i64], play_free: bool) -> HashMap<(i64, i64), Tile> {
-    let mut panel = HashMap::new();
-    let mut program = program.to_vec();
-    // FIXME: program should probably be a hashmap, but this works for now
-    program.resize(program.len() + 1000, 0);
-
-    let mut ip = 0;
-    let mut base = 0;
-
-    let mut x = 0;
-    let mut y = 0;
-
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+mod arcade;
+use arcade::{Autopilot, Controller, Human, Screen};
+use intcode::Computer;
+use std::env;
+use std::fs;
+use std::io;
+
+// the final screen
+fn execute(program: &[i64], play_free: bool, controller: &mut dyn Controller, frames: Option<&str>) -> Screen {
+    let mut computer = Computer::new(program);
     if play_free {
         // infinite (two, sir!) quarters
-        program[0] = 2;
+        computer.write(0, 2);
     }
-    let mut ballx = 0;
-    let mut padx = 0;
 
-    let mut outmode = 0;
-    while let Some((newip, newbase, newout)) =
-            step(&mut program, ip, base, &mut [(ballx as i64 - padx as i64).signum()].iter()) {
-        if newout.is_some() {
-            match outmode {
-                0 => {
-                    x = newout.unwrap() as i64;
-                },
-                1 => {
-                    y = newout.unwrap() as i64;
-                },
-                2 => {
-                    if x == -1 && y == 0 {
-                        println!("current score: {}", newout.unwrap());
-                    } else {
-                        panel.insert((x, y), match newout.unwrap() {
-                            0 => Empty,
-                            1 => Wall,
-                            2 => Block,
-                            3 => {
-                                padx = x;
-                                Paddle
-                            },
-                            4 => {
-                                ballx = x;
-                                Ball
-                            },
-                            _ => panic!("bad tile")
-                        });
-                        dump(&panel);
-                    }
-                },
-                _ => unreachable!()
-            }
-            outmode = (outmode + 1) % 3;
+    let mut n = 0;
+    arcade::play(&mut computer, controller, |screen| {
+        n += 1;
+        if play_free {
+            print!("{}", screen.render());
         }
-        ip = newip;
-        base = newbase;
-    }
-
-    panel
+        if let Some(dir) = frames {
+            screen.write_ppm(&format!("{}/frame_{:0>8}.ppm", dir, n));
+        }
+    })
 }
 
-fn dump(panel: &HashMap<(i64, i64), Tile>) {
-    let minx = panel.keys().map(|&(x, _)| x).min().unwrap();
-    let maxx = panel.keys().map(|&(x, _)| x).max().unwrap();
-    let miny = panel.keys().map(|&(_, y)| y).min().unwrap();
-    let maxy = panel.keys().map(|&(_, y)| y).max().unwrap();
-    for y in miny..=maxy {
-        for x in minx..=maxx {
-            let ch = match *panel.get(&(x, y)).unwrap_or(&Empty) {
-                Empty => ' ',
-                Wall => '*',
-                Block => 'x',
-                Paddle => '-',
-                Ball => 'o',
-            };
-            print!("{}", ch);
+// 19/13 < 19/13.input                     the answers, with the autopilot's game animated
+// 19/13 -f frames < 19/13.input           also every frame as frames/frame_00000001.ppm and so on
+// 19/13 -p 19/13.input                    play it yourself, the keys come from stdin
+fn main() {
+    let mut frames = None;
+    let mut human = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-f" => frames = Some(args.next().expect("directory for frames")),
+            "-p" => human = Some(args.next().expect("program to play")),
+            _ => panic!("unknown argument {}", arg),
         }
-        println!();
     }
-}
+    if let Some(dir) = &frames {
+        fs::create_dir_all(dir).unwrap();
+    }
 
-fn main() {
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    if let Some(path) = human {
+        let program = intcode::parse_program(&fs::read_to_string(path).unwrap());
+        let mut player = Human { keys: io::stdin().lock() };
+        let screen = execute(&program, true, &mut player, frames.as_deref());
+        println!("{}final score {}, {} blocks left", screen.render(), screen.score, screen.blocks());
+        return;
+    }
+
+    let _session = answer::start();
+    let program = intcode::read_program();
 
-    let panel = execute(&program, false);
-    println!("{}", panel.values().filter(|&&x| x == Block).count());
-    dump(&panel);
+    let screen = execute(&program, false, &mut Autopilot, None);
+    print!("{}", screen.render());
+    answer::part1(screen.blocks());
 
-    execute(&program, true);
+    let screen = execute(&program, true, &mut Autopilot, frames.as_deref());
+    answer::part2(screen.score);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 use std::fmt;
@@ -151,6 +153,7 @@ fn dump_graphviz(chain: &[Reaction]) {
 }
 
 fn main() {
+    let _session = answer::start();
     let reaction_chain: Vec<_> = io::stdin().lock().lines().map(
         |line| parse_reaction(line.unwrap())
     ).collect();
@@ -159,8 +162,8 @@ fn main() {
         dump_graphviz(&reaction_chain);
     }
 
-    println!("{}", ores_for_fuel(&reaction_chain));
-    println!("{}", max_fuel_produceable(&reaction_chain, 1000000000000));
+    answer::part1(ores_for_fuel(&reaction_chain));
+    answer::part2(max_fuel_produceable(&reaction_chain, 1000000000000));
     if false {
         println!("double check: wait ...");
         println!("{}", max_fuel_produceable_linear(&reaction_chain, 1000000000000));
@@ -1,110 +1,29 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+use intcode::Computer;
 mod day15rusted;
 use day15rusted::OxygenMap;
+#[path = "../lib/search.rs"]
+mod search;
 
-use std::io::{self, BufRead};
 use std::collections::HashMap;
 use std::collections::VecDeque;
-
-fn step<'a, 'b, I: Iterator<Item = &'b i64>>(program: &'a mut [i64], ip: usize, base: i64, input: &mut I) -> Option<(usize, i64, Option<i64>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        // short circuit this, the discontinuity is annoying
-        return None;
-    }
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 2);
-    assert!(mode1 <= 2);
-    assert!(mode2 <= 2);
-    let immflags = (mode0 == 1, mode1 == 1, mode2 == 1);
-    let relflags = (mode0 == 2, mode1 == 2, mode2 == 2);
-
-    // indirection via closures instead of direct variables because indexing might go off with
-    // arbitrary values: only evaluate when it's known that indexing is okay. Need this to avoid
-    // repetition in the opcode decoding
-
-    let rel0 = if relflags.0 { base } else { 0 };
-    let rel1 = if relflags.1 { base } else { 0 };
-    let rel2 = if relflags.2 { base } else { 0 };
-    let imm0 = || program[ip + 1];
-    let imm1 = || program[ip + 2];
-    let val0 = || if immflags.0 { imm0() } else { program[(imm0() + rel0) as usize ] };
-    let val1 = || if immflags.1 { imm1() } else { program[(imm1() + rel1) as usize ] };
-    // program as input for lifetime; using imm0 here would cause more lifetime trouble
-    let mut0 = |program: &'a mut [i64]| {
-        assert!(!immflags.0); &mut program[(program[ip + 1] + rel0) as usize] };
-    let mut2 = |program: &'a mut [i64]| {
-        assert!(!immflags.2); &mut program[(program[ip + 3] + rel2) as usize] };
-
-    match opcode {
-        1 => {
-            *mut2(program) = val0() + val1();
-            Some((ip + 4, base, None))
-        },
-        2 => {
-            *mut2(program) = val0() * val1();
-            Some((ip + 4, base, None))
-        },
-        3 => {
-            *mut0(program) = *input.next().unwrap();
-            Some((ip + 2, base, None))
-        }
-        4 => {
-            Some((ip + 2, base, Some(val0())))
-        },
-        5 => {
-            if val0() != 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        6 => {
-            if val0() == 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        7 => {
-            *mut2(program) = if val0() < val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        8 => {
-            *mut2(program) = if val0() == val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        9 => {
-            Some((ip + 2, base + val0(), None))
-        },
-        _ => panic!("something went wrong at {}: {}", ip, program[ip])
-    }
-}
-
-struct Computer {
-    program: Vec<i64>,
-    ip: usize,
-    base: i64,
-    rusted: OxygenMap,
+use std::env;
+use std::fs;
+
+struct Droid {
+    computer: Computer,
+    // the maze of the program compiled to rust by hand; a lot faster, but there's no saving it
+    rusted: Option<OxygenMap>,
+    // where to save the droid when it finds the oxygen system
+    checkpoint: Option<String>,
 }
 
-fn execute(computer: &mut Computer, next_in: i64) -> Option<i64> {
-    if false {
-        let inputs = &[next_in];
-        let mut input = inputs.iter();
-        while let Some((newip, newbase, newout)) =
-                step(&mut computer.program, computer.ip, computer.base, &mut input) {
-            computer.ip = newip;
-            computer.base = newbase;
-            if newout.is_some() {
-                // return *after* updating the ip or the computer will keep replying infinitely... doh.
-                return newout;
-            }
-        }
-        None
-    } else {
-        computer.rusted.run(next_in)
+fn execute(droid: &mut Droid, next_in: i64) -> Option<i64> {
+    match &mut droid.rusted {
+        Some(rusted) => rusted.run(next_in),
+        None => droid.computer.run_with(&mut std::iter::once(next_in)).output(),
     }
 }
 
@@ -119,6 +38,13 @@ const DIR_EAST: i64 = 4;
 
 type Grid = HashMap<(i32, i32), i64>;
 
+const STEPS: [(i64, (i32, i32)); 4] = [
+    (DIR_NORTH, (0, -1)),
+    (DIR_SOUTH, (0, 1)),
+    (DIR_WEST, (-1, 0)),
+    (DIR_EAST, (1, 0)),
+];
+
 fn dump(grid: &Grid, droidpos: (i32, i32)) {
     println!("<map>");
     let minx = grid.keys().map(|&(x, _)| x).min().unwrap();
@@ -145,7 +71,7 @@ fn dump(grid: &Grid, droidpos: (i32, i32)) {
     println!("</map>");
 }
 
-fn explore(computer: &mut Computer, grid: &mut Grid, x: i32, y: i32, current_tile: i64) {
+fn explore(droid: &mut Droid, grid: &mut Grid, x: i32, y: i32, current_tile: i64) {
     if grid.contains_key(&(x, y)) {
         // around a corner we've seen already
         return;
@@ -160,6 +86,11 @@ fn explore(computer: &mut Computer, grid: &mut Grid, x: i32, y: i32, current_til
         // the robot didn't actually go here; just mark it in the map
         return;
     }
+    if current_tile == LOCATION_OXYGEN {
+        if let Some(path) = droid.checkpoint.take() {
+            save_checkpoint(droid, grid, (x, y), &path);
+        }
+    }
     assert!(current_tile == LOCATION_OPEN || current_tile == LOCATION_OXYGEN);
 
     let attempts = &[
@@ -169,15 +100,63 @@ fn explore(computer: &mut Computer, grid: &mut Grid, x: i32, y: i32, current_til
         (x + 1, y, DIR_EAST,  DIR_WEST),
     ];
     for &(xi, yi, dircmd, backcmd) in attempts {
-        let reply = execute(computer, dircmd).unwrap(); // never stop the madness
-        explore(computer, grid, xi, yi, reply);
+        let reply = execute(droid, dircmd).unwrap(); // never stop the madness
+        explore(droid, grid, xi, yi, reply);
         if reply != LOCATION_WALL {
-            let back = execute(computer, backcmd).unwrap();
+            let back = execute(droid, backcmd).unwrap();
             assert_eq!(back, current_tile);
         };
     }
 }
 
+// the machine, where the droid stands and the map so far, for AOC_RESUME to continue from
+fn save_checkpoint(droid: &Droid, grid: &Grid, pos: (i32, i32), path: &str) {
+    let seen = grid.iter()
+        .map(|(&(x, y), tile)| format!("{},{},{}", x, y, tile))
+        .collect::<Vec<_>>().join(" ");
+    let notes = [("droid", format!("{},{}", pos.0, pos.1)), ("seen", seen)];
+    fs::write(path, droid.computer.snapshot_with_notes(&notes)).unwrap();
+    println!("saved the droid at {} {} in {}", pos.0, pos.1, path);
+}
+
+fn load_checkpoint(path: &str) -> (Droid, Grid, (i32, i32)) {
+    let text = fs::read_to_string(path).unwrap();
+    let computer = Computer::from_snapshot(&text).unwrap();
+    let numbers = |s: &str| s.split(',').map(|n| n.parse().unwrap()).collect::<Vec<i32>>();
+    let pos = numbers(intcode::snapshot_note(&text, "droid").expect("no droid in the snapshot"));
+    let grid = intcode::snapshot_note(&text, "seen").expect("no map in the snapshot")
+        .split(' ')
+        .map(|cell| {
+            let c = numbers(cell);
+            ((c[0], c[1]), c[2] as i64)
+        })
+        .collect();
+    (Droid { computer, rusted: None, checkpoint: None }, grid, (pos[0], pos[1]))
+}
+
+// A resumed droid is somewhere in the middle, and the recursion that took it there is gone. Walk
+// it to each spot that has something unknown next to it and explore from there instead.
+fn explore_rest(droid: &mut Droid, grid: &mut Grid, mut pos: (i32, i32)) {
+    loop {
+        let open = |p: &(i32, i32)| grid.get(p).map_or(false, |&tile| tile != LOCATION_WALL);
+        let found = search::bfs([pos], |&(x, y)| {
+            STEPS.iter().map(move |&(_, (dx, dy))| (x + dx, y + dy)).filter(open).collect::<Vec<_>>()
+        }, |&(x, y)| STEPS.iter().any(|&(_, (dx, dy))| !grid.contains_key(&(x + dx, y + dy))));
+        let Some(goal) = found.goal else {
+            break;
+        };
+        for step in found.path(&goal).unwrap().windows(2) {
+            let &(dir, _) = STEPS.iter()
+                .find(|&&(_, (dx, dy))| (step[0].0 + dx, step[0].1 + dy) == step[1]).unwrap();
+            assert_ne!(execute(droid, dir).unwrap(), LOCATION_WALL);
+        }
+        pos = goal;
+        // explore() doesn't go where it has been already
+        let tile = grid.remove(&pos).unwrap();
+        explore(droid, grid, pos.0, pos.1, tile);
+    }
+}
+
 fn bfs(grid: &Grid, start: (i32, i32), destination: Option<(i32, i32)>) -> HashMap<(i32, i32), usize> {
     let mut queue = VecDeque::new();
     let mut distances = HashMap::new();
@@ -214,18 +193,23 @@ fn bfs(grid: &Grid, start: (i32, i32), destination: Option<(i32, i32)>) -> HashM
 }
 
 fn oxygen_quest(program: &[i64]) -> (usize, usize) {
-    let mut prog = program.to_vec();
-    prog.resize(prog.len() + 1000, 0);
-    let mut computer = Computer {
-        program: prog,
-        ip: 0,
-        base: 0,
-        rusted: OxygenMap::new(),
-    };
-
-    // dfs the map using the robot, don't stop at the oxygen tile yet
+    // AOC_SNAPSHOT=file to save the droid at the oxygen system, which takes the real machine
+    // instead of the compiled maze, and AOC_RESUME=file to carry on from such a save
     let mut grid = HashMap::new();
-    explore(&mut computer, &mut grid, 0, 0, LOCATION_OPEN);
+    if let Ok(path) = env::var("AOC_RESUME") {
+        let (mut droid, seen, pos) = load_checkpoint(&path);
+        grid = seen;
+        explore_rest(&mut droid, &mut grid, pos);
+    } else {
+        let checkpoint = env::var("AOC_SNAPSHOT").ok();
+        let mut droid = Droid {
+            computer: Computer::new(program),
+            rusted: if checkpoint.is_none() { Some(OxygenMap::new()) } else { None },
+            checkpoint,
+        };
+        // dfs the map using the robot, don't stop at the oxygen tile yet
+        explore(&mut droid, &mut grid, 0, 0, LOCATION_OPEN);
+    }
 
     // then search the minimal distance to the goal using the full map
     let oxy_coords = *grid.iter().find(|(_, &v)| v == LOCATION_OXYGEN).unwrap().0;
@@ -237,8 +221,10 @@ fn oxygen_quest(program: &[i64]) -> (usize, usize) {
 }
 
 fn main() {
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let _session = answer::start();
+    let program = intcode::read_program();
 
-    println!("{:?}", oxygen_quest(&program));
+    let (steps, minutes) = oxygen_quest(&program);
+    answer::part1(steps);
+    answer::part2(minutes);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::iter;
 
@@ -65,9 +67,10 @@ fn fft_100_message_real(digits: &[i32]) -> String {
 }
 
 fn main() {
+    let _session = answer::start();
     let digits: Vec<i32> = io::stdin().lock().lines().next().unwrap().unwrap()
         .bytes().map(|b| (b - b'0') as i32).collect();
 
-    println!("{}", fft_100_message(digits.clone()));
-    println!("{}", fft_100_message_real(&digits));
+    answer::part1(fft_100_message(digits.clone()));
+    answer::part2(fft_100_message_real(&digits));
 }
@@ -1,112 +1,18 @@
-use std::io::{self, BufRead};
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+mod ascii;
+use ascii::{Output, Terminal};
+use intcode::Computer;
 use std::collections::HashMap;
 
-fn step<'a, 'b, I: Iterator<Item = &'b i64>>(program: &'a mut [i64], ip: usize, base: i64, input: &mut I) -> Option<(usize, i64, Option<i64>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        return None;
-    }
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 2);
-    assert!(mode1 <= 2);
-    assert!(mode2 <= 2);
-    let immflags = (mode0 == 1, mode1 == 1, mode2 == 1);
-    let relflags = (mode0 == 2, mode1 == 2, mode2 == 2);
-
-    let rel0 = if relflags.0 { base } else { 0 };
-    let rel1 = if relflags.1 { base } else { 0 };
-    let rel2 = if relflags.2 { base } else { 0 };
-    let imm0 = || program[ip + 1];
-    let imm1 = || program[ip + 2];
-    let val0 = || if immflags.0 { imm0() } else { program[(imm0() + rel0) as usize ] };
-    let val1 = || if immflags.1 { imm1() } else { program[(imm1() + rel1) as usize ] };
-
-    let mut0 = |program: &'a mut [i64]| {
-        assert!(!immflags.0); &mut program[(program[ip + 1] + rel0) as usize] };
-    let mut2 = |program: &'a mut [i64]| {
-        assert!(!immflags.2); &mut program[(program[ip + 3] + rel2) as usize] };
-
-    match opcode {
-        1 => {
-            *mut2(program) = val0() + val1();
-            Some((ip + 4, base, None))
-        },
-        2 => {
-            *mut2(program) = val0() * val1();
-            Some((ip + 4, base, None))
-        },
-        3 => {
-            *mut0(program) = *input.next().unwrap();
-            Some((ip + 2, base, None))
-        }
-        4 => {
-            Some((ip + 2, base, Some(val0())))
-        },
-        5 => {
-            if val0() != 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        6 => {
-            if val0() == 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        7 => {
-            *mut2(program) = if val0() < val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        8 => {
-            *mut2(program) = if val0() == val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        9 => {
-            Some((ip + 2, base + val0(), None))
-        },
-        _ => panic!("something went wrong at {}: {}", ip, program[ip])
-    }
-}
-
-struct Computer {
-    program: Vec<i64>,
-    ip: usize,
-    base: i64,
-}
-
-fn execute(computer: &mut Computer) -> Option<i64> {
-    let inputs = &[];
-    let mut input = inputs.iter();
-    while let Some((newip, newbase, newout)) =
-            step(&mut computer.program, computer.ip, computer.base, &mut input) {
-        computer.ip = newip;
-        computer.base = newbase;
-        if newout.is_some() {
-            // return *after* updating the ip or the computer will keep replying infinitely... doh.
-            return newout;
-        }
-    }
-    None
-}
-
 fn read_map(program: &[i64]) -> HashMap<(i64, i64), char> {
-    let mut prog = program.to_vec();
-    prog.resize(prog.len() + 2000, 0);
-    let mut computer = Computer {
-        program: prog,
-        ip: 0,
-        base: 0
-    };
+    let mut computer = Computer::new(program);
 
     let mut pixels = HashMap::new();
     let mut x = 0;
     let mut y = 0;
-    while let Some(out) = execute(&mut computer) {
+    while let Some(out) = computer.next_output() {
         let ch = out as u8 as char;
         if ch == '\n' {
             y += 1;
@@ -150,43 +56,32 @@ fn alignment_parameters(program: &[i64]) -> i64 {
     calib
 }
 
-fn execute_dusting(computer: &mut Computer, inputs: &[i64]) -> Option<i64> {
-    let mut input = inputs.iter();
+fn execute_dusting(computer: Computer, inputs: &[&str]) -> Option<i64> {
+    let mut terminal = Terminal::new(computer);
+    for line in inputs {
+        terminal.send_line(line);
+    }
     let mut dust = None;
-    while let Some((newip, newbase, newout)) =
-            step(&mut computer.program, computer.ip, computer.base, &mut input) {
-        computer.ip = newip;
-        computer.base = newbase;
-        if let Some(out) = newout {
-            if out <= 127 {
-                // the animation is epic
-                print!("{}", newout.unwrap() as u8 as char);
-            } else {
+    while let Some(out) = terminal.read() {
+        match out {
+            // the animation is epic
+            Output::Line(line) => println!("{}", line),
+            Output::Value(value) => {
                 assert!(dust.is_none());
-                dust = Some(out);
-            }
+                dust = Some(value);
+            },
         }
     }
     dust
 }
 
-
 fn dust_scaffold(program: &[i64], functions: &[&str]) -> i64 {
-    let mut prog = program.to_vec();
-    prog.resize(prog.len() + 2000, 0);
-    prog[0] = 2;
-    let mut computer = Computer {
-        program: prog,
-        ip: 0,
-        base: 0
-    };
-    let input = functions.join("\n");
-    let mut input = input.as_bytes().iter().map(|&x| x as i64).collect::<Vec<_>>();
-    input.push('\n' as i64);
-    input.push('y' as i64);
-    input.push('\n' as i64);
+    let mut computer = Computer::new(program);
+    computer.write(0, 2);
+    // no continuous video feed
+    let inputs = functions.iter().copied().chain(std::iter::once("y")).collect::<Vec<_>>();
 
-    execute_dusting(&mut computer, &input).unwrap()
+    execute_dusting(computer, &inputs).unwrap()
 }
 
 fn walk_scaffold(pixels: &HashMap<(i64, i64), char>) -> Vec<String> {
@@ -261,9 +156,9 @@ fn sweep_clean(program: &[i64]) -> i64 {
 }
 
 fn main() {
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let _session = answer::start();
+    let program = intcode::read_program();
 
-    println!("{}", alignment_parameters(&program));
-    println!("{}", sweep_clean(&program));
+    answer::part1(alignment_parameters(&program));
+    answer::part2(sweep_clean(&program));
 }
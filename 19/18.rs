@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, VecDeque, BinaryHeap};
 use std::cmp::Ordering;
@@ -434,10 +436,11 @@ fn parse_world(mut map: Vec<Vec<char>>) -> World {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<Vec<char>> = io::stdin().lock().lines().map(|line|
         line.unwrap().chars().collect()).collect();
     let world = parse_world(input);
     dump(&world);
-    println!("{}", shortest_keypath(world.clone()));
-    println!("{}", shortest_keypath_divided(world));
+    answer::part1(shortest_keypath(world.clone()));
+    answer::part2(shortest_keypath_divided(world));
 }
@@ -1,38 +1,15 @@
-use std::io::{self, BufRead};
-
-fn step(program: &mut [u32], ip: usize) -> Option<usize> {
-    let opcode = program[ip];
-    if opcode == 99 {
-        None
-    } else {
-        let a = program[program[ip + 1] as usize];
-        let b = program[program[ip + 2] as usize];
-        let dest = &mut program[program[ip + 3] as usize];
-        match opcode {
-            1 => {
-                *dest = a + b;
-            },
-            2 => {
-                *dest = a * b;
-            },
-            _ => panic!("something went wrong")
-        };
-        Some(ip + 4)
-    }
-}
-
-fn execute(program: &[u32]) -> u32 {
-    let mut program = program.to_vec();
-    let mut ip = 0;
-
-    while let Some(newip) = step(&mut program, ip) {
-        ip = newip;
-    }
-
-    program[0]
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+use intcode::{Computer, RunState};
+
+fn execute(program: &[i64]) -> i64 {
+    let mut computer = Computer::new(program);
+    assert_eq!(computer.run(), RunState::Halted);
+    computer.read(0)
 }
 
-fn exec_input(program: &[u32], noun: u32, verb: u32) -> u32 {
+fn exec_input(program: &[i64], noun: i64, verb: i64) -> i64 {
     let mut program = program.to_vec();
 
     program[1] = noun;
@@ -41,24 +18,24 @@ fn exec_input(program: &[u32], noun: u32, verb: u32) -> u32 {
     execute(&mut program)
 }
 
-fn bruteforce(program: &[u32], output: u32) -> u32 {
+fn bruteforce(program: &[i64], output: i64) -> i64 {
     // the puzzle answer is 100 * noun + verb which is conveniently the position in this flattened
     // list of 2d search parameters: for each noun we have 100 verbs
     (0..100).flat_map(|noun| (0..100).map(move |verb| (noun, verb)))
         .position(|(noun, verb)| exec_input(&program, noun, verb) == output)
-        .unwrap() as u32
+        .unwrap() as i64
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(execute(&[1,9,10,3,2,3,11,0,99,30,40,50]), 3500);
     assert_eq!(execute(&[1,0,0,0,99]), 2);
     assert_eq!(execute(&[2,3,0,3,99]), 2);
     assert_eq!(execute(&[2,4,4,5,99,0]), 2);
     assert_eq!(execute(&[1,1,1,4,99,5,6,0,99]), 30);
 
-    let program: Vec<u32> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let program = intcode::read_program();
 
-    println!("{}", exec_input(&program, 12, 2));
-    println!("{}", bruteforce(&program, 19690720));
+    answer::part1(exec_input(&program, 12, 2));
+    answer::part2(bruteforce(&program, 19690720));
 }
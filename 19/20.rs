@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, VecDeque, BinaryHeap};
 
@@ -272,11 +274,12 @@ fn parse_map(raw_map: Vec<Vec<char>>) -> Map {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<Vec<char>> = io::stdin().lock().lines().map(|line|
         line.unwrap().chars().collect()).collect();
     let map = parse_map(input);
 
     dump(&map);
-    println!("{}", step_maze(&map, false));
-    println!("{}", step_maze(&map, true));
+    answer::part1(step_maze(&map, false));
+    answer::part2(step_maze(&map, true));
 }
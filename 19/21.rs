@@ -1,98 +1,24 @@
-use std::io::{self, BufRead};
-
-fn step<'a, I: Iterator<Item = i64>>(program: &'a mut [i64], ip: usize, base: i64, input: &mut I) -> Option<(usize, i64, Option<i64>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        return None;
-    }
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 2);
-    assert!(mode1 <= 2);
-    assert!(mode2 <= 2);
-    let immflags = (mode0 == 1, mode1 == 1, mode2 == 1);
-    let relflags = (mode0 == 2, mode1 == 2, mode2 == 2);
-
-    let rel0 = if relflags.0 { base } else { 0 };
-    let rel1 = if relflags.1 { base } else { 0 };
-    let rel2 = if relflags.2 { base } else { 0 };
-    let imm0 = || program[ip + 1];
-    let imm1 = || program[ip + 2];
-    let val0 = || if immflags.0 { imm0() } else { program[(imm0() + rel0) as usize ] };
-    let val1 = || if immflags.1 { imm1() } else { program[(imm1() + rel1) as usize ] };
-
-    let mut0 = |program: &'a mut [i64]| {
-        assert!(!immflags.0); &mut program[(program[ip + 1] + rel0) as usize] };
-    let mut2 = |program: &'a mut [i64]| {
-        assert!(!immflags.2); &mut program[(program[ip + 3] + rel2) as usize] };
-
-    match opcode {
-        1 => {
-            *mut2(program) = val0() + val1();
-            Some((ip + 4, base, None))
-        },
-        2 => {
-            *mut2(program) = val0() * val1();
-            Some((ip + 4, base, None))
-        },
-        3 => {
-            *mut0(program) = input.next().unwrap();
-            Some((ip + 2, base, None))
-        }
-        4 => {
-            Some((ip + 2, base, Some(val0())))
-        },
-        5 => {
-            if val0() != 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        6 => {
-            if val0() == 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        7 => {
-            *mut2(program) = if val0() < val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        8 => {
-            *mut2(program) = if val0() == val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        9 => {
-            Some((ip + 2, base + val0(), None))
-        },
-        _ => panic!("something went wrong at {}: {}", ip, program[ip])
-    }
-}
-
-struct Computer {
-    program: Vec<i64>,
-    ip: usize,
-    base: i64,
-}
-
-fn execute_springscript(computer: &mut Computer, inputs: &str) -> Option<i64> {
-    let mut input = inputs.bytes().map(|b| b as i64);
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+mod ascii;
+mod springscript;
+use ascii::{Output, Terminal};
+use intcode::Computer;
+use springscript::Mode;
+
+fn execute_springscript(computer: Computer, script: &str) -> Option<i64> {
+    let mut terminal = Terminal::new(computer);
+    terminal.send(script);
     let mut damage = None;
-    while let Some((newip, newbase, newout)) =
-            step(&mut computer.program, computer.ip, computer.base, &mut input) {
-        computer.ip = newip;
-        computer.base = newbase;
-        if let Some(out) = newout {
-            if out <= 127 {
-                // the animation is epic
-                print!("{}", newout.unwrap() as u8 as char);
-            } else {
+    while let Some(out) = terminal.read() {
+        match out {
+            // the animation is epic
+            Output::Line(line) => println!("{}", line),
+            Output::Value(value) => {
                 assert!(damage.is_none());
-                damage = Some(out);
-            }
+                damage = Some(value);
+            },
         }
     }
     damage
@@ -231,61 +157,38 @@ OR H T
 
 AND T J
 */
+// the cases from above where only one choice keeps the droid alive, sensors from A on
+const WALK_TABLE: &[(&str, bool)] = &[
+    // hole right ahead and land at D
+    ("...#", true), ("..##", true), (".#.#", true), (".###", true),
+    // a hole later, so jump over it while D is there to land on
+    ("#..#", true), ("#.##", true), ("##.#", true),
+    // landing at D would be a fall
+    ("#...", false), ("#.#.", false), ("##..", false), ("###.", false),
+    // nothing to jump over
+    ("####", false),
+];
+
 fn research_hull_damage(program: &[i64]) -> i64 {
-    let mut prog = program.to_vec();
-    prog.resize(prog.len() + prog.len(), 0);
-    let mut computer = Computer {
-        program: prog,
-        ip: 0,
-        base: 0
-    };
-    // no floor in front or a jump land site on D
-    // !A | (!C & D)
-    let _script = "NOT A J\n\
-                 NOT C T\n\
-                 AND D T\n\
-                 OR T J\n\
-                 WALK\n";
-    // gap somewhere ahead and jump land site on D
-    // !(A & B & C) & D
-    let script = "NOT A J\n\
-                  NOT J J\n\
-                  AND B J\n\
-                  AND C J\n\
-                  NOT J J\n\
-                  AND D J\n\
-                  WALK\n";
-
-    execute_springscript(&mut computer, script).unwrap_or(0)
+    let computer = Computer::new(program);
+    // comes out as the gap somewhere ahead and jump land site on D: !(A & B & C) & D
+    let expr = springscript::synthesize(WALK_TABLE, Mode::Walk, 10).expect("no expression fits the table");
+    let script = springscript::compile(&expr, Mode::Walk).unwrap();
+
+    execute_springscript(computer, &script).unwrap_or(0)
 }
 
 fn extended_sensor_mode(program: &[i64]) -> i64 {
-    let mut prog = program.to_vec();
-    prog.resize(prog.len() + prog.len(), 0);
-    let mut computer = Computer {
-        program: prog,
-        ip: 0,
-        base: 0
-    };
-    let if_nabc_d = "NOT A J\n\
-                  NOT J J\n\
-                  AND B J\n\
-                  AND C J\n\
-                  NOT J J\n\
-                  AND D J\n";
-    let if_also_eh = "NOT E T\n\
-                      NOT T T\n\
-                      OR H T\n\
-                      AND T J\n";
-    let go = "RUN\n";
-    let script = if_nabc_d.to_string() + if_also_eh + go;
-    execute_springscript(&mut computer, &script).unwrap_or(0)
+    let computer = Computer::new(program);
+    let expr = springscript::parse("!(A & B & C) & D & (E | H)").unwrap();
+    let script = springscript::compile(&expr, Mode::Run).unwrap();
+    execute_springscript(computer, &script).unwrap_or(0)
 }
 
 fn main() {
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let _session = answer::start();
+    let program = intcode::read_program();
 
-    println!("{}", research_hull_damage(&program));
-    println!("{}", extended_sensor_mode(&program));
+    answer::part1(research_hull_damage(&program));
+    answer::part2(extended_sensor_mode(&program));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::str::FromStr;
 
@@ -271,6 +273,7 @@ fn giant_deck_2020(steps: &[Technique]) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(sampledeck(NewStack), &[9, 8, 7, 6, 5, 4, 3, 2, 1, 0]);
     assert_eq!(sampledeck(Cut(3)), &[3, 4, 5, 6, 7, 8, 9, 0, 1, 2]);
     assert_eq!(sampledeck(Cut(-4)), &[6, 7, 8, 9, 0, 1, 2, 3, 4, 5]);
@@ -292,6 +295,6 @@ fn main() {
     let steps: Vec<Technique> = io::stdin().lock().lines()
         .map(|l| l.unwrap().parse().unwrap()).collect();
 
-    println!("{}", where_2019(&steps));
-    println!("{}", giant_deck_2020(&steps));
+    answer::part1(where_2019(&steps));
+    answer::part2(giant_deck_2020(&steps));
 }
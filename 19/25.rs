@@ -1,140 +1,32 @@
-use std::io::{self, BufRead};
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+mod ascii;
+use ascii::Terminal;
 use std::collections::{HashSet, HashMap, VecDeque};
 use std::collections::hash_map::Entry;
+use std::env;
+use std::fs;
 
 
-fn step<'a, I: Iterator<Item = i64>>(program: &'a mut [i64], ip: usize, base: i64, input: &mut I) -> Option<(usize, i64, Option<i64>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        return None;
-    }
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 2);
-    assert!(mode1 <= 2);
-    assert!(mode2 <= 2);
-    let immflags = (mode0 == 1, mode1 == 1, mode2 == 1);
-    let relflags = (mode0 == 2, mode1 == 2, mode2 == 2);
-
-    let rel0 = if relflags.0 { base } else { 0 };
-    let rel1 = if relflags.1 { base } else { 0 };
-    let rel2 = if relflags.2 { base } else { 0 };
-    let imm0 = || program[ip + 1];
-    let imm1 = || program[ip + 2];
-    let val0 = || if immflags.0 { imm0() } else { program[(imm0() + rel0) as usize ] };
-    let val1 = || if immflags.1 { imm1() } else { program[(imm1() + rel1) as usize ] };
-
-    let mut0 = |program: &'a mut [i64]| {
-        assert!(!immflags.0); &mut program[(program[ip + 1] + rel0) as usize] };
-    let mut2 = |program: &'a mut [i64]| {
-        assert!(!immflags.2); &mut program[(program[ip + 3] + rel2) as usize] };
-
-    match opcode {
-        1 => {
-            *mut2(program) = val0() + val1();
-            Some((ip + 4, base, None))
-        },
-        2 => {
-            *mut2(program) = val0() * val1();
-            Some((ip + 4, base, None))
-        },
-        3 => {
-            *mut0(program) = input.next().unwrap();
-            Some((ip + 2, base, None))
-        }
-        4 => {
-            Some((ip + 2, base, Some(val0())))
-        },
-        5 => {
-            if val0() != 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        6 => {
-            if val0() == 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        7 => {
-            *mut2(program) = if val0() < val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        8 => {
-            *mut2(program) = if val0() == val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        9 => {
-            Some((ip + 2, base + val0(), None))
-        },
-        _ => panic!("something went wrong at {}: {}", ip, program[ip])
-    }
-}
-
-#[derive(Clone)]
-struct Computer {
-    program: Vec<i64>,
-    ip: usize,
-    base: i64,
-    iodebug: bool,
-}
-
-fn execute_dungeon(computer: &mut Computer, inputs: &str) -> Option<char> {
-    let mut input = inputs.bytes().map(|b| b as i64);
-    while let Some((newip, newbase, newout)) =
-            step(&mut computer.program, computer.ip, computer.base, &mut input) {
-        computer.ip = newip;
-        computer.base = newbase;
-        if let Some(out) = newout {
-            assert!(out <= 127);
-            return Some(out as u8 as char);
-        }
-    }
-    None
-}
-
-fn game_io(computer: &mut Computer, input: &str) -> String {
-    let mut chars = Vec::new();
-
-    while let Some(ch) = execute_dungeon(computer, input) {
-        if computer.iodebug {
-            print!("{}", ch);
-        }
-        if ch == '\n' {
-            break;
-        } else {
-            chars.push(ch);
-        }
-    }
-
-    chars.iter().collect()
-}
-
-// TODO: iterator or something
-fn read_line(computer: &mut Computer) -> String {
-    game_io(computer, "")
-}
-
-fn read_empty_line(computer: &mut Computer) {
-    let line = game_io(computer, "");
+fn read_empty_line(computer: &mut Terminal) {
+    let line = computer.read_line();
     assert_eq!(line, "");
 }
 
-fn read_expected_line(computer: &mut Computer, should_be: &str) {
-    let line = game_io(computer, "");
+fn read_expected_line(computer: &mut Terminal, should_be: &str) {
+    let line = computer.read_line();
     assert_eq!(line, should_be);
 }
 
-fn communicate_line(computer: &mut Computer, input: &str) {
-    if computer.iodebug {
-        println!("> {}", input);
-    }
-    let reply = game_io(computer, &(input.to_string() + "\n"));
+// the commands come with their newlines already
+fn communicate_line(computer: &mut Terminal, input: &str) {
+    computer.send_line(input.trim_end_matches('\n'));
+    let reply = computer.read_line();
     assert_eq!(reply, "");
+    // the pressure floor throws the droid back to the checkpoint on its own, so the step back from
+    // there is never read; don't let it leak into the next command
+    computer.cpu.inputs.clear();
 }
 
 #[derive(Debug, PartialEq)]
@@ -184,17 +76,17 @@ const FORBIDDEN_ITEMS: &[&str] = &[
  *
  * Command?
  */
-fn read_room(computer: &mut Computer, inventory: &mut Option<&mut Vec<String>>) -> Room {
+fn read_room(computer: &mut Terminal, inventory: &mut Option<&mut Vec<String>>) -> Room {
     read_empty_line(computer);
     read_empty_line(computer);
-    let title = read_line(computer);
-    let description = read_line(computer);
+    let title = computer.read_line();
+    let description = computer.read_line();
     read_empty_line(computer);
     read_expected_line(computer, "Doors here lead:");
 
     let mut doors = [false; 4];
     loop {
-        let door = read_line(computer);
+        let door = computer.read_line();
         match door.as_str() {
             "- north" => doors[0] = true,
             "- south" => doors[1] = true,
@@ -206,10 +98,10 @@ fn read_room(computer: &mut Computer, inventory: &mut Option<&mut Vec<String>>)
     }
 
     let mut items = Vec::new();
-    let line = read_line(computer);
+    let line = computer.read_line();
     if line == "Items here:" {
         loop {
-            let line = read_line(computer);
+            let line = computer.read_line();
             if line == "" {
                 break;
             }
@@ -250,7 +142,7 @@ struct Map {
     edges: HashMap<String, [Option<String>; 4]>,
 }
 
-fn crawl_dungeon(computer: &mut Computer, map: &mut Map, inventory: &mut Option<&mut Vec<String>>) -> String {
+fn crawl_dungeon(computer: &mut Terminal, map: &mut Map, inventory: &mut Option<&mut Vec<String>>) -> String {
     let room = read_room(computer, inventory);
     let this_title = room.title.clone();
     let doors = match map.rooms.entry(this_title.clone()) {
@@ -322,7 +214,7 @@ fn find_route(map: &Map, source_room: &str, dest_room: &str) -> Vec<&'static str
     route
 }
 
-fn enter_checkpoint(computer: &mut Computer, map: &Map, inventory: &mut Option<&mut Vec<String>>) {
+fn enter_checkpoint(computer: &mut Terminal, map: &Map, inventory: &mut Option<&mut Vec<String>>) {
     // FIXME: strip "=="s off
     let route = find_route(map, "== Hull Breach ==", "== Security Checkpoint ==");
     for turn in route {
@@ -331,22 +223,22 @@ fn enter_checkpoint(computer: &mut Computer, map: &Map, inventory: &mut Option<&
     }
 }
 
-fn drop_stuff(computer: &mut Computer, drop: &[&str]) {
+fn drop_stuff(computer: &mut Terminal, drop: &[&str]) {
     for d in drop {
         let command = "drop ".to_string() + d + "\n";
         communicate_line(computer, &command);
 
-        let line = read_line(computer);
+        let line = computer.read_line();
         assert_eq!(line, "You drop the ".to_string() + d + ".");
 
         read_empty_line(computer);
 
-        let line = read_line(computer);
+        let line = computer.read_line();
         assert_eq!(line, "Command?");
     }
 }
 
-fn attempt_weight(computer: &Computer, drop: &[&str], direction: &str) -> bool {
+fn attempt_weight(computer: &Terminal, drop: &[&str], direction: &str) -> bool {
     let mut computer = computer.clone();
 
     drop_stuff(&mut computer, drop);
@@ -355,7 +247,7 @@ fn attempt_weight(computer: &Computer, drop: &[&str], direction: &str) -> bool {
 
     read_room(&mut computer, &mut None);
     //A loud, robotic voice says "Analysis complete! You may proceed." and you enter the cockpit.
-    let line = read_line(&mut computer);
+    let line = computer.read_line();
     if line == "" {
         // ejected back to checkpoint
         read_room(&mut computer, &mut None);
@@ -367,7 +259,7 @@ fn attempt_weight(computer: &Computer, drop: &[&str], direction: &str) -> bool {
 }
 
 // floor is in the south
-fn reach_correct_weight<'a>(computer: &Computer, inventory: &'a [String], direction: &str) -> Vec<&'a str> {
+fn reach_correct_weight<'a>(computer: &Terminal, inventory: &'a [String], direction: &str) -> Vec<&'a str> {
     let n = inventory.len();
     let options = 1 << n;
     for bitmap in 0..options {
@@ -388,7 +280,7 @@ fn floor_direction(map: &Map) -> &'static str {
     route[0]
 }
 
-fn embark(computer: &mut Computer) -> String {
+fn embark(computer: &mut Terminal) -> String {
     read_empty_line(computer);
 
     // search for a complete map and collect all items that don't cause game over
@@ -405,19 +297,31 @@ fn embark(computer: &mut Computer) -> String {
     // what's the way to the magical chamber with the floor?
     let direction = floor_direction(&map);
 
+    // everything picked up and standing next to the floor; a good place to continue by hand with
+    // the debugger, or to look at what went wrong with the weights with AOC_RESUME
+    if let Ok(path) = env::var("AOC_SNAPSHOT") {
+        let notes = [("floor", direction.trim().to_string()), ("carrying", inventory.join(", "))];
+        fs::write(&path, computer.cpu.snapshot_with_notes(&notes)).unwrap();
+        println!("# saved the checkpoint in {}, {} to the floor", path, direction.trim());
+    }
+
+    onto_the_floor(computer, &inventory, direction)
+}
+
+fn onto_the_floor(computer: &mut Terminal, inventory: &[String], direction: &str) -> String {
     // figure out what's the right weight allowed by the pressure-sensitive floor
-    let useless_items = reach_correct_weight(&computer, &inventory, &direction);
+    let useless_items = reach_correct_weight(&computer, inventory, direction);
     // on my setup: ["weather machine", "polygon", "manifold", "hypercube"]
     // leave some items here and step on the floor
     drop_stuff(computer, &useless_items);
-    communicate_line(computer, &direction);
+    communicate_line(computer, direction);
     // parse where we went, but the inventory is no longer useful
     read_room(computer, &mut None);
 
     // end of story
-    let line = read_line(computer);
+    let line = computer.read_line();
     assert_eq!(line, "Santa notices your small droid, looks puzzled for a moment, realizes what has happened, and radios your ship directly.");
-    let pw_line = read_line(computer);
+    let pw_line = computer.read_line();
     assert!(pw_line.starts_with("\"Oh, hello!"));
     pw_line.chars().filter(|&ch| ch >= '0' && ch <= '9').collect()
 }
@@ -455,26 +359,22 @@ fn dump_graphviz(map: &Map) {
 }
 
 fn play_game(program: &[i64]) -> String {
-    let mut prog = program.to_vec();
-    prog.resize(prog.len() + prog.len(), 0);
-    let mut computer = Computer {
-        program: prog,
-        ip: 0,
-        base: 0,
-        iodebug: false,
-    };
+    let mut computer = Terminal::new(intcode::Computer::new(program));
     embark(&mut computer)
 }
 
+// from the checkpoint saved above
+fn resume_game(path: &str) -> String {
+    let text = fs::read_to_string(path).unwrap();
+    let mut computer = Terminal::new(intcode::Computer::from_snapshot(&text).unwrap());
+    let direction = intcode::snapshot_note(&text, "floor").expect("no floor in the snapshot");
+    let inventory = intcode::snapshot_note(&text, "carrying").expect("no items in the snapshot")
+        .split(", ").filter(|item| !item.is_empty()).map(String::from).collect::<Vec<_>>();
+    onto_the_floor(&mut computer, &inventory, direction)
+}
+
 fn crawl_map_graphviz(program: &[i64]) {
-    let mut prog = program.to_vec();
-    prog.resize(prog.len() + prog.len(), 0);
-    let mut computer = Computer {
-        program: prog,
-        ip: 0,
-        base: 0,
-        iodebug: false,
-    };
+    let mut computer = Terminal::new(intcode::Computer::new(program));
 
     read_empty_line(&mut computer);
 
@@ -488,9 +388,13 @@ fn crawl_map_graphviz(program: &[i64]) {
 }
 
 fn main() {
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let _session = answer::start_commented("# ");
+    let program = intcode::read_program();
 
     crawl_map_graphviz(&program);
-    println!("# main airlock password: {}", play_game(&program));
+    // AOC_RESUME=file to skip the crawl and go on from a checkpoint saved with AOC_SNAPSHOT
+    match env::var("AOC_RESUME") {
+        Ok(path) => answer::part1(resume_game(&path)),
+        Err(_) => answer::part1(play_game(&program)),
+    }
 }
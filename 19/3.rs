@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashSet, HashMap};
 use std::str::FromStr;
@@ -72,6 +74,7 @@ fn parseline(stepline: &str) -> Vec<Step> {
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(crossing_distance(
             &parseline("R8,U5,L5,D3"),
             &parseline("U7,R6,D4,L4")),
@@ -100,6 +103,6 @@ fn main() {
     let wire_paths: Vec<Vec<Step>> = io::stdin().lock().lines()
         .map(|stepline| parseline(&stepline.unwrap()))
         .collect();
-    println!("{}", crossing_distance(&wire_paths[0], &wire_paths[1]));
-    println!("{}", crossing_distance_steps(&wire_paths[0], &wire_paths[1]));
+    answer::part1(crossing_distance(&wire_paths[0], &wire_paths[1]));
+    answer::part2(crossing_distance_steps(&wire_paths[0], &wire_paths[1]));
 }
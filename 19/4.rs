@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 const DIGITS: usize = 6;
@@ -56,6 +58,7 @@ fn meets_criteria_b(pw: u32) -> bool {
 }
 
 fn main() {
+    let _session = answer::start();
     assert!(digit(135679, 0) == 1);
     assert!(digit(135679, 1) == 3);
     assert!(digit(135679, 5) == 9);
@@ -73,7 +76,7 @@ fn main() {
 
     // the six-digit key fact is trivial because the puzzle input is contained
     let n = (range[0]..=range[1]).filter(|&pw| meets_criteria(pw)).count();
-    println!("{}", n);
+    answer::part1(n);
     let n = (range[0]..=range[1]).filter(|&pw| meets_criteria_b(pw)).count();
-    println!("{}", n);
+    answer::part2(n);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -64,10 +66,11 @@ fn parse_orbit(desc: &str) -> (String, String) {
 }
 
 fn main() {
+    let _session = answer::start();
     // each "moon" should orbit directly just one "planet", so no map collisions
     let orbits: HashMap<String, String> = io::stdin().lock().lines()
         .map(|orbitdesc| parse_orbit(&orbitdesc.unwrap()))
         .collect();
-    println!("{:?}", orbit_count(&orbits));
-    println!("{:?}", path_to_santa(&orbits));
+    answer::part1(orbit_count(&orbits));
+    answer::part2(path_to_santa(&orbits).unwrap());
 }
@@ -1,86 +1,10 @@
-use std::io::{self, BufRead};
-
-fn step<'a, 'b, I: Iterator<Item = &'b i64>>(program: &'a mut [i64], ip: usize, input: &mut I) -> Option<(usize, Option<i64>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        // short circuit this, the discontinuity is annoying
-        return None;
-    }
-
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 1);
-    assert!(mode1 <= 1);
-    assert!(mode2 <= 1);
-    let immflags = (mode0 == 1, mode1 == 1, mode2 == 1);
-
-    // indirection via closures instead of direct variables because indexing might go off with
-    // arbitrary values: only evaluate when it's known that indexing is okay. Need this to avoid
-    // repetition in the opcode decoding
-
-    let imm0 = || program[ip + 1];
-    let imm1 = || program[ip + 2];
-    let val0 = || if immflags.0 { imm0() } else { program[imm0() as usize ] };
-    let val1 = || if immflags.1 { imm1() } else { program[imm1() as usize ] };
-    // program as input for lifetime; using imm0 here would cause more lifetime trouble
-    let mut0 = |program: &'a mut [i64]| { assert!(!immflags.0); &mut program[program[ip + 1] as usize] };
-    let mut2 = |program: &'a mut [i64]| { assert!(!immflags.2); &mut program[program[ip + 3] as usize] };
-
-    match opcode {
-        1 => {
-            *mut2(program) = val0() + val1();
-            Some((ip + 4, None))
-        },
-        2 => {
-            *mut2(program) = val0() * val1();
-            Some((ip + 4, None))
-        },
-        3 => {
-            *mut0(program) = *input.next().unwrap();
-            Some((ip + 2, None))
-        }
-        4 => {
-            Some((ip + 2, Some(val0())))
-        },
-        5 => {
-            if val0() != 0 {
-                Some((val1() as usize, None))
-            } else {
-                Some((ip + 3, None))
-            }
-        },
-        6 => {
-            if val0() == 0 {
-                Some((val1() as usize, None))
-            } else {
-                Some((ip + 3, None))
-            }
-        },
-        7 => {
-            *mut2(program) = if val0() < val1() { 1 } else { 0 };
-            Some((ip + 4, None))
-        },
-        8 => {
-            *mut2(program) = if val0() == val1() { 1 } else { 0 };
-            Some((ip + 4, None))
-        },
-        _ => panic!("something went wrong at {}: {}", ip, program[ip])
-    }
-}
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+use intcode::Computer;
 
 fn execute(program: &[i64], inputs: &[i64]) -> Option<i64> {
-    let mut program = program.to_vec();
-    let mut ip = 0;
-    let mut output = None;
-    let mut input = inputs.iter();
-
-    while let Some((newip, newout)) = step(&mut program, ip, &mut input) {
-        output = newout;
-        ip = newip;
-    }
-
-    output
+    Computer::new(program).run_to_halt(inputs).last().copied()
 }
 
 fn swap_codes(a: i64, i: usize, j: usize) -> i64 {
@@ -132,35 +56,18 @@ fn max_thruster_signal(program: &[i64]) -> i64 {
         .max_by_key(|&(_, sig)| sig).unwrap().1
 }
 
-struct Computer<'a> {
-    program: &'a mut [i64],
-    ip: usize
-}
-
 fn drive_output(machine: &mut Computer, inputs: &[i64]) -> Option<i64> {
-    let program = &mut machine.program;
-    let mut ip = machine.ip;
-    let mut input = inputs.iter();
-
-    while let Some((newip, out)) = step(program, ip, &mut input) {
-        ip = newip;
-        if out.is_some() {
-            machine.ip = ip;
-            return out;
-        }
-    }
-
-    // stopped without input
-    None
+    // stops with None without input
+    machine.run_with(&mut inputs.iter().copied()).output()
 }
 
 fn thruster_signal_loop(program: &[i64], mut phasecode: i64) -> i64 {
     let amps = &mut [
-        Computer { program: &mut program.to_vec(), ip: 0 },
-        Computer { program: &mut program.to_vec(), ip: 0 },
-        Computer { program: &mut program.to_vec(), ip: 0 },
-        Computer { program: &mut program.to_vec(), ip: 0 },
-        Computer { program: &mut program.to_vec(), ip: 0 },
+        Computer::new(program),
+        Computer::new(program),
+        Computer::new(program),
+        Computer::new(program),
+        Computer::new(program),
     ];
     let mut prev = 0;
 
@@ -198,6 +105,7 @@ fn max_thruster_signal_loop(program: &[i64]) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(swap_codes(0x12345, 0, 1), 0x12354);
     assert_eq!(swap_codes(0x12345, 1, 3), 0x14325);
 
@@ -219,9 +127,8 @@ fn main() {
             -5,54,1105,1,12,1,53,54,53,1008,54,0,55,1001,55,1,55,2,53,55,53,4,
             53,1001,56,-1,56,1005,56,6,99,0,0,0,0,10]), 18216);
 
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let program = intcode::read_program();
 
-    println!("{}", max_thruster_signal(&program));
-    println!("{}", max_thruster_signal_loop(&program));
+    answer::part1(max_thruster_signal(&program));
+    answer::part2(max_thruster_signal_loop(&program));
 }
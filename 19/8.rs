@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn num_digits(layer: &[u8]) -> (usize, usize, usize) {
@@ -23,19 +25,23 @@ fn decode(digits: &[u8]) -> [u8; 25 * 6] {
     image
 }
 
-fn print_layer(layer: &[u8]) {
+fn render_layer(layer: &[u8]) -> String {
+    let mut picture = String::new();
     for row in layer.chunks(25) {
         for &ch in row {
-            print!("{}", if ch == b'1' { '#' } else { ' ' });
+            picture.push(if ch == b'1' { '#' } else { '.' });
         }
-        println!();
+        picture.push('\n');
     }
+    picture
 }
 
 fn main() {
+    let _session = answer::start();
     let digits: Vec<u8> = io::stdin().lock().lines().next().unwrap().unwrap()
         .bytes().collect();
     let fewest_0 = digits.chunks(25 * 6).map(num_digits).min_by_key(|&d| d.0).unwrap();
-    println!("{}", fewest_0.1 * fewest_0.2);
-    print_layer(&decode(&digits));
+    answer::part1(fewest_0.1 * fewest_0.2);
+    // the message in the image
+    answer::part2(render_layer(&decode(&digits)));
 }
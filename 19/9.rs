@@ -1,114 +1,22 @@
-use std::io::{self, BufRead};
-
-fn step<'a, 'b, I: Iterator<Item = &'b i64>>(program: &'a mut [i64], ip: usize, base: i64, input: &mut I) -> Option<(usize, i64, Option<i64>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        // short circuit this, the discontinuity is annoying
-        return None;
-    }
-
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 2);
-    assert!(mode1 <= 2);
-    assert!(mode2 <= 2);
-    let immflags = (mode0 == 1, mode1 == 1, mode2 == 1);
-    let relflags = (mode0 == 2, mode1 == 2, mode2 == 2);
-
-    // indirection via closures instead of direct variables because indexing might go off with
-    // arbitrary values: only evaluate when it's known that indexing is okay. Need this to avoid
-    // repetition in the opcode decoding
-
-    let rel0 = if relflags.0 { base } else { 0 };
-    let rel1 = if relflags.1 { base } else { 0 };
-    let rel2 = if relflags.2 { base } else { 0 };
-    let imm0 = || program[ip + 1];
-    let imm1 = || program[ip + 2];
-    let val0 = || if immflags.0 { imm0() } else { program[(imm0() + rel0) as usize ] };
-    let val1 = || if immflags.1 { imm1() } else { program[(imm1() + rel1) as usize ] };
-    // program as input for lifetime; using imm0 here would cause more lifetime trouble
-    let mut0 = |program: &'a mut [i64]| {
-        assert!(!immflags.0); &mut program[(program[ip + 1] + rel0) as usize] };
-    let mut2 = |program: &'a mut [i64]| {
-        assert!(!immflags.2); &mut program[(program[ip + 3] + rel2) as usize] };
-
-    match opcode {
-        1 => {
-            *mut2(program) = val0() + val1();
-            Some((ip + 4, base, None))
-        },
-        2 => {
-            *mut2(program) = val0() * val1();
-            Some((ip + 4, base, None))
-        },
-        3 => {
-            *mut0(program) = *input.next().unwrap();
-            Some((ip + 2, base, None))
-        }
-        4 => {
-            Some((ip + 2, base, Some(val0())))
-        },
-        5 => {
-            if val0() != 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        6 => {
-            if val0() == 0 {
-                Some((val1() as usize, base, None))
-            } else {
-                Some((ip + 3, base, None))
-            }
-        },
-        7 => {
-            *mut2(program) = if val0() < val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        8 => {
-            *mut2(program) = if val0() == val1() { 1 } else { 0 };
-            Some((ip + 4, base, None))
-        },
-        9 => {
-            Some((ip + 2, base + val0(), None))
-        },
-        _ => panic!("something went wrong at {}: {}", ip, program[ip])
-    }
-}
+#[path = "../lib/answer.rs"]
+mod answer;
+mod intcode;
+use intcode::Computer;
 
 fn execute(program: &[i64], inputs: &[i64]) -> Vec<i64> {
-    let mut program = program.to_vec();
-    // make the available memory "much larger than the initial program"
-    program.resize(program.len() + 1000, 0);
-
-    let mut ip = 0;
-    let mut output = Vec::new();
-    let mut input = inputs.iter();
-    let mut base = 0;
-
-    while let Some((newip, newbase, newout)) = step(&mut program, ip, base, &mut input) {
-        if newout.is_some() {
-            output.push(newout.unwrap());
-        }
-        ip = newip;
-        base = newbase;
-    }
-
-    output
+    Computer::new(program).run_to_halt(inputs)
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(execute(&[109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99], &[]),
         &[109,1,204,-1,1001,100,1,100,1008,100,16,101,1006,101,0,99]);
 
     assert!(execute(&[1102,34915192,34915192,7,4,7,99,0], &[])[0] / 1_000_000_000_000_000 < 10);
     assert!(execute(&[1102,34915192,34915192,7,4,7,99,0], &[])[0] / 1_000_000_000_000_000 > 0);
 
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let program = intcode::read_program();
 
-    println!("{:?}", execute(&program, &[1])[0]);
-    println!("{:?}", execute(&program, &[2])[0]);
+    answer::part1(execute(&program, &[1])[0]);
+    answer::part2(execute(&program, &[2])[0]);
 }
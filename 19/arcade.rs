@@ -0,0 +1,173 @@
+// The arcade cabinet of day 13: the program draws (x, y, tile) triples and a score at (-1, 0), and
+// reads the joystick as -1, 0 or 1 whenever it's about to move things. Each read is a natural
+// frame boundary, as the screen is complete then. Who moves the joystick is up to a controller.
+#![allow(dead_code)]
+
+use crate::intcode::{Computer, RunState};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, Write};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Tile {
+    Empty,
+    Wall,
+    Block,
+    Paddle,
+    Ball
+}
+use Tile::*;
+
+// each tile is a square of this many pixels in the ppm frames
+const PIXELS: i64 = 4;
+
+#[derive(Debug, Clone, Default)]
+pub struct Screen {
+    pub tiles: HashMap<(i64, i64), Tile>,
+    pub score: i64,
+    pub ball: i64,
+    pub paddle: i64,
+}
+
+impl Screen {
+    fn draw(&mut self, x: i64, y: i64, value: i64) {
+        if x == -1 && y == 0 {
+            self.score = value;
+            return;
+        }
+        let tile = match value {
+            0 => Empty,
+            1 => Wall,
+            2 => Block,
+            3 => {
+                self.paddle = x;
+                Paddle
+            },
+            4 => {
+                self.ball = x;
+                Ball
+            },
+            _ => panic!("bad tile")
+        };
+        self.tiles.insert((x, y), tile);
+    }
+
+    pub fn blocks(&self) -> usize {
+        self.tiles.values().filter(|&&t| t == Block).count()
+    }
+
+    fn bounds(&self) -> ((i64, i64), (i64, i64)) {
+        let minx = self.tiles.keys().map(|&(x, _)| x).min().unwrap_or(0);
+        let maxx = self.tiles.keys().map(|&(x, _)| x).max().unwrap_or(0);
+        let miny = self.tiles.keys().map(|&(_, y)| y).min().unwrap_or(0);
+        let maxy = self.tiles.keys().map(|&(_, y)| y).max().unwrap_or(0);
+        ((minx, miny), (maxx, maxy))
+    }
+
+    pub fn render(&self) -> String {
+        let ((minx, miny), (maxx, maxy)) = self.bounds();
+        let mut s = format!("score {}\n", self.score);
+        for y in miny..=maxy {
+            for x in minx..=maxx {
+                s.push(match *self.tiles.get(&(x, y)).unwrap_or(&Empty) {
+                    Empty => ' ',
+                    Wall => '*',
+                    Block => 'x',
+                    Paddle => '-',
+                    Ball => 'o',
+                });
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    // plain P3 like the other puzzles write, so the frames go straight to ffmpeg or convert
+    pub fn write_ppm(&self, name: &str) {
+        let ((minx, miny), (maxx, maxy)) = self.bounds();
+        let w = (maxx - minx + 1) * PIXELS;
+        let h = (maxy - miny + 1) * PIXELS;
+        let mut s = String::new();
+        s.push_str(&format!("P3\n{} {}\n255\n", w, h));
+        for py in 0..h {
+            for px in 0..w {
+                let pos = (minx + px / PIXELS, miny + py / PIXELS);
+                s.push_str(match *self.tiles.get(&pos).unwrap_or(&Empty) {
+                    Empty => "0 0 0 ",
+                    Wall => "128 128 128 ",
+                    Block => "255 160 0 ",
+                    Paddle => "255 255 255 ",
+                    Ball => "255 0 0 ",
+                });
+            }
+            s.push_str("\n");
+        }
+        let mut file = File::create(name).unwrap();
+        file.write_all(s.as_bytes()).unwrap();
+    }
+}
+
+pub trait Controller {
+    // joystick position for the next move, or None to walk away from the game
+    fn joystick(&mut self, screen: &Screen) -> Option<i64>;
+}
+
+// just keep the paddle under the ball
+pub struct Autopilot;
+
+impl Controller for Autopilot {
+    fn joystick(&mut self, screen: &Screen) -> Option<i64> {
+        Some((screen.ball - screen.paddle).signum())
+    }
+}
+
+// a line per move: a or h for left, d or l for right, just enter to stay, q to quit
+pub struct Human<R: BufRead> {
+    pub keys: R,
+}
+
+impl<R: BufRead> Controller for Human<R> {
+    fn joystick(&mut self, screen: &Screen) -> Option<i64> {
+        print!("{}> ", screen.render());
+        std::io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if self.keys.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        match line.trim() {
+            "a" | "h" => Some(-1),
+            "d" | "l" => Some(1),
+            "q" => None,
+            _ => Some(0),
+        }
+    }
+}
+
+// run until the game is over or the controller gives up, showing each frame to the callback
+pub fn play(computer: &mut Computer, controller: &mut dyn Controller, mut frame: impl FnMut(&Screen)) -> Screen {
+    let mut screen = Screen::default();
+    let mut triple = Vec::new();
+    loop {
+        match computer.run() {
+            RunState::Output(value) => {
+                triple.push(value);
+                if let [x, y, value] = triple[..] {
+                    screen.draw(x, y, value);
+                    triple.clear();
+                }
+            },
+            RunState::NeedInput => {
+                frame(&screen);
+                match controller.joystick(&screen) {
+                    Some(position) => computer.push_input(position),
+                    None => break,
+                }
+            },
+            RunState::Halted => {
+                frame(&screen);
+                break;
+            },
+        }
+    }
+    screen
+}
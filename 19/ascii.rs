@@ -0,0 +1,86 @@
+// Line based text I/O for the Intcode programs that talk ASCII: the scaffold robot, the
+// springdroid and the text adventure. Whatever doesn't fit in ASCII, like the amount of dust or
+// the hull damage at the end, comes out as a separate value instead of a broken character.
+#![allow(dead_code)]
+
+use crate::intcode::{Computer, RunState};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Output {
+    // without the newline
+    Line(String),
+    Value(i64),
+}
+
+#[derive(Debug, Clone)]
+pub struct Terminal {
+    pub cpu: Computer,
+    // characters of a line that hasn't ended yet
+    partial: String,
+    halted: bool,
+    // print the traffic as it goes, to see what the program is up to
+    pub echo: bool,
+}
+
+impl Terminal {
+    pub fn new(cpu: Computer) -> Self {
+        Terminal { cpu, partial: String::new(), halted: false, echo: false }
+    }
+
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    pub fn send(&mut self, text: &str) {
+        self.cpu.push_str(text);
+    }
+
+    pub fn send_line(&mut self, line: &str) {
+        if self.echo {
+            println!("> {}", line);
+        }
+        self.cpu.push_str(line);
+        self.cpu.push_input(10);
+    }
+
+    // The next full line or value, or None if the program waits for input or has halted. A
+    // prompt that doesn't end in a newline is given as a line of its own when the program stops.
+    pub fn read(&mut self) -> Option<Output> {
+        let out = loop {
+            match self.cpu.run() {
+                RunState::Output(10) => break Output::Line(std::mem::take(&mut self.partial)),
+                RunState::Output(ch @ 0..=127) => self.partial.push(ch as u8 as char),
+                RunState::Output(value) => break Output::Value(value),
+                state => {
+                    self.halted = state == RunState::Halted;
+                    if self.partial.is_empty() {
+                        return None;
+                    }
+                    break Output::Line(std::mem::take(&mut self.partial));
+                },
+            }
+        };
+        if self.echo {
+            match &out {
+                Output::Line(line) => println!("{}", line),
+                Output::Value(value) => println!("= {}", value),
+            }
+        }
+        Some(out)
+    }
+
+    // the next line of text; nothing counts as an empty line, so a stopped program reads as
+    // empty lines forever
+    pub fn read_line(&mut self) -> String {
+        match self.read() {
+            Some(Output::Line(line)) => line,
+            Some(Output::Value(value)) => panic!("expected text, got {}", value),
+            None => String::new(),
+        }
+    }
+
+    // everything until the program stops
+    pub fn read_all(&mut self) -> Vec<Output> {
+        std::iter::from_fn(|| self.read()).collect()
+    }
+}
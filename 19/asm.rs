@@ -0,0 +1,424 @@
+// Intcode instructions in a readable form, for the disassembler and the debugger
+#![allow(dead_code)]
+
+use crate::intcode::{self, Mode};
+use std::fmt;
+use std::fs;
+use std::sync::Mutex;
+
+#[derive(Debug)]
+pub enum SourceParam {
+    Immediate(i64),
+    Position(i64),
+    Relative(i64),
+}
+
+impl fmt::Display for SourceParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Immediate(val) => write!(f, "{:>6}", val),
+            //Self::Position(addr) => write!(f, "[{:>4}]", addr),
+            Self::Position(addr) => addr_nickname(addr).map(|name| write!(f, "{:>6}", name)).unwrap_or_else(|| write!(f, "[{:>4}]", addr)),
+            Self::Relative(addr) => write!(f, "[{:>4} + base]", addr),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum DestParam {
+    Position(i64),
+    Relative(i64),
+}
+
+
+impl fmt::Display for DestParam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            //Self::Position(addr) => write!(f, "[{:>4}]", addr),
+            Self::Position(addr) => addr_nickname(addr).map(|name| write!(f, "{:>6}", name)).unwrap_or_else(|| write!(f, "[{:>4}]", addr)),
+            Self::Relative(addr) => write!(f, "[{:>4} + base]", addr),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct OpAdd {
+    pub a: SourceParam,
+    pub b: SourceParam,
+    pub dest: DestParam,
+}
+
+impl fmt::Display for OpAdd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "add {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpMul {
+    pub a: SourceParam,
+    pub b: SourceParam,
+    pub dest: DestParam,
+}
+
+impl fmt::Display for OpMul {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "mul {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpIn {
+    pub dest: DestParam,
+}
+
+impl fmt::Display for OpIn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "in  {:>6}", self.dest)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpOut {
+    pub val: SourceParam,
+}
+
+impl fmt::Display for OpOut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "out {:>6}", self.val)
+    }
+}
+
+// jump if argument is not zero
+#[derive(Debug)]
+pub struct OpJnz {
+    pub src: SourceParam,
+    pub addr: SourceParam,
+}
+
+impl fmt::Display for OpJnz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "jnz {:>6} {:>6}", self.src, self.addr)
+    }
+}
+
+// jump if argument is zero
+#[derive(Debug)]
+pub struct OpJz {
+    pub src: SourceParam,
+    pub addr: SourceParam,
+}
+
+impl fmt::Display for OpJz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "jz  {:>6} {:>6}", self.src, self.addr)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpLess {
+    pub a: SourceParam,
+    pub b: SourceParam,
+    pub dest: DestParam,
+}
+
+impl fmt::Display for OpLess {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "lt  {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpEqual {
+    pub a: SourceParam,
+    pub b: SourceParam,
+    pub dest: DestParam,
+}
+
+impl fmt::Display for OpEqual {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "eq  {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
+    }
+}
+
+#[derive(Debug)]
+pub struct OpBase {
+    pub val: SourceParam,
+}
+
+impl fmt::Display for OpBase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "base {:>6}", self.val)
+    }
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+    Add(OpAdd),
+    Mul(OpMul),
+    In(OpIn),
+    Out(OpOut),
+    Jnz(OpJnz),
+    Jz(OpJz),
+    Lt(OpLess),
+    Eq(OpEqual),
+    Base(OpBase),
+    Stop,
+}
+use Instruction::*;
+
+impl Instruction {
+    // what this reads and where it writes, if anywhere
+    pub fn operands(&self) -> (Vec<&SourceParam>, Option<&DestParam>) {
+        match self {
+            Add(op)  => (vec![&op.a, &op.b], Some(&op.dest)),
+            Mul(op)  => (vec![&op.a, &op.b], Some(&op.dest)),
+            In(op)   => (vec![], Some(&op.dest)),
+            Out(op)  => (vec![&op.val], None),
+            Jnz(op)  => (vec![&op.src, &op.addr], None),
+            Jz(op)   => (vec![&op.src, &op.addr], None),
+            Lt(op)   => (vec![&op.a, &op.b], Some(&op.dest)),
+            Eq(op)   => (vec![&op.a, &op.b], Some(&op.dest)),
+            Base(op) => (vec![&op.val], None),
+            Stop     => (vec![], None),
+        }
+    }
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Add(op)  => write!(f, "{}", op),
+            Mul(op)  => write!(f, "{}", op),
+            In(op)   => write!(f, "{}", op),
+            Out(op)  => write!(f, "{}", op),
+            Jnz(op)  => write!(f, "{}", op),
+            Jz(op)   => write!(f, "{}", op),
+            Lt(op)   => write!(f, "{}", op),
+            Eq(op)   => write!(f, "{}", op),
+            Base(op) => write!(f, "{}", op),
+            Stop     => write!(f, "stop"),
+        }
+    }
+}
+
+// instruction at ip, where the next one would be and where it can jump to, if known; None if this
+// doesn't decode (probably data)
+pub fn disassemble(program: &[i64], ip: usize) -> Option<(usize, Instruction, Option<usize>)> {
+    // unknown opcodes or modes are probably a data section after the program
+    let (opcode, modes) = intcode::decode(*program.get(ip)?)?;
+    if opcode == 99 {
+        return Some((ip + 1, Instruction::Stop, None));
+    }
+    // and so is something that would run off the end or write to an immediate
+    if ip + intcode::instruction_size(opcode).unwrap() > program.len() {
+        return None;
+    }
+    let dest_param = match opcode {
+        1 | 2 | 7 | 8 => Some(2),
+        3 => Some(0),
+        _ => None,
+    };
+    if dest_param.map_or(false, |x| modes[x] == Mode::Immediate) {
+        return None;
+    }
+
+    let immflags = modes.map(|m| m == Mode::Immediate);
+    let relflags = modes.map(|m| m == Mode::Relative);
+
+    let simm = |x| SourceParam::Immediate(program[ip + 1 + x]);
+    let spos = |x| SourceParam::Position(program[ip + 1 + x]);
+    let srel = |x| SourceParam::Relative(program[ip + 1 + x]);
+
+    let dpos = |x| DestParam::Position(program[ip + 1 + x]);
+    let drel = |x| DestParam::Relative(program[ip + 1 + x]);
+
+    let input = |x: usize| {
+        assert!(!(immflags[x] && relflags[x]));
+        if immflags[x] { simm(x) }
+        else if relflags[x] { srel(x) }
+        else { spos(x) }
+    };
+
+    let output = |x: usize| {
+        assert!(!immflags[x]);
+        if relflags[x] { drel(x) }
+        else { dpos(x) }
+    };
+
+    // indirect jumps are known only at runtime; jumps out of the program are probably misdecoded data
+    let val1_num = ||
+        if immflags[1] && program[ip + 2] >= 0 && (program[ip + 2] as usize) < program.len() {
+            Some(program[ip + 2] as usize)
+        } else {
+            None
+        };
+
+    match opcode {
+        1 => { Some((
+                    ip + 4,
+                    Instruction::Add(OpAdd {
+                        a: input(0),
+                        b: input(1),
+                        dest: output(2),
+                    }),
+                    None))
+        },
+
+        2 => { Some((
+                    ip + 4,
+                    Instruction::Mul(OpMul {
+                        a: input(0),
+                        b: input(1),
+                        dest: output(2),
+                    }),
+                    None))
+        },
+
+        3 => { Some((
+                    ip + 2,
+                    Instruction::In(OpIn {
+                        dest: output(0),
+                    }),
+                    None))
+        },
+
+        4 => { Some((
+                    ip + 2,
+                    Instruction::Out(OpOut {
+                        val: input(0),
+                    }),
+                    None))
+        },
+
+        5 => { Some((
+                    ip + 3,
+                    Instruction::Jnz(OpJnz {
+                        src: input(0),
+                        addr: input(1),
+                    }),
+                    val1_num()))
+        },
+
+        6 => { Some((
+                    ip + 3,
+                    Instruction::Jz(OpJz {
+                        src: input(0),
+                        addr: input(1),
+                    }),
+                    val1_num()))
+        },
+
+        7 => { Some((
+                    ip + 4,
+                    Instruction::Lt(OpLess {
+                        a: input(0),
+                        b: input(1),
+                        dest: output(2),
+                    }),
+                    None))
+        },
+
+        8 => { Some((
+                    ip + 4,
+                    Instruction::Eq(OpEqual {
+                        a: input(0),
+                        b: input(1),
+                        dest: output(2),
+                    }),
+                    None))
+        },
+
+        9 => { Some((
+                    ip + 2,
+                    Instruction::Base(OpBase {
+                        val: input(0),
+                    }),
+                    None))
+        },
+
+        _ => unreachable!()
+    }
+}
+
+// Names for data addresses, loaded from a symbol file with lines like
+//
+// 1033 inp bool # last movement command
+//
+// that is, address, name, an optional type and an optional comment after a #. Lines starting with
+// a # are ignored. See 15.sym.
+#[derive(Debug, Clone)]
+pub struct Symbol {
+    pub addr: i64,
+    pub name: String,
+    pub kind: Option<String>,
+    pub comment: Option<String>,
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.addr, self.name)?;
+        if let Some(kind) = &self.kind {
+            write!(f, " {}", kind)?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, " # {}", comment)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_symbols(text: &str) -> Result<Vec<Symbol>, String> {
+    let mut symbols = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        let (fields, comment) = match line.split_once('#') {
+            Some((fields, comment)) => (fields, Some(comment.trim().to_string())),
+            None => (line, None),
+        };
+        let words = fields.split_whitespace().collect::<Vec<_>>();
+        if words.is_empty() {
+            continue;
+        }
+        if words.len() > 3 {
+            return Err(format!("line {}: too many fields: {}", i + 1, line));
+        }
+        let addr = words[0].parse::<i64>()
+            .map_err(|_| format!("line {}: bad address: {}", i + 1, words[0]))?;
+        if words.len() < 2 {
+            return Err(format!("line {}: no name for {}", i + 1, addr));
+        }
+        symbols.push(Symbol {
+            addr,
+            name: words[1].to_string(),
+            kind: words.get(2).map(|k| k.to_string()),
+            comment: comment.filter(|c| !c.is_empty()),
+        });
+    }
+    Ok(symbols)
+}
+
+// the instruction formatting looks names up from here, so they're global
+static SYMBOLS: Mutex<Vec<Symbol>> = Mutex::new(Vec::new());
+
+pub fn set_symbols(symbols: Vec<Symbol>) {
+    *SYMBOLS.lock().unwrap() = symbols;
+}
+
+pub fn load_symbols(path: &str) -> Vec<Symbol> {
+    let text = fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    let symbols = parse_symbols(&text).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    set_symbols(symbols.clone());
+    symbols
+}
+
+pub fn symbol(addr: i64) -> Option<Symbol> {
+    SYMBOLS.lock().unwrap().iter().find(|s| s.addr == addr).cloned()
+}
+
+pub fn addr_nickname(addr: i64) -> Option<String> {
+    symbol(addr).map(|s| s.name)
+}
+
+pub fn nickname_addr(name: &str) -> Option<i64> {
+    SYMBOLS.lock().unwrap().iter().find(|s| s.name == name).map(|s| s.addr)
+}
@@ -0,0 +1,309 @@
+// Stepping debugger for any Intcode program, built on the disassembler's instruction decoding. The
+// program comes either from a file argument with the commands typed in, or from the first line of
+// stdin with the commands following it as a script:
+//
+// 19/debugger -s 19/15.sym 19/15.input
+// (head -1 19/15.input; echo "b 31"; echo "i 1"; echo c) | 19/debugger
+//
+// Addresses are plain numbers or names from the symbol file given with -s, see asm.rs for the
+// format. Type "help" for the commands. The file argument may also be a snapshot written with
+// "save" or by a puzzle run, to continue from exactly that state:
+//
+// 19/debugger checkpoint.snap
+mod intcode;
+mod asm;
+use intcode::{Computer, Mode, Step};
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+const HELP: &str = "\
+s [n]         step one or n instructions
+c             continue until a breakpoint, a watched change, input or halt
+b [addr]      set a breakpoint, or list them
+w [addr]      watch a memory cell, or list the watches
+d addr        delete the breakpoint and watch at addr
+x addr [n]    dump n memory cells
+l [addr] [n]  disassemble n instructions from addr, default ip
+r             show ip and the relative base
+i n...        queue numbers as input
+a text        queue an ascii line as input
+set addr val  write to memory
+save file     write a snapshot of the whole machine
+load file     continue from a snapshot
+q             quit";
+
+#[derive(Debug)]
+enum Stop {
+    Steps,
+    Breakpoint,
+    Watch(usize, i64, i64),
+    NeedInput,
+    Halted,
+}
+
+struct Debugger {
+    computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    // address and the value last seen there
+    watches: Vec<(usize, i64)>,
+    executed: usize,
+}
+
+fn addr_name(addr: usize) -> String {
+    asm::addr_nickname(addr as i64).unwrap_or_else(|| format!("[{}]", addr))
+}
+
+fn parse_addr(word: &str) -> Option<usize> {
+    word.parse().ok().or_else(|| asm::nickname_addr(word).map(|addr| addr as usize))
+}
+
+impl Debugger {
+    fn new(computer: Computer) -> Self {
+        Debugger {
+            computer,
+            breakpoints: BTreeSet::new(),
+            watches: Vec::new(),
+            executed: 0,
+        }
+    }
+
+    // the first instruction always runs so that continuing from a breakpoint works
+    fn run(&mut self, limit: Option<usize>) -> Stop {
+        let mut count = 0;
+        loop {
+            match self.computer.step() {
+                Step::Continue | Step::Input => (),
+                Step::Output(value) => self.computer.outputs.push_back(value),
+                Step::NeedInput => return Stop::NeedInput,
+                Step::Halted => return Stop::Halted,
+            }
+            self.executed += 1;
+            count += 1;
+
+            for watch in self.watches.iter_mut() {
+                let value = self.computer.read(watch.0);
+                if value != watch.1 {
+                    let old = watch.1;
+                    watch.1 = value;
+                    return Stop::Watch(watch.0, old, value);
+                }
+            }
+            if limit == Some(count) {
+                return Stop::Steps;
+            }
+            if self.breakpoints.contains(&self.computer.ip()) {
+                return Stop::Breakpoint;
+            }
+        }
+    }
+
+    // padded so that the operands of the last instructions can be read too
+    fn memory_window(&self, end: usize) -> Vec<i64> {
+        let mut mem = self.computer.memory().to_vec();
+        if mem.len() < end {
+            mem.resize(end, 0);
+        }
+        mem
+    }
+
+    // one rawasm-like line, plus the current operand values when at ip
+    fn instruction_line(&self, mem: &[i64], ip: usize) -> (String, Option<usize>) {
+        let (next_ip, instruction, _) = match asm::disassemble(mem, ip) {
+            Some(decoded) => decoded,
+            None => return (format!("#{:05}: {:>4?} (data)", ip, mem[ip]), None),
+        };
+        let raw_numbs = format!("{:>4?}", &mem[ip..next_ip]);
+        let mut line = format!("#{:05}: {:<25} {:<30}", ip, raw_numbs, format!("{}", instruction));
+
+        if ip == self.computer.ip() {
+            let (_, modes) = intcode::decode(mem[ip]).unwrap();
+            for (n, mode) in modes.iter().take(next_ip - ip - 1).enumerate() {
+                let raw = mem[ip + 1 + n];
+                let addr = match mode {
+                    Mode::Immediate => continue,
+                    Mode::Position => raw,
+                    Mode::Relative => raw + self.computer.base(),
+                };
+                if addr >= 0 {
+                    line += &format!(" {}={}", addr_name(addr as usize), self.computer.read(addr as usize));
+                }
+            }
+        }
+        (line, Some(next_ip))
+    }
+
+    fn list(&self, from: usize, count: usize) {
+        let mem = self.memory_window(from + 4 * count + 4);
+        let mut ip = from;
+        for _ in 0..count {
+            let marker = if ip == self.computer.ip() { ">" } else if self.breakpoints.contains(&ip) { "*" } else { " " };
+            let (line, next_ip) = self.instruction_line(&mem, ip);
+            println!("{}{}", marker, line);
+            ip = next_ip.unwrap_or(ip + 1);
+        }
+    }
+
+    fn show_outputs(&mut self) {
+        if self.computer.outputs.is_empty() {
+            return;
+        }
+        let outputs = self.computer.outputs.drain(..).collect::<Vec<_>>();
+        if outputs.iter().all(|&v| v > 0 && v < 128) {
+            print!("{}", outputs.iter().map(|&v| v as u8 as char).collect::<String>());
+            if outputs.last() != Some(&10) {
+                println!();
+            }
+        } else {
+            println!("out: {:?}", outputs);
+        }
+    }
+
+    fn show_watches(&self) {
+        for &(addr, _) in &self.watches {
+            println!("  {} = {}", addr_name(addr), self.computer.read(addr));
+        }
+    }
+
+    fn show_stop(&mut self, stop: Stop) {
+        self.show_outputs();
+        match stop {
+            Stop::Steps => (),
+            Stop::Breakpoint => println!("breakpoint at {}", self.computer.ip()),
+            Stop::Watch(addr, old, new) => println!("{} changed: {} -> {}", addr_name(addr), old, new),
+            Stop::NeedInput => println!("waiting for input"),
+            Stop::Halted => println!("halted after {} instructions", self.executed),
+        }
+        self.list(self.computer.ip(), 1);
+        self.show_watches();
+    }
+
+    // false to quit
+    fn command(&mut self, line: &str) -> bool {
+        let mut words = line.split_whitespace();
+        let cmd = match words.next() {
+            Some(cmd) => cmd,
+            None => return true,
+        };
+        let args = words.collect::<Vec<_>>();
+        let addr_arg = |n: usize| args.get(n).and_then(|w| parse_addr(w));
+        let num_arg = |n: usize, default: usize| args.get(n).and_then(|w| w.parse().ok()).unwrap_or(default);
+
+        match (cmd, args.len()) {
+            ("s", _) | ("step", _) => {
+                let stop = self.run(Some(num_arg(0, 1)));
+                self.show_stop(stop);
+            },
+            ("c", 0) | ("continue", 0) => {
+                let stop = self.run(None);
+                self.show_stop(stop);
+            },
+            ("b", 0) => {
+                for &addr in &self.breakpoints {
+                    self.list(addr, 1);
+                }
+            },
+            ("b", 1) => match addr_arg(0) {
+                Some(addr) => { self.breakpoints.insert(addr); },
+                None => println!("bad address"),
+            },
+            ("w", 0) => self.show_watches(),
+            ("w", 1) => match addr_arg(0) {
+                Some(addr) => self.watches.push((addr, self.computer.read(addr))),
+                None => println!("bad address"),
+            },
+            ("d", 1) => match addr_arg(0) {
+                Some(addr) => {
+                    self.breakpoints.remove(&addr);
+                    self.watches.retain(|&(a, _)| a != addr);
+                },
+                None => println!("bad address"),
+            },
+            ("x", 1) | ("x", 2) => match addr_arg(0) {
+                Some(addr) => {
+                    for a in addr..addr + num_arg(1, 1) {
+                        println!("  {} = {}", addr_name(a), self.computer.read(a));
+                    }
+                },
+                None => println!("bad address"),
+            },
+            ("l", _) => self.list(addr_arg(0).unwrap_or(self.computer.ip()), num_arg(1, 10)),
+            ("r", 0) => println!("ip {} base {} executed {} input queue {:?}",
+                                 self.computer.ip(), self.computer.base(), self.executed, self.computer.inputs),
+            ("i", _) => {
+                match args.iter().map(|w| w.parse::<i64>()).collect::<Result<Vec<_>, _>>() {
+                    Ok(values) => self.computer.inputs.extend(values),
+                    Err(_) => println!("bad number"),
+                }
+            },
+            ("a", _) => {
+                // keep the spacing as typed
+                let text = line.trim_start()[1..].trim_start();
+                self.computer.push_str(text);
+                self.computer.push_input(10);
+            },
+            ("set", 2) => match (addr_arg(0), args[1].parse()) {
+                (Some(addr), Ok(value)) => self.computer.write(addr, value),
+                _ => println!("bad address or value"),
+            },
+            ("save", 1) => match self.computer.save_snapshot(args[0]) {
+                Ok(()) => println!("saved at ip {}", self.computer.ip()),
+                Err(e) => println!("{}", e),
+            },
+            ("load", 1) => match Computer::load_snapshot(args[0]) {
+                Ok(computer) => {
+                    self.computer = computer;
+                    self.watches.iter_mut().for_each(|w| w.1 = self.computer.read(w.0));
+                    self.list(self.computer.ip(), 1);
+                },
+                Err(e) => println!("{}", e),
+            },
+            ("q", 0) | ("quit", 0) => return false,
+            ("h", _) | ("help", _) => println!("{}", HELP),
+            _ => println!("unknown command, try help"),
+        }
+        true
+    }
+}
+
+fn main() {
+    let stdin = io::stdin();
+    let mut commands = stdin.lock().lines();
+    let mut program_path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-s" => { asm::load_symbols(&args.next().expect("symbol file")); },
+            _ => program_path = Some(arg),
+        }
+    }
+    let text = match program_path {
+        Some(path) => fs::read_to_string(path).unwrap(),
+        None => commands.next().unwrap().unwrap(),
+    };
+
+    let computer = if intcode::is_snapshot(&text) {
+        Computer::from_snapshot(&text).unwrap()
+    } else {
+        Computer::new(&intcode::parse_program(&text))
+    };
+    let mut debugger = Debugger::new(computer);
+    debugger.list(debugger.computer.ip(), 1);
+    loop {
+        print!("(ic) ");
+        io::stdout().flush().unwrap();
+        match commands.next() {
+            Some(line) => {
+                let line = line.unwrap();
+                if !debugger.command(&line) {
+                    break;
+                }
+            },
+            None => {
+                println!();
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,686 @@
+// Structured pseudo-Rust out of the basic blocks, like 15.decompiled.rs but without the manual
+// labour. The bigger programs have functions: a call stores the return address in [0 + base] and
+// the arguments after it, then jumps; a return jumps back through [0 + base]. Within a function,
+// a back edge to a block that dominates its source makes a loop, and a block that several others
+// jump forward to follows a labeled block they break out of, the way 18/transpile lays them out.
+use crate::asm::*;
+use crate::asm::Instruction::*;
+use crate::{asm_line, build_bbs, AsmRow, BlockEdges, Blocks, ProgAddr};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+// where control goes after a block, or what ends it there
+#[derive(Clone)]
+enum Target {
+    Block(ProgAddr),
+    End(String),
+}
+
+enum Exit {
+    Jump(Target),
+    // callee and where it returns to
+    Call(ProgAddr, Target),
+    // conditions for taking the jump and for not taking it, then the jump and the fallthrough
+    Branch((String, String), Target, Target),
+}
+
+fn immediate(param: &SourceParam) -> Option<i64> {
+    match *param {
+        SourceParam::Immediate(val) => Some(val),
+        _ => None,
+    }
+}
+
+fn position(addr: i64) -> String {
+    addr_nickname(addr).unwrap_or_else(|| format!("mem[{}]", addr))
+}
+
+fn source(param: &SourceParam) -> String {
+    match *param {
+        SourceParam::Immediate(val) => val.to_string(),
+        SourceParam::Position(addr) => position(addr),
+        SourceParam::Relative(offset) => format!("frame[{}]", offset),
+    }
+}
+
+fn dest(param: &DestParam) -> String {
+    match *param {
+        DestParam::Position(addr) => position(addr),
+        DestParam::Relative(offset) => format!("frame[{}]", offset),
+    }
+}
+
+fn same_dest(a: &DestParam, b: &DestParam) -> bool {
+    match (a, b) {
+        (DestParam::Position(a), DestParam::Position(b)) => a == b,
+        (DestParam::Relative(a), DestParam::Relative(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn same_place(src: &SourceParam, dest: &DestParam) -> bool {
+    match (src, dest) {
+        (SourceParam::Position(a), DestParam::Position(b)) => a == b,
+        (SourceParam::Relative(a), DestParam::Relative(b)) => a == b,
+        _ => false,
+    }
+}
+
+fn sum(a: &SourceParam, b: &SourceParam) -> String {
+    match (immediate(a), immediate(b)) {
+        (Some(x), Some(y)) => (x + y).to_string(),
+        (Some(0), None) => source(b),
+        (None, Some(0)) => source(a),
+        (Some(x), None) if x < 0 => format!("{} - {}", source(b), -x),
+        (None, Some(y)) if y < 0 => format!("{} - {}", source(a), -y),
+        _ => format!("{} + {}", source(a), source(b)),
+    }
+}
+
+fn product(a: &SourceParam, b: &SourceParam) -> String {
+    match (immediate(a), immediate(b)) {
+        (Some(x), Some(y)) => (x * y).to_string(),
+        (Some(0), None) | (None, Some(0)) => "0".to_string(),
+        (Some(1), None) => source(b),
+        (None, Some(1)) => source(a),
+        (Some(-1), None) => format!("-{}", source(b)),
+        (None, Some(-1)) => format!("-{}", source(a)),
+        _ => format!("{} * {}", source(a), source(b)),
+    }
+}
+
+// the jumps are handled by the control flow
+fn statement(instruction: &Instruction) -> Option<String> {
+    match instruction {
+        Add(op) => Some(format!("{} = {};", dest(&op.dest), sum(&op.a, &op.b))),
+        Mul(op) => Some(format!("{} = {};", dest(&op.dest), product(&op.a, &op.b))),
+        In(op) => Some(format!("input({});", dest(&op.dest))),
+        Out(op) => Some(format!("output({});", source(&op.val))),
+        Lt(op) => Some(format!("{} = {} < {};", dest(&op.dest), source(&op.a), source(&op.b))),
+        Eq(op) => Some(format!("{} = {} == {};", dest(&op.dest), source(&op.a), source(&op.b))),
+        Base(op) => Some(match immediate(&op.val) {
+            Some(val) if val < 0 => format!("base -= {};", -val),
+            _ => format!("base += {};", source(&op.val)),
+        }),
+        Jnz(_) | Jz(_) | Stop => None,
+    }
+}
+
+// (tested value, jump target, jumps if nonzero)
+fn jump_operands(instruction: &Instruction) -> Option<(&SourceParam, &SourceParam, bool)> {
+    match instruction {
+        Jnz(op) => Some((&op.src, &op.addr, true)),
+        Jz(op) => Some((&op.src, &op.addr, false)),
+        _ => None,
+    }
+}
+
+// what a constant store like "add 58 0 => [0 + base]" writes where
+fn constant_store(instruction: &Instruction) -> Option<(&DestParam, i64)> {
+    match instruction {
+        Add(op) => Some((&op.dest, immediate(&op.a)? + immediate(&op.b)?)),
+        Mul(op) => Some((&op.dest, immediate(&op.a)? * immediate(&op.b)?)),
+        _ => None,
+    }
+}
+
+fn is_return_store(instruction: &Instruction, ret: ProgAddr) -> bool {
+    matches!(constant_store(instruction), Some((DestParam::Relative(0), val)) if val == ret.value() as i64)
+}
+
+// the comparison just before a jump that computed the tested value, unless it overwrote its own
+// operand: the operands, where it stored the result, and whether it's an equality
+fn inlined_compare(rows: &[AsmRow]) -> Option<(&SourceParam, &SourceParam, &DestParam, bool)> {
+    let (src, _, _) = jump_operands(&rows.last().unwrap().instruction)?;
+    let (a, b, dest, is_eq) = match &rows.get(rows.len().checked_sub(2)?)?.instruction {
+        Eq(op) => (&op.a, &op.b, &op.dest, true),
+        Lt(op) => (&op.a, &op.b, &op.dest, false),
+        _ => return None,
+    };
+    if same_place(src, dest) && !same_place(a, dest) && !same_place(b, dest) {
+        Some((a, b, dest, is_eq))
+    } else {
+        None
+    }
+}
+
+// the condition for taking the jump and for not, with the comparison inlined if there is one
+fn condition(rows: &[AsmRow]) -> (String, String) {
+    let (src, _, if_nonzero) = jump_operands(&rows.last().unwrap().instruction).unwrap();
+    let (nonzero, zero) = match inlined_compare(rows) {
+        Some((a, b, _, true)) => (format!("{} == {}", source(a), source(b)), format!("{} != {}", source(a), source(b))),
+        Some((a, b, _, false)) => (format!("{} < {}", source(a), source(b)), format!("{} >= {}", source(a), source(b))),
+        None => (format!("{} != 0", source(src)), format!("{} == 0", source(src))),
+    };
+    if if_nonzero { (nonzero, zero) } else { (zero, nonzero) }
+}
+
+fn function_name(addr: ProgAddr) -> String {
+    match addr.value() {
+        0 => "main".to_string(),
+        _ => addr_nickname(addr.value() as i64).unwrap_or_else(|| format!("f{}", addr.value())),
+    }
+}
+
+// Cooper, Harvey & Kennedy: "A Simple, Fast Dominance Algorithm". None for the entry and for
+// what can't be reached from it; also the reverse postorder position of each node
+fn immediate_dominators(succs: &[Vec<usize>], entry: usize) -> (Vec<Option<usize>>, Vec<usize>) {
+    let n = succs.len();
+    let mut preds = vec![Vec::new(); n];
+    for (from, tos) in succs.iter().enumerate() {
+        for &to in tos {
+            preds[to].push(from);
+        }
+    }
+
+    let mut order = Vec::new();
+    let mut seen = vec![false; n];
+    seen[entry] = true;
+    let mut stack = vec![(entry, 0)];
+    while let Some((node, next_child)) = stack.pop() {
+        if let Some(&child) = succs[node].get(next_child) {
+            stack.push((node, next_child + 1));
+            if !seen[child] {
+                seen[child] = true;
+                stack.push((child, 0));
+            }
+        } else {
+            order.push(node);
+        }
+    }
+    order.reverse();
+    let mut rank = vec![usize::MAX; n];
+    for (r, &node) in order.iter().enumerate() {
+        rank[node] = r;
+    }
+
+    let mut idom = vec![None; n];
+    idom[entry] = Some(entry);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &node in &order[1..] {
+            let mut new_idom = None;
+            for &p in preds[node].iter().filter(|&&p| idom[p].is_some()) {
+                new_idom = Some(match new_idom {
+                    None => p,
+                    Some(mut other) => {
+                        let mut p = p;
+                        while p != other {
+                            while rank[p] > rank[other] { p = idom[p].unwrap(); }
+                            while rank[other] > rank[p] { other = idom[other].unwrap(); }
+                        }
+                        p
+                    }
+                });
+            }
+            if new_idom != idom[node] {
+                idom[node] = new_idom;
+                changed = true;
+            }
+        }
+    }
+    idom[entry] = None;
+    (idom, rank)
+}
+
+// the blocks reachable from an entry without following calls, numbered
+struct Function {
+    entry: ProgAddr,
+    nodes: Vec<ProgAddr>,
+    index: HashMap<ProgAddr, usize>,
+    succs: Vec<Vec<usize>>,
+    // what a block calls, and whether control can leave the function there other than by halting
+    callees: Vec<Option<ProgAddr>>,
+    open: Vec<bool>,
+    idom: Vec<Option<usize>>,
+    rank: Vec<usize>,
+    // targets of back edges, and blocks more than one forward edge leads to
+    headers: HashSet<usize>,
+    merges: HashSet<usize>,
+    // the merges each block immediately dominates, in reverse postorder
+    merges_under: Vec<Vec<usize>>,
+}
+
+impl Function {
+    fn dominates(&self, a: usize, b: usize) -> bool {
+        let mut node = Some(b);
+        while let Some(x) = node {
+            if x == a {
+                return true;
+            }
+            node = self.idom[x];
+        }
+        false
+    }
+}
+
+struct Decompiler<'a> {
+    program: &'a [i64],
+    asm: &'a [AsmRow],
+    refs: &'a HashMap<ProgAddr, Vec<ProgAddr>>,
+    bbs: Blocks,
+    bb_edges: BlockEdges,
+    // the positions each function reads, counting what it calls
+    reads: HashMap<ProgAddr, HashSet<i64>>,
+}
+
+impl<'a> Decompiler<'a> {
+    fn rows(&self, addr: ProgAddr) -> &'a [AsmRow] {
+        let (first, last) = self.bbs[&addr].source_rows.unwrap();
+        &self.asm[first..=last]
+    }
+
+    fn target(&self, edge: Option<ProgAddr>, next_ip: ProgAddr) -> Target {
+        match edge {
+            Some(addr) if !self.bbs[&addr].is_sentinel() => Target::Block(addr),
+            Some(addr) if addr.value() >= self.program.len() => Target::End("// runs off the end".to_string()),
+            Some(addr) => Target::End(format!("// runs into data at {}", addr.value())),
+            None => Target::End(format!("// runs into data at {}", next_ip.value())),
+        }
+    }
+
+    fn exit(&self, addr: ProgAddr) -> Exit {
+        let rows = self.rows(addr);
+        let last = rows.last().unwrap();
+        let (fall, jump) = self.bb_edges[&addr];
+        let fall = self.target(fall, last.next_ip);
+
+        let (src, dest, if_nonzero) = match jump_operands(&last.instruction) {
+            Some(operands) => operands,
+            None if matches!(last.instruction, Stop) => return Exit::Jump(Target::End("halt;".to_string())),
+            None => return Exit::Jump(fall),
+        };
+        let taken = match (jump, dest) {
+            (Some(addr), _) => Target::Block(addr),
+            (None, SourceParam::Immediate(val)) => Target::End(format!("goto {}; // into data", val)),
+            (None, SourceParam::Relative(0)) => Target::End("return;".to_string()),
+            (None, dest) => Target::End(format!("goto *{};", source(dest))),
+        };
+        match immediate(src).map(|val| (val != 0) == if_nonzero) {
+            Some(true) => {
+                let ret = last.next_ip;
+                match taken {
+                    Target::Block(callee) if rows.iter().any(|row| is_return_store(&row.instruction, ret)) => {
+                        let ret = if self.bbs.contains_key(&ret) { Some(ret) } else { None };
+                        Exit::Call(callee, self.target(ret, last.next_ip))
+                    },
+                    taken => Exit::Jump(taken),
+                }
+            },
+            Some(false) => Exit::Jump(fall),
+            None => Exit::Branch(condition(rows), taken, fall),
+        }
+    }
+
+    fn function(&self, entry: ProgAddr) -> Function {
+        let mut nodes = vec![entry];
+        let mut index = HashMap::new();
+        index.insert(entry, 0);
+        let mut succs: Vec<Vec<usize>> = Vec::new();
+        let mut callees = Vec::new();
+        let mut open = Vec::new();
+        let mut i = 0;
+        while i < nodes.len() {
+            let exit = self.exit(nodes[i]);
+            let halts = matches!(self.rows(nodes[i]).last().unwrap().instruction, Stop);
+            let (targets, callee) = match exit {
+                Exit::Jump(t) => (vec![t], None),
+                Exit::Call(callee, t) => (vec![t], Some(callee)),
+                Exit::Branch(_, taken, fall) => (vec![taken, fall], None),
+            };
+            let mut node_succs = Vec::new();
+            let mut has_end = false;
+            for t in targets {
+                match t {
+                    Target::Block(addr) => {
+                        let next = *index.entry(addr).or_insert_with(|| {
+                            nodes.push(addr);
+                            nodes.len() - 1
+                        });
+                        node_succs.push(next);
+                    },
+                    Target::End(_) => has_end = true,
+                }
+            }
+            succs.push(node_succs);
+            callees.push(callee);
+            open.push(has_end && !halts);
+            i += 1;
+        }
+
+        let n = nodes.len();
+        let (idom, rank) = immediate_dominators(&succs, 0);
+        let mut f = Function {
+            entry, nodes, index, succs, callees, open, idom, rank,
+            headers: HashSet::new(),
+            merges: HashSet::new(),
+            merges_under: vec![Vec::new(); n],
+        };
+        let mut forward_preds = vec![0; n];
+        for from in 0..n {
+            for &to in &f.succs[from] {
+                if f.rank[to] > f.rank[from] {
+                    forward_preds[to] += 1;
+                } else if f.dominates(to, from) {
+                    f.headers.insert(to);
+                }
+            }
+        }
+        for node in 0..n {
+            if forward_preds[node] > 1 {
+                f.merges.insert(node);
+                f.merges_under[f.idom[node].unwrap()].push(node);
+            }
+        }
+        for merges in f.merges_under.iter_mut() {
+            merges.sort_by_key(|&m| f.rank[m]);
+        }
+        f
+    }
+}
+
+enum Line {
+    Label(ProgAddr),
+    Loop(usize, ProgAddr),
+    // a block a merge follows, and its end
+    Block(usize, ProgAddr),
+    EndBlock(usize, ProgAddr),
+    Text(usize, String),
+    Goto(usize, ProgAddr),
+    // back to a loop's header, with its label if it's not the innermost one
+    Continue(usize, Option<ProgAddr>),
+    // on to the merge after a block
+    Break(usize, ProgAddr),
+}
+
+// Like 18/transpile's: a loop at each back edge target, and a labeled block in front of each merge,
+// nested along the dominator tree so that every forward jump to a merge is a break out of its
+// block. Whatever else a block goes on to is emitted right there, as nothing else jumps to it. A
+// back edge into the middle of a loop, which only a goto can do, is the one thing left over.
+struct Emitter<'a, 'b> {
+    dc: &'b Decompiler<'a>,
+    f: &'b Function,
+    lines: Vec<Line>,
+    indent: usize,
+    loops: Vec<usize>,
+    // where falling off the end of what's being emitted goes
+    falls_to: Option<usize>,
+}
+
+impl<'a, 'b> Emitter<'a, 'b> {
+    fn line(&mut self, text: String) {
+        self.lines.push(Line::Text(self.indent, text));
+    }
+
+    fn tree(&mut self, node: usize) {
+        let merges = self.f.merges_under[node].clone();
+        if self.f.headers.contains(&node) {
+            self.lines.push(Line::Loop(self.indent, self.f.nodes[node]));
+            self.indent += 1;
+            self.loops.push(node);
+            let falls_to = self.falls_to.replace(node);
+            self.within(node, &merges);
+            self.falls_to = falls_to;
+            self.loops.pop();
+            self.indent -= 1;
+            self.line("}".to_string());
+        } else {
+            self.within(node, &merges);
+        }
+    }
+
+    // the last merge goes after a block holding the rest, which can break out to it
+    fn within(&mut self, node: usize, merges: &[usize]) {
+        match merges.split_last() {
+            None => self.block(node),
+            Some((&last, rest)) => {
+                let addr = self.f.nodes[last];
+                self.lines.push(Line::Block(self.indent, addr));
+                self.indent += 1;
+                let falls_to = self.falls_to.replace(last);
+                self.within(node, rest);
+                self.falls_to = falls_to;
+                self.indent -= 1;
+                self.lines.push(Line::EndBlock(self.indent, addr));
+                self.tree(last);
+            },
+        }
+    }
+
+    fn block(&mut self, node: usize) {
+        let addr = self.f.nodes[node];
+        self.lines.push(Line::Label(addr));
+        let rows = self.dc.rows(addr);
+        for row in rows {
+            self.line(format!("//{}", asm_line(self.dc.program, row, self.dc.refs)));
+        }
+        let exit = self.dc.exit(addr);
+        let ret = rows.last().unwrap().next_ip;
+        // a comparison inlined into the branch needn't be stored as well if nothing reads it again
+        let dead_compare = matches!(exit, Exit::Branch(..)) && inlined_compare(rows)
+            .map_or(false, |(_, _, dest, _)| !self.live_after(node, dest));
+        for (i, row) in rows.iter().enumerate() {
+            let is_call_setup = matches!(exit, Exit::Call(_, _)) && is_return_store(&row.instruction, ret);
+            let is_dead = dead_compare && i + 2 == rows.len();
+            if let Some(text) = statement(&row.instruction).filter(|_| !is_call_setup && !is_dead) {
+                self.line(text);
+            }
+        }
+
+        match exit {
+            Exit::Jump(target) => self.branch(node, &target, true),
+            Exit::Call(callee, ret) => {
+                self.line(format!("{}();", function_name(callee)));
+                self.branch(node, &ret, true)
+            },
+            Exit::Branch((cond, not_cond), taken, fall) => {
+                let falls = |t: &Target| matches!(t, Target::Block(addr) if Some(self.f.index[addr]) == self.falls_to);
+                if falls(&taken) && falls(&fall) {
+                } else if falls(&taken) {
+                    self.if_only(not_cond, node, &fall);
+                } else if falls(&fall) {
+                    self.if_only(cond, node, &taken);
+                } else if self.leaves(node, &taken) {
+                    // one way leaves, so the other can go on without an else
+                    self.if_only(cond, node, &taken);
+                    self.branch(node, &fall, true);
+                } else if self.leaves(node, &fall) {
+                    self.if_only(not_cond, node, &fall);
+                    self.branch(node, &taken, true);
+                } else {
+                    self.line(format!("if {} {{", cond));
+                    self.arm(node, &taken);
+                    self.line("} else {".to_string());
+                    self.arm(node, &fall);
+                    self.line("}".to_string());
+                }
+            },
+        }
+    }
+
+    // whether what the block leaves at a place might be read later on: anything might be once
+    // control leaves the function, and a frame slot is another one after the base changes
+    fn live_after(&self, node: usize, place: &DestParam) -> bool {
+        let read_on_exit = |n: usize| self.f.open[n] || self.f.callees[n].map_or(false, |callee| match *place {
+            DestParam::Position(addr) => self.dc.reads[&callee].contains(&addr),
+            DestParam::Relative(_) => true,
+        });
+        if read_on_exit(node) {
+            return true;
+        }
+        let mut seen = HashSet::new();
+        let mut stack = self.f.succs[node].clone();
+        'paths: while let Some(n) = stack.pop() {
+            if !seen.insert(n) {
+                continue;
+            }
+            for row in self.dc.rows(self.f.nodes[n]) {
+                let (sources, dest) = row.instruction.operands();
+                let rebased = matches!(row.instruction, Base(_)) && matches!(place, DestParam::Relative(_));
+                if rebased || sources.iter().any(|src| same_place(src, place)) {
+                    return true;
+                }
+                if dest.map_or(false, |dest| same_dest(dest, place)) {
+                    continue 'paths;
+                }
+            }
+            if read_on_exit(n) {
+                return true;
+            }
+            stack.extend(&self.f.succs[n]);
+        }
+        false
+    }
+
+    // whether going to a target is a jump of some kind rather than carrying on with it here
+    fn leaves(&self, from: usize, target: &Target) -> bool {
+        match target {
+            Target::Block(addr) => {
+                let to = self.f.index[addr];
+                self.f.rank[to] <= self.f.rank[from] || self.f.merges.contains(&to)
+            },
+            Target::End(_) => true,
+        }
+    }
+
+    // at the end of what's being emitted, unless inside an if that something follows
+    fn branch(&mut self, from: usize, target: &Target, at_end: bool) {
+        let addr = match target {
+            Target::Block(addr) => *addr,
+            Target::End(text) => return self.line(text.clone()),
+        };
+        let to = self.f.index[&addr];
+        if at_end && Some(to) == self.falls_to {
+            return;
+        }
+        if self.f.rank[to] <= self.f.rank[from] {
+            if self.f.dominates(to, from) {
+                let label = if self.loops.last() == Some(&to) { None } else { Some(addr) };
+                self.lines.push(Line::Continue(self.indent, label));
+            } else {
+                self.lines.push(Line::Goto(self.indent, addr));
+            }
+        } else if self.f.merges.contains(&to) {
+            self.lines.push(Line::Break(self.indent, addr));
+        } else {
+            self.tree(to);
+        }
+    }
+
+    fn if_only(&mut self, cond: String, from: usize, target: &Target) {
+        self.line(format!("if {} {{", cond));
+        self.indent += 1;
+        let at_end = !self.leaves(from, target);
+        self.branch(from, target, at_end);
+        self.indent -= 1;
+        self.line("}".to_string());
+    }
+
+    fn arm(&mut self, from: usize, target: &Target) {
+        self.indent += 1;
+        self.branch(from, target, true);
+        self.indent -= 1;
+    }
+
+    fn print(&self) {
+        let goto_targets = self.lines.iter().filter_map(|line| match line {
+            Line::Goto(_, addr) => Some(*addr),
+            _ => None,
+        }).collect::<HashSet<_>>();
+        let labels = self.lines.iter().filter_map(|line| match line {
+            Line::Continue(_, label) => *label,
+            Line::Break(_, addr) => Some(*addr),
+            _ => None,
+        }).collect::<HashSet<_>>();
+        // a block nothing breaks out of is just what's in it
+        let mut dedent = 0;
+        let indent = |n: usize, dedent: usize| "    ".repeat(n - dedent);
+
+        println!("fn {}() {{", function_name(self.f.entry));
+        for line in &self.lines {
+            match line {
+                Line::Label(addr) if goto_targets.contains(addr) => println!("l_{}:", addr.value()),
+                Line::Label(_) => (),
+                Line::Loop(n, header) if labels.contains(header) => println!("{}'l_{}: loop {{", indent(*n, dedent), header.value()),
+                Line::Loop(n, _) => println!("{}loop {{", indent(*n, dedent)),
+                Line::Block(n, addr) if labels.contains(addr) => println!("{}'b_{}: {{", indent(*n, dedent), addr.value()),
+                Line::EndBlock(n, addr) if labels.contains(addr) => println!("{}}}", indent(*n, dedent)),
+                Line::Block(_, _) => dedent += 1,
+                Line::EndBlock(_, _) => dedent -= 1,
+                Line::Text(n, text) => println!("{}{}", indent(*n, dedent), text),
+                Line::Goto(n, addr) => println!("{}goto l_{};", indent(*n, dedent), addr.value()),
+                Line::Continue(n, Some(label)) => println!("{}continue 'l_{};", indent(*n, dedent), label.value()),
+                Line::Continue(n, None) => println!("{}continue;", indent(*n, dedent)),
+                Line::Break(n, addr) => println!("{}break 'b_{};", indent(*n, dedent), addr.value()),
+            }
+        }
+        println!("}}");
+    }
+}
+
+fn boot(program: &[i64], symbols: &[Symbol]) {
+    if symbols.is_empty() {
+        return;
+    }
+    println!("boot:");
+    for sym in symbols {
+        let value = program.get(sym.addr as usize).copied().unwrap_or(0);
+        let comment = sym.comment.as_ref().map(|c| format!(" // {}", c)).unwrap_or_default();
+        println!("{} = {};{}", sym.name, value, comment);
+    }
+    println!();
+}
+
+pub fn decompile(program: &[i64], asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgAddr>>, symbols: &[Symbol]) {
+    let (bbs, bb_edges) = build_bbs(asm, refs);
+    let mut dc = Decompiler { program, asm, refs, bbs, bb_edges, reads: HashMap::new() };
+
+    let mut entries = BTreeSet::new();
+    entries.insert(ProgAddr(0));
+    for (&addr, bb) in &dc.bbs {
+        if bb.is_sentinel() {
+            continue;
+        }
+        if let Exit::Call(callee, _) = dc.exit(addr) {
+            entries.insert(callee);
+        }
+    }
+
+    let functions = entries.iter().map(|&entry| dc.function(entry)).collect::<Vec<_>>();
+    for f in &functions {
+        let reads = f.nodes.iter().flat_map(|&addr| dc.rows(addr)).flat_map(|row| row.instruction.operands().0)
+            .filter_map(|src| match *src {
+                SourceParam::Position(addr) => Some(addr),
+                _ => None,
+            }).collect();
+        dc.reads.insert(f.entry, reads);
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for f in &functions {
+            for &callee in f.callees.iter().flatten() {
+                let more = dc.reads[&callee].difference(&dc.reads[&f.entry]).copied().collect::<Vec<_>>();
+                changed |= !more.is_empty();
+                dc.reads.get_mut(&f.entry).unwrap().extend(more);
+            }
+        }
+    }
+
+    boot(program, symbols);
+    for (i, f) in functions.iter().enumerate() {
+        let mut em = Emitter {
+            dc: &dc,
+            f,
+            lines: Vec::new(),
+            indent: 1,
+            loops: Vec::new(),
+            falls_to: None,
+        };
+        em.tree(0);
+        if i > 0 {
+            println!();
+        }
+        em.print();
+    }
+}
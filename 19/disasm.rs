@@ -1,322 +1,19 @@
-use std::io::{self, BufRead};
-use std::collections::HashMap;
-use std::fmt;
-
-#[derive(Debug)]
-enum SourceParam {
-    Immediate(i64),
-    Position(i64),
-    Relative(i64),
-}
-
-impl fmt::Display for SourceParam {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            Self::Immediate(val) => write!(f, "{:>6}", val),
-            //Self::Position(addr) => write!(f, "[{:>4}]", addr),
-            Self::Position(addr) => addr_nickname(addr).map(|name| write!(f, "{:>6}", name)).unwrap_or_else(|| write!(f, "[{:>4}]", addr)),
-            Self::Relative(addr) => write!(f, "[{:>4} + base]", addr),
-        }
-    }
-}
-
-#[derive(Debug)]
-enum DestParam {
-    Position(i64),
-    Relative(i64),
-}
-
-
-impl fmt::Display for DestParam {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match *self {
-            //Self::Position(addr) => write!(f, "[{:>4}]", addr),
-            Self::Position(addr) => addr_nickname(addr).map(|name| write!(f, "{:>6}", name)).unwrap_or_else(|| write!(f, "[{:>4}]", addr)),
-            Self::Relative(addr) => write!(f, "[{:>4} + base]", addr),
-        }
-    }
-}
+mod intcode;
+mod asm;
+mod decompile;
+mod trace;
+use asm::*;
+use asm::Instruction::*;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::env;
+use std::fs;
+use trace::Trace;
 
 enum Param {
     Source(SourceParam),
     Dest(DestParam),
 }
 
-#[derive(Debug)]
-struct OpAdd {
-    a: SourceParam,
-    b: SourceParam,
-    dest: DestParam,
-}
-
-impl fmt::Display for OpAdd {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "add {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
-    }
-}
-
-#[derive(Debug)]
-struct OpMul {
-    a: SourceParam,
-    b: SourceParam,
-    dest: DestParam,
-}
-
-impl fmt::Display for OpMul {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "mul {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
-    }
-}
-
-#[derive(Debug)]
-struct OpIn {
-    dest: DestParam,
-}
-
-impl fmt::Display for OpIn {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "in  {:>6}", self.dest)
-    }
-}
-
-#[derive(Debug)]
-struct OpOut {
-    val: SourceParam,
-}
-
-impl fmt::Display for OpOut {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "out {:>6}", self.val)
-    }
-}
-
-// jump if argument is not zero
-#[derive(Debug)]
-struct OpJnz {
-    src: SourceParam,
-    addr: SourceParam,
-}
-
-impl fmt::Display for OpJnz {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "jnz {:>6} {:>6}", self.src, self.addr)
-    }
-}
-
-// jump if argument is zero
-#[derive(Debug)]
-struct OpJz {
-    src: SourceParam,
-    addr: SourceParam,
-}
-
-impl fmt::Display for OpJz {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "jz  {:>6} {:>6}", self.src, self.addr)
-    }
-}
-
-#[derive(Debug)]
-struct OpLess {
-    a: SourceParam,
-    b: SourceParam,
-    dest: DestParam,
-}
-
-impl fmt::Display for OpLess {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "lt  {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
-    }
-}
-
-#[derive(Debug)]
-struct OpEqual {
-    a: SourceParam,
-    b: SourceParam,
-    dest: DestParam,
-}
-
-impl fmt::Display for OpEqual {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "eq  {:>6} {:>6} => {:>6}", self.a, self.b, self.dest)
-    }
-}
-
-#[derive(Debug)]
-struct OpBase {
-    val: SourceParam,
-}
-
-impl fmt::Display for OpBase {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "base {:>6}", self.val)
-    }
-}
-
-#[derive(Debug)]
-enum Instruction {
-    Add(OpAdd),
-    Mul(OpMul),
-    In(OpIn),
-    Out(OpOut),
-    Jnz(OpJnz),
-    Jz(OpJz),
-    Lt(OpLess),
-    Eq(OpEqual),
-    Base(OpBase),
-    Stop,
-}
-use Instruction::*;
-
-impl fmt::Display for Instruction {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Add(op)  => write!(f, "{}", op),
-            Mul(op)  => write!(f, "{}", op),
-            In(op)   => write!(f, "{}", op),
-            Out(op)  => write!(f, "{}", op),
-            Jnz(op)  => write!(f, "{}", op),
-            Jz(op)   => write!(f, "{}", op),
-            Lt(op)   => write!(f, "{}", op),
-            Eq(op)   => write!(f, "{}", op),
-            Base(op) => write!(f, "{}", op),
-            Stop     => write!(f, "stop"),
-        }
-    }
-}
-
-fn step(program: &[i64], ip: usize) -> Option<(usize, Instruction, Option<usize>)> {
-    let opcode = program[ip] % 100;
-    if opcode == 99 {
-        return Some((ip + 1, Instruction::Stop, None));
-    }
-
-    let mode0 = program[ip] / 100 % 10;
-    let mode1 = program[ip] / 1000 % 10;
-    let mode2 = program[ip] / 10000 % 10;
-    assert!(mode0 <= 2);
-    assert!(mode1 <= 2);
-    assert!(mode2 <= 2);
-
-    let immflags = [mode0 == 1, mode1 == 1, mode2 == 1];
-    let relflags = [mode0 == 2, mode1 == 2, mode2 == 2];
-
-    let simm = |x| SourceParam::Immediate(program[ip + 1 + x]);
-    let spos = |x| SourceParam::Position(program[ip + 1 + x]);
-    let srel = |x| SourceParam::Relative(program[ip + 1 + x]);
-
-    let dpos = |x| DestParam::Position(program[ip + 1 + x]);
-    let drel = |x| DestParam::Relative(program[ip + 1 + x]);
-
-    let input = |x: usize| {
-        assert!(!(immflags[x] && relflags[x]));
-        if immflags[x] { simm(x) }
-        else if relflags[x] { srel(x) }
-        else { spos(x) }
-    };
-
-    let output = |x: usize| {
-        assert!(!immflags[x]);
-        if relflags[x] { drel(x) }
-        else { dpos(x) }
-    };
-
-    let val1_num = ||
-        if immflags[1] {
-            Some(program[ip + 2] as usize)
-        } else if relflags[1] {
-            println!("warning! indir rel jump"); None
-        } else {
-            println!("warning! indir jump"); None
-        };
-
-    match opcode {
-        1 => { Some((
-                    ip + 4,
-                    Instruction::Add(OpAdd {
-                        a: input(0),
-                        b: input(1),
-                        dest: output(2),
-                    }),
-                    None))
-        },
-
-        2 => { Some((
-                    ip + 4,
-                    Instruction::Mul(OpMul {
-                        a: input(0),
-                        b: input(1),
-                        dest: output(2),
-                    }),
-                    None))
-        },
-
-        3 => { Some((
-                    ip + 2,
-                    Instruction::In(OpIn {
-                        dest: output(0),
-                    }),
-                    None))
-        },
-
-        4 => { Some((
-                    ip + 2,
-                    Instruction::Out(OpOut {
-                        val: input(0),
-                    }),
-                    None))
-        },
-
-        5 => { Some((
-                    ip + 3,
-                    Instruction::Jnz(OpJnz {
-                        src: input(0),
-                        addr: input(1),
-                    }),
-                    val1_num()))
-        },
-
-        6 => { Some((
-                    ip + 3,
-                    Instruction::Jz(OpJz {
-                        src: input(0),
-                        addr: input(1),
-                    }),
-                    val1_num()))
-        },
-
-        7 => { Some((
-                    ip + 4,
-                    Instruction::Lt(OpLess {
-                        a: input(0),
-                        b: input(1),
-                        dest: output(2),
-                    }),
-                    None))
-        },
-
-        8 => { Some((
-                    ip + 4,
-                    Instruction::Eq(OpEqual {
-                        a: input(0),
-                        b: input(1),
-                        dest: output(2),
-                    }),
-                    None))
-        },
-
-        9 => { Some((
-                    ip + 2,
-                    Instruction::Base(OpBase {
-                        val: input(0),
-                    }),
-                    None))
-        },
-
-        _ => None // probably a data section after the program
-    }
-}
-
 // newtype to not mix up with array indices
 #[derive(Debug, Eq, PartialEq, Hash, Clone, Copy, Ord, PartialOrd)]
 struct ProgAddr(usize);
@@ -335,25 +32,64 @@ struct AsmRow {
     jump: Option<ProgAddr>,
 }
 
-fn execute(program: &[i64]) -> (Vec<AsmRow>, HashMap<ProgAddr, Vec<ProgAddr>>) {
-    let mut ip = 0;
-    let mut asm = Vec::new();
+// Follows the code from the start and from every jump target found, so that code after data like
+// strings gets found too. Each run goes on until a stop or something that doesn't decode; the
+// code after an unconditional jump is often a return address, so that's followed too. A trace
+// adds the addresses that ran and where they jumped to.
+fn execute(program: &[i64], trace: Option<&Trace>) -> (Vec<AsmRow>, HashMap<ProgAddr, Vec<ProgAddr>>) {
+    let mut rows = BTreeMap::new();
     let mut refs = HashMap::new();
+    let mut entrypoints = vec![0];
+    if let Some(trace) = trace {
+        entrypoints.extend(trace.executed.keys().rev());
+    }
+
+    while let Some(mut ip) = entrypoints.pop() {
+        while !rows.contains_key(&ip) {
+            let (next_ip, instruction, jump) = match disassemble(program, ip) {
+                Some(decoded) => decoded,
+                None => break,
+            };
+            match instruction {
+                Jnz(OpJnz { addr: SourceParam::Relative(_), .. }) | Jz(OpJz { addr: SourceParam::Relative(_), .. }) =>
+                    eprintln!("warning! indir rel jump at {}", ip),
+                Jnz(_) | Jz(_) if jump.is_none() => eprintln!("warning! indir jump at {}", ip),
+                _ => (),
+            }
+            let stop = matches!(instruction, Stop);
+            rows.insert(ip, AsmRow {
+                ip: ProgAddr(ip),
+                next_ip: ProgAddr(next_ip),
+                instruction,
+                jump: jump.map(ProgAddr),
+            });
+            if let Some(dest) = jump {
+                refs.entry(ProgAddr(dest)).or_insert(Vec::new()).push(ProgAddr(ip));
+                entrypoints.push(dest);
+            }
+            if stop {
+                break;
+            }
+            ip = next_ip;
+        }
+    }
 
-    while let Some((next_ip, instruction, jump)) = step(program, ip) {
-        asm.push(AsmRow {
-            ip: ProgAddr(ip),
-            next_ip: ProgAddr(next_ip),
-            instruction,
-            jump: jump.map(ProgAddr),
-        });
-        if let Some(dest) = jump {
-            refs.entry(ProgAddr(dest)).or_insert(Vec::new()).push(ProgAddr(ip));
+    // the jumps seen only when running, like returns
+    if let Some(trace) = trace {
+        for &(from, to) in &trace.flow {
+            if to != from + trace.executed[&from] && rows.contains_key(&from) {
+                refs.entry(ProgAddr(to)).or_insert(Vec::new()).push(ProgAddr(from));
+            }
         }
-        ip = next_ip;
     }
 
-    (asm, refs)
+    // jumps into data aren't blocks
+    refs.retain(|dest, _| rows.contains_key(&dest.0));
+    for sources in refs.values_mut() {
+        sources.sort();
+        sources.dedup();
+    }
+    (rows.into_values().collect(), refs)
 }
 
 #[derive(Debug, PartialEq)]
@@ -390,6 +126,12 @@ type Blocks = HashMap<ProgAddr, BasicBlock>;
 type BlockEdge = (Option<ProgAddr>, Option<ProgAddr>);
 type BlockEdges = HashMap<ProgAddr, BlockEdge>;
 
+// the fallthrough goes to data if there's a gap, and jumps into data or past the end go nowhere
+fn block_exits(asm: &[AsmRow], last_row: &AsmRow, adjacent: bool) -> BlockEdge {
+    let is_code = |addr: &ProgAddr| asm.binary_search_by_key(addr, |row| row.ip).is_ok();
+    (if adjacent { Some(last_row.next_ip) } else { None }, last_row.jump.filter(is_code))
+}
+
 fn read_blocks(asm: &[AsmRow]) -> (Blocks, BlockEdges) {
 
     // begin -> (begin, end, (beginrow idx in asm, endrow idx in asm) or None if last sentinel)
@@ -402,8 +144,12 @@ fn read_blocks(asm: &[AsmRow]) -> (Blocks, BlockEdges) {
     let mut current_i = 0;
 
     for (i, row) in asm.iter().enumerate() {
-        // a basic block ends at a jump instruction
-        if let Some(jumpdest) = row.jump {
+        // a basic block ends at a jump instruction, also one that goes who knows where, or at a stop
+        // or a gap in the code; the very last one falls to the sentinel
+        let last = i + 1 == asm.len();
+        let adjacent = last || asm[i + 1].ip == row.next_ip;
+        let ends = matches!(row.instruction, Jnz(_) | Jz(_) | Stop) || !adjacent || last;
+        if ends {
             // - bbs:
             // o bb at newip will get created next in this loop
             // o bb at jumpdest will get created below if jumpdest isn't a normal beginning of a bb
@@ -414,12 +160,11 @@ fn read_blocks(asm: &[AsmRow]) -> (Blocks, BlockEdges) {
             // o the split top half has only one edge
             // x (TODO does this work if the bb jumps into itself?)
             bbs.insert(current, BasicBlock::new(current, row.ip, (current_i, i)));
-            bb_edges.insert(current, (Some(row.next_ip), Some(jumpdest)));
-            current = row.next_ip;
+            bb_edges.insert(current, block_exits(asm, row, adjacent));
+            current = asm.get(i + 1).map_or(row.next_ip, |next| next.ip);
             current_i = i + 1;
         }
     }
-    // last bb must end with a jump
     assert!(current == asm.last().unwrap().next_ip);
     // insert sentinel end node for the last continuation
     bbs.insert(current, BasicBlock::new_sentinel(current));
@@ -457,8 +202,7 @@ fn split_bbs(asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgAddr>>,
 
                 // fallthrough edge only
                 let old_top_edge = bb_edges.insert(orig_bb.top, (Some(entrypoint), None));
-                assert_eq!(old_top_edge,
-                           Some((Some(row_by_addr[&bottom_bb.bottom].next_ip), row_by_addr[&bottom_bb.bottom].jump)));
+                assert_eq!(old_top_edge.map(|edge| edge.1), Some(block_exits(asm, row_by_addr[&bottom_bb.bottom], true).1));
                 *orig_bb = top_bb;
 
                 // destination
@@ -475,7 +219,7 @@ fn split_bbs(asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgAddr>>,
 
 // note that if the program is self-modifying and alters these operands, then the missing edges
 // will be very confusing in the diagram. Let's hope that it a) doesn't happen or b) will be
-// detectable.
+// detectable: --trace shows the instructions that get written to and the edges that really ran.
 fn cut_edges(asm: &[AsmRow], _refs: &HashMap<ProgAddr, Vec<ProgAddr>>,
              bbs: &mut Blocks, bb_edges: &mut BlockEdges) {
     for bb in bbs.values() {
@@ -537,40 +281,26 @@ fn inst_edge_hint(inst: &Instruction, is_jump: bool) -> String {
     }
 }
 
-fn print_edgedesc(asm: &[AsmRow], bbs: &Blocks, frombb: &BasicBlock, nextopt: Option<ProgAddr>, title: &str, is_jump: bool) {
+fn print_edgedesc(asm: &[AsmRow], bbs: &Blocks, frombb: &BasicBlock, nextopt: Option<ProgAddr>, title: &str, is_jump: bool,
+                  trace: Option<&Trace>) {
     if let Some(next) = nextopt {
         let nextbb = &bbs[&next];
         let rows = frombb.source_rows.unwrap();
         let row = &asm[rows.1];
         let hint = inst_edge_hint(&row.instruction, is_jump);
-        println!("L{}_{} -> L{}_{} [label=\"{}\\n{}\"]",
+        let style = match trace {
+            Some(trace) if trace.flow.contains(&(row.ip.value(), next.value())) => ", style=bold, color=blue",
+            _ => "",
+        };
+        println!("L{}_{} -> L{}_{} [label=\"{}\\n{}\"{}]",
                  frombb.top.value(), frombb.bottom.value(),
                  (nextbb.top).value(), (nextbb.bottom).value(),
-                 title, hint);
+                 title, hint, style);
     }
 
 }
 
-fn addr_nickname(addr: i64) -> Option<&'static str> {
-    match addr {
-        1032 => Some("tmp"),
-        1033 => Some("inp"),
-        1034 => Some("currx"),
-        1035 => Some("curry"),
-        1036 => Some("oddx"),
-        1037 => Some("halfy"),
-        1038 => Some("oddy"),
-        1039 => Some("nx"),
-        1040 => Some("ny"),
-        1041 => Some("noddx"),
-        1042 => Some("nhalfy"),
-        1043 => Some("noddy"),
-        1044 => Some("outval"),
-        _ => None,
-    }
-}
-
-fn nicknames(param: Param) -> Option<&'static str> {
+fn nicknames(param: Param) -> Option<String> {
     match param {
         Param::Source(SourceParam::Relative(addr)) => addr_nickname(addr),
         Param::Dest(DestParam::Relative(addr)) => addr_nickname(addr),
@@ -578,12 +308,35 @@ fn nicknames(param: Param) -> Option<&'static str> {
     }
 }
 
-fn graphviz(_program: &[i64], asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgAddr>>) {
+// instruction address -> the instructions that wrote into it during the trace
+fn overwritten(asm: &[AsmRow], trace: Option<&Trace>) -> BTreeMap<ProgAddr, BTreeSet<usize>> {
+    let mut rows = BTreeMap::new();
+    if let Some(trace) = trace {
+        for row in asm {
+            for (_, writers) in trace.writes.range(row.ip.value()..row.next_ip.value()) {
+                rows.entry(row.ip).or_insert(BTreeSet::new()).extend(writers);
+            }
+        }
+    }
+    rows
+}
+
+fn graphviz(_program: &[i64], asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgAddr>>, trace: Option<&Trace>) {
     println!("digraph G {{");
     println!("node [shape=box, fontname=monospace]");
     println!();
 
     let (bbs, bb_edges) = build_bbs(asm, refs);
+    let overwritten = overwritten(asm, trace);
+
+    if let Some(trace) = trace {
+        println!("// traced {} steps until {}, {} instructions ran", trace.steps, trace.end, trace.executed.len());
+        println!("// bold blue edges ran, dashed ones were only seen running; red code got written to, gray code never ran");
+        for (ip, writers) in &overwritten {
+            println!("// {:05} overwritten by {:?}", ip.value(), writers);
+        }
+        println!();
+    }
 
     // sort the starting addresses that the names start with for consistent bbs; graphviz cares
     // about the input order
@@ -598,13 +351,22 @@ fn graphviz(_program: &[i64], asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgA
             let first_row = coords.0;
             let last_row = coords.1;
             let strings = asm[first_row..=last_row].iter()
-                .map(|row| format!("{:05}: {}", row.ip.0, row.instruction)).collect::<Vec<_>>();
+                .map(|row| match overwritten.get(&row.ip) {
+                    Some(writers) => format!("{:05}: {}  <- {:?}", row.ip.0, row.instruction, writers),
+                    None => format!("{:05}: {}", row.ip.0, row.instruction),
+                }).collect::<Vec<_>>();
             strings.join("\\l")
         } else {
             // the sentinel node has its own bb too, the last instruction may advance to it
             "END".to_string()
         };
-        println!("L{}_{} [label=\"{}\\l\"]", bb.top.value(), bb.bottom.value(), label);
+        let style = match (trace, bb.source_rows) {
+            (Some(_), Some((first, last))) if asm[first..=last].iter().any(|row| overwritten.contains_key(&row.ip)) =>
+                ", color=red",
+            (Some(trace), Some(_)) if !trace.executed.contains_key(&bb.top.value()) => ", color=gray, fontcolor=gray",
+            _ => "",
+        };
+        println!("L{}_{} [label=\"{}\\l\"{}]", bb.top.value(), bb.bottom.value(), label, style);
     }
 
     println!();
@@ -622,50 +384,173 @@ fn graphviz(_program: &[i64], asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgA
 
         let &(contopt, jumpopt) = &bb_edges[&from];
         let frombb = &bbs[&from];
-        print_edgedesc(asm, &bbs, frombb, contopt, "fall", false);
-        print_edgedesc(asm, &bbs, frombb, jumpopt, "jump", true);
+        print_edgedesc(asm, &bbs, frombb, contopt, "fall", false, trace);
+        print_edgedesc(asm, &bbs, frombb, jumpopt, "jump", true, trace);
+
+        // the ones the static analysis couldn't see, like returns and computed jumps
+        if let Some(trace) = trace {
+            let bottom = frombb.bottom.value();
+            let mut seen = trace.flow.iter().filter(|&&(from, to)| from == bottom
+                    && Some(ProgAddr(to)) != contopt && Some(ProgAddr(to)) != jumpopt)
+                .filter_map(|&(_, to)| bbs.get(&ProgAddr(to))).collect::<Vec<_>>();
+            seen.sort_by_key(|bb| bb.top);
+            for tobb in seen {
+                println!("L{}_{} -> L{}_{} [label=\"traced\", style=dashed, color=blue]",
+                         frombb.top.value(), frombb.bottom.value(), tobb.top.value(), tobb.bottom.value());
+            }
+        }
     }
 
     println!("}}");
 }
 
-fn rawasm(program: &[i64], asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgAddr>>) {
-    for row in asm {
-        let raw_numbs = format!("{:>4?}", &program[row.ip.value()..row.next_ip.value()]);
-        let jumpfrom = &match refs.get(&row.ip) {
-            // hmm, could &Vec<ProcAddr> be cast into &Vec<usize> for printing? unsafe only?
-            Some(sources) => format!("{:>4?}", sources.iter().map(|x| x.value()).collect::<Vec<_>>()),
-            None => "|".to_string(),
-        };
+// the address, raw numbers, instruction and where it's jumped to from
+fn asm_line(program: &[i64], row: &AsmRow, refs: &HashMap<ProgAddr, Vec<ProgAddr>>) -> String {
+    let raw_numbs = format!("{:>4?}", &program[row.ip.value()..row.next_ip.value()]);
+    let jumpfrom = &match refs.get(&row.ip) {
+        // hmm, could &Vec<ProcAddr> be cast into &Vec<usize> for printing? unsafe only?
+        Some(sources) => format!("{:>4?}", sources.iter().map(|x| x.value()).collect::<Vec<_>>()),
+        None => "|".to_string(),
+    };
 
-        // HACK: stringize instruction first, then pad that
-        println!("#{:05}: {:<25} {:<30}{}", row.ip.value(), raw_numbs, format!("{}", row.instruction), jumpfrom);
+    // HACK: stringize instruction first, then pad that
+    format!("{:05}: {:<25} {:<30}{}", row.ip.value(), raw_numbs, format!("{}", row.instruction), jumpfrom)
+}
+
+// what's left between and after the code, as [begin, end) pairs
+fn data_ranges(program: &[i64], asm: &[AsmRow]) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut ip = 0;
+    for row in asm {
+        if row.ip.value() > ip {
+            ranges.push((ip, row.ip.value()));
+        }
+        ip = ip.max(row.next_ip.value());
     }
+    if ip < program.len() {
+        ranges.push((ip, program.len()));
+    }
+    ranges
+}
 
-    // "data segment"
-    let mut ip = asm.last().unwrap().next_ip.0;
-    while ip < program.len() {
-        let right = if ip + 10 < program.len() { ip + 10 } else { program.len() };
+fn print_data(program: &[i64], begin: usize, end: usize) {
+    let mut ip = begin;
+    while ip < end {
+        let right = if ip + 10 < end { ip + 10 } else { end };
         println!("#{:05}: {:>4?}", ip, &program[ip..right]);
         ip += 10;
     }
 }
 
-fn analyze(program: &[i64]) {
-    let (asm, refs) = execute(program);
+fn rawasm(program: &[i64], asm: &[AsmRow], refs: &HashMap<ProgAddr, Vec<ProgAddr>>, symbols: &[Symbol]) {
+    let data = data_ranges(program, asm);
+    let mut gaps = data.iter().peekable();
+    for row in asm {
+        // strings and such in the middle of code
+        while let Some(&&(begin, end)) = gaps.peek() {
+            if begin > row.ip.value() {
+                break;
+            }
+            print_data(program, begin, end);
+            gaps.next();
+        }
+        println!("#{}", asm_line(program, row, refs));
+    }
+
+    // "data segment"
+    for &(begin, end) in gaps {
+        print_data(program, begin, end);
+    }
+
+    for sym in symbols {
+        let value = program.get(sym.addr as usize).copied().unwrap_or(0);
+        let comment = sym.comment.as_ref().map(|c| format!(" # {}", c)).unwrap_or_default();
+        println!("#{:05}: {:<6} {:<6} = {}{}", sym.addr, sym.name, sym.kind.as_deref().unwrap_or(""), value, comment);
+    }
+}
+
+// a starting point for a symbol file: the data segment addresses that the code refers to directly
+fn skeleton(program: &[i64], asm: &[AsmRow], symbols: &[Symbol]) {
+    let data = data_ranges(program, asm);
+    let is_data = |addr: i64| data.iter().any(|&(begin, end)| (begin as i64..end as i64).contains(&addr));
+    // addr -> (reads, writes, first use)
+    let mut uses: BTreeMap<i64, (usize, usize, usize)> = BTreeMap::new();
+    for row in asm {
+        let (sources, dest) = row.instruction.operands();
+        let reads = sources.iter().filter_map(|&src| match *src {
+            SourceParam::Position(addr) => Some(addr),
+            _ => None,
+        });
+        let writes = dest.and_then(|dest| match *dest {
+            DestParam::Position(addr) => Some(addr),
+            _ => None,
+        });
+        for (addr, is_write) in reads.map(|a| (a, false)).chain(writes.map(|a| (a, true))) {
+            if is_data(addr) || addr >= program.len() as i64 {
+                let entry = uses.entry(addr).or_insert((0, 0, row.ip.value()));
+                if is_write { entry.1 += 1; } else { entry.0 += 1; }
+            }
+        }
+    }
+
+    for (begin, end) in data {
+        println!("# data segment at {}..{}", begin, end);
+    }
+    for (addr, (reads, writes, first)) in uses {
+        match symbols.iter().find(|s| s.addr == addr) {
+            Some(sym) => println!("{}", sym),
+            None => println!("{} v{} # read {}x, written {}x, first used at {}", addr, addr, reads, writes, first),
+        }
+    }
+}
+
+fn analyze(program: &[i64], symbols: &[Symbol], skeleton_only: bool, decompile_only: bool, trace: Option<&Trace>) {
+    let (asm, refs) = execute(program, trace);
+
+    if skeleton_only {
+        skeleton(program, &asm, symbols);
+        return;
+    }
+
+    if decompile_only {
+        decompile::decompile(program, &asm, &refs, symbols);
+        return;
+    }
 
     if true {
-        rawasm(program, &asm, &refs);
+        rawasm(program, &asm, &refs, symbols);
     }
 
     if true {
-        graphviz(program, &asm, &refs);
+        graphviz(program, &asm, &refs, trace);
     }
 }
 
+// usage: disasm [-s symbols] [--trace inputs] [--skeleton | --decompile] < program
 fn main() {
-    let program: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
-        .split(',').map(|n| n.parse().unwrap()).collect();
+    let mut symbols = Vec::new();
+    let mut inputs_path = None;
+    let mut skeleton_only = false;
+    let mut decompile_only = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-s" => symbols = asm::load_symbols(&args.next().expect("symbol file")),
+            "--skeleton" => skeleton_only = true,
+            "--decompile" => decompile_only = true,
+            "--trace" => inputs_path = Some(args.next().expect("recorded inputs")),
+            _ => panic!("unknown argument {}", arg),
+        }
+    }
+
+    let program = intcode::read_program();
+
+    let trace = inputs_path.map(|path| {
+        let inputs = trace::parse_inputs(&fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e)));
+        trace::trace(&program, &inputs)
+    });
+    // code that only exists at runtime is shown as it ran
+    let program = trace.as_ref().map_or(program.clone(), |trace| trace.patched(&program));
 
-    analyze(&program);
+    analyze(&program, &symbols, skeleton_only, decompile_only, trace.as_ref());
 }
@@ -0,0 +1,341 @@
+// The Intcode machine shared by all 2019 puzzles. Each day used to carry its own copy of step();
+// this is the one to build against now. Not every day uses every feature.
+#![allow(dead_code)]
+
+use std::io::{self, BufRead};
+use std::collections::VecDeque;
+use std::fs;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Mode {
+    Position,
+    Immediate,
+    Relative,
+}
+
+// opcode and the modes of its up to three parameters, or None if this doesn't look like an
+// instruction (probably data then)
+pub fn decode(word: i64) -> Option<(i64, [Mode; 3])> {
+    let opcode = word % 100;
+    if instruction_size(opcode).is_none() {
+        return None;
+    }
+
+    let mode = |digit: i64| match word / digit % 10 {
+        0 => Some(Mode::Position),
+        1 => Some(Mode::Immediate),
+        2 => Some(Mode::Relative),
+        _ => None,
+    };
+
+    Some((opcode, [mode(100)?, mode(1000)?, mode(10000)?]))
+}
+
+pub fn instruction_size(opcode: i64) -> Option<usize> {
+    match opcode {
+        1 | 2 | 7 | 8 => Some(4),
+        5 | 6 => Some(3),
+        3 | 4 | 9 => Some(2),
+        99 => Some(1),
+        _ => None,
+    }
+}
+
+// Result of a single instruction
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Step {
+    Continue,
+    // an input value was consumed
+    Input,
+    Output(i64),
+    // would read input but none is available; ip stays at the input instruction
+    NeedInput,
+    Halted,
+}
+
+// Why a run stopped; the machine can be resumed after the first two
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RunState {
+    NeedInput,
+    Output(i64),
+    Halted,
+}
+
+impl RunState {
+    pub fn output(self) -> Option<i64> {
+        match self {
+            RunState::Output(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Computer {
+    // grows on demand, "much larger than the initial program"
+    mem: Vec<i64>,
+    ip: usize,
+    base: i64,
+    // default channels for step() and run(); the *_with variants take any iterator instead
+    pub inputs: VecDeque<i64>,
+    pub outputs: VecDeque<i64>,
+}
+
+impl Computer {
+    pub fn new(program: &[i64]) -> Self {
+        Computer {
+            mem: program.to_vec(),
+            ip: 0,
+            base: 0,
+            inputs: VecDeque::new(),
+            outputs: VecDeque::new(),
+        }
+    }
+
+    pub fn ip(&self) -> usize {
+        self.ip
+    }
+
+    pub fn base(&self) -> i64 {
+        self.base
+    }
+
+    pub fn memory(&self) -> &[i64] {
+        &self.mem
+    }
+
+    // unwritten memory reads as zero
+    pub fn read(&self, addr: usize) -> i64 {
+        self.mem.get(addr).copied().unwrap_or(0)
+    }
+
+    pub fn write(&mut self, addr: usize, value: i64) {
+        *self.cell(addr) = value;
+    }
+
+    fn cell(&mut self, addr: usize) -> &mut i64 {
+        if addr >= self.mem.len() {
+            self.mem.resize(addr + 1, 0);
+        }
+        &mut self.mem[addr]
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.read(self.ip) == 99
+    }
+
+    pub fn push_input(&mut self, value: i64) {
+        self.inputs.push_back(value);
+    }
+
+    pub fn push_str(&mut self, text: &str) {
+        self.inputs.extend(text.bytes().map(|b| b as i64));
+    }
+
+    // position in memory where parameter n (0-based) of the current instruction refers to
+    fn param_addr(&self, mode: Mode, n: usize) -> usize {
+        let raw = self.read(self.ip + 1 + n);
+        let addr = match mode {
+            Mode::Position => raw,
+            Mode::Relative => raw + self.base,
+            Mode::Immediate => panic!("immediate param {} has no address at {}", n, self.ip),
+        };
+        assert!(addr >= 0, "negative address {} at {}", addr, self.ip);
+        addr as usize
+    }
+
+    fn param(&self, modes: &[Mode; 3], n: usize) -> i64 {
+        match modes[n] {
+            Mode::Immediate => self.read(self.ip + 1 + n),
+            mode => self.read(self.param_addr(mode, n)),
+        }
+    }
+
+    fn store(&mut self, modes: &[Mode; 3], n: usize, value: i64) {
+        let addr = self.param_addr(modes[n], n);
+        self.write(addr, value);
+    }
+
+    pub fn step_with<I: Iterator<Item = i64>>(&mut self, input: &mut I) -> Step {
+        let (opcode, modes) = decode(self.read(self.ip))
+            .unwrap_or_else(|| panic!("something went wrong at {}: {}", self.ip, self.read(self.ip)));
+        let arg = |n| self.param(&modes, n);
+
+        let mut result = Step::Continue;
+        let mut next_ip = self.ip + instruction_size(opcode).unwrap();
+        match opcode {
+            1 => self.store(&modes, 2, arg(0) + arg(1)),
+            2 => self.store(&modes, 2, arg(0) * arg(1)),
+            3 => {
+                match input.next() {
+                    Some(value) => {
+                        self.store(&modes, 0, value);
+                        result = Step::Input;
+                    },
+                    None => return Step::NeedInput,
+                }
+            },
+            4 => result = Step::Output(arg(0)),
+            5 => if arg(0) != 0 { next_ip = arg(1) as usize },
+            6 => if arg(0) == 0 { next_ip = arg(1) as usize },
+            7 => self.store(&modes, 2, (arg(0) < arg(1)) as i64),
+            8 => self.store(&modes, 2, (arg(0) == arg(1)) as i64),
+            9 => self.base += arg(0),
+            // short circuit this, the discontinuity is annoying; ip stays here
+            99 => return Step::Halted,
+            _ => unreachable!()
+        }
+        self.ip = next_ip;
+
+        result
+    }
+
+    // run until something interesting happens
+    pub fn run_with<I: Iterator<Item = i64>>(&mut self, input: &mut I) -> RunState {
+        loop {
+            match self.step_with(input) {
+                Step::Continue | Step::Input => (),
+                Step::Output(value) => return RunState::Output(value),
+                Step::NeedInput => return RunState::NeedInput,
+                Step::Halted => return RunState::Halted,
+            }
+        }
+    }
+
+    // these use the builtin input queue
+    pub fn step(&mut self) -> Step {
+        let mut inputs = std::mem::take(&mut self.inputs);
+        let result = self.step_with(&mut std::iter::from_fn(|| inputs.pop_front()));
+        self.inputs = inputs;
+        result
+    }
+
+    pub fn run(&mut self) -> RunState {
+        let mut inputs = std::mem::take(&mut self.inputs);
+        let result = self.run_with(&mut std::iter::from_fn(|| inputs.pop_front()));
+        self.inputs = inputs;
+        result
+    }
+
+    // next output value, or None if the program halts or starves first
+    pub fn next_output(&mut self) -> Option<i64> {
+        self.run().output()
+    }
+
+    // run as far as possible, collecting the outputs in the builtin output queue
+    pub fn run_buffered(&mut self) -> RunState {
+        loop {
+            match self.run() {
+                RunState::Output(value) => self.outputs.push_back(value),
+                state => return state,
+            }
+        }
+    }
+
+    // for the simple run-to-completion puzzles
+    pub fn run_to_halt(&mut self, inputs: &[i64]) -> Vec<i64> {
+        self.inputs.extend(inputs);
+        assert_eq!(self.run_buffered(), RunState::Halted, "ran out of input");
+        self.outputs.drain(..).collect()
+    }
+
+    // The complete machine state as text, to checkpoint a long session or attach to a bug report:
+    //
+    // intcode snapshot 1
+    // ip 1033
+    // base 2140
+    // inputs 110,111
+    // outputs
+    // mem 109,4806,21101,...
+    //
+    // Memory past the last nonzero cell is left out since it reads as zero anyway. Lines starting
+    // with # are notes from whatever saved the machine, like "# droid 3,-5"; the machine itself
+    // doesn't care about them.
+    pub fn snapshot(&self) -> String {
+        let used = self.mem.iter().rposition(|&v| v != 0).map_or(0, |last| last + 1);
+        let fields = [
+            SNAPSHOT_HEADER.to_string(),
+            format!("ip {}", self.ip),
+            format!("base {}", self.base),
+            format!("inputs {}", join_numbers(&self.inputs)),
+            format!("outputs {}", join_numbers(&self.outputs)),
+            format!("mem {}", join_numbers(&self.mem[..used])),
+        ];
+        fields.iter().map(|f| f.trim_end().to_string() + "\n").collect()
+    }
+
+    pub fn snapshot_with_notes(&self, notes: &[(&str, String)]) -> String {
+        let mut text = self.snapshot();
+        for (key, value) in notes {
+            text += format!("# {} {}", key, value).trim_end();
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn from_snapshot(text: &str) -> Result<Computer, String> {
+        let mut lines = text.lines();
+        if lines.next().map(str::trim) != Some(SNAPSHOT_HEADER) {
+            return Err(format!("not an {:?}", SNAPSHOT_HEADER));
+        }
+        let list = |value: &str| -> Result<Vec<i64>, String> {
+            value.split(',').filter(|v| !v.is_empty())
+                .map(|v| v.parse().map_err(|_| format!("bad number {:?}", v)))
+                .collect()
+        };
+        let (mut ip, mut base, mut mem) = (None, None, None);
+        let mut computer = Computer::new(&[]);
+        for line in lines.filter(|l| !l.trim().is_empty() && !l.starts_with('#')) {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            let value = value.trim();
+            match key {
+                "ip" => ip = Some(value.parse().map_err(|_| format!("bad ip {:?}", value))?),
+                "base" => base = Some(value.parse().map_err(|_| format!("bad base {:?}", value))?),
+                "inputs" => computer.inputs = list(value)?.into(),
+                "outputs" => computer.outputs = list(value)?.into(),
+                "mem" => mem = Some(list(value)?),
+                _ => return Err(format!("unknown field {:?}", key)),
+            }
+        }
+        computer.ip = ip.ok_or("no ip")?;
+        computer.base = base.ok_or("no base")?;
+        computer.mem = mem.ok_or("no mem")?;
+        Ok(computer)
+    }
+
+    pub fn save_snapshot(&self, path: &str) -> io::Result<()> {
+        fs::write(path, self.snapshot())
+    }
+
+    pub fn load_snapshot(path: &str) -> io::Result<Computer> {
+        Computer::from_snapshot(&fs::read_to_string(path)?)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path, e)))
+    }
+}
+
+fn join_numbers<'a>(values: impl IntoIterator<Item = &'a i64>) -> String {
+    values.into_iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",")
+}
+
+pub const SNAPSHOT_HEADER: &str = "intcode snapshot 1";
+
+pub fn is_snapshot(text: &str) -> bool {
+    text.starts_with(SNAPSHOT_HEADER)
+}
+
+// the value of a "# key value" note in a snapshot
+pub fn snapshot_note<'a>(text: &'a str, key: &str) -> Option<&'a str> {
+    text.lines()
+        .filter_map(|l| l.strip_prefix('#'))
+        .map(|l| l.trim().split_once(' ').unwrap_or((l.trim(), "")))
+        .find(|&(k, _)| k == key)
+        .map(|(_, value)| value.trim())
+}
+
+pub fn parse_program(line: &str) -> Vec<i64> {
+    line.trim().split(',').map(|n| n.parse().unwrap()).collect()
+}
+
+pub fn read_program() -> Vec<i64> {
+    parse_program(&io::stdin().lock().lines().next().unwrap().unwrap())
+}
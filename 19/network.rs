@@ -0,0 +1,256 @@
+// A network of Intcode machines passing (x, y) packets to each other by address, like the NICs of
+// day 23. Nodes are booted with their own address and then read either packets or -1 for nothing.
+// Where packets go is up to the routing table, and what happens to the ones for the NAT, as well
+// as what to do when the whole network goes idle, is up to a NAT policy. Everything that moves
+// can be traced as text, one event per line:
+//
+// packet 3 255 42 1337
+// idle
+// packet 255 0 42 1337
+//
+// and a trace read back can be compared to another run to see where two schedules or two
+// versions of a policy start to disagree.
+#![allow(dead_code)]
+
+use crate::intcode::{Computer, RunState};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+
+pub type Address = i64;
+
+pub const NAT_ADDRESS: Address = 255;
+
+// a node that has been fed -1 and sent nothing this many turns in a row counts as idle; one isn't
+// always enough for a node that does some work between polls
+const IDLE_TURNS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Packet {
+    pub from: Address,
+    pub to: Address,
+    pub x: i64,
+    pub y: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Route {
+    Node(usize),
+    Nat,
+    Drop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Schedule {
+    // every node gets one turn in order whether it has anything to do or not
+    RoundRobin,
+    // only nodes with packets waiting get to run; faster, but a node that would send something
+    // only after polling a few times in vain never gets the chance
+    EventDriven,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Nothing,
+    Send(Packet),
+    Stop,
+}
+
+pub trait Nat {
+    // a packet was routed to the NAT
+    fn receive(&mut self, packet: Packet) -> Action;
+    // nothing is moving anywhere
+    fn idle(&mut self) -> Action;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Packet(Packet),
+    Idle,
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Event::Packet(p) => write!(f, "packet {} {} {} {}", p.from, p.to, p.x, p.y),
+            Event::Idle => write!(f, "idle"),
+        }
+    }
+}
+
+impl FromStr for Event {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let words = s.split_whitespace().collect::<Vec<_>>();
+        match words[..] {
+            ["idle"] => Ok(Event::Idle),
+            ["packet", from, to, x, y] => {
+                let num = |w: &str| w.parse::<i64>().map_err(|_| format!("bad number {:?} in {:?}", w, s));
+                Ok(Event::Packet(Packet { from: num(from)?, to: num(to)?, x: num(x)?, y: num(y)? }))
+            },
+            _ => Err(format!("bad trace line {:?}", s)),
+        }
+    }
+}
+
+pub fn parse_trace(text: &str) -> Result<Vec<Event>, String> {
+    text.lines().filter(|l| !l.trim().is_empty() && !l.starts_with('#')).map(str::parse).collect()
+}
+
+pub fn trace_text(trace: &[Event]) -> String {
+    trace.iter().map(|e| e.to_string() + "\n").collect()
+}
+
+// index of the first event where the traces disagree, including one ending before the other
+pub fn first_difference(a: &[Event], b: &[Event]) -> Option<usize> {
+    let common = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    if common == a.len() && common == b.len() { None } else { Some(common) }
+}
+
+pub struct Network<N: Nat> {
+    nodes: Vec<Computer>,
+    // consecutive idle turns per node
+    idle: Vec<usize>,
+    routes: HashMap<Address, Route>,
+    schedule: Schedule,
+    // nodes with packets to read, for the event driven schedule
+    ready: VecDeque<usize>,
+    pub nat: N,
+    stopped: bool,
+    tracing: bool,
+    pub trace: Vec<Event>,
+    pub turns: usize,
+}
+
+impl<N: Nat> Network<N> {
+    // nodes get addresses from 0 on, and the NAT is at 255 as usual
+    pub fn new(program: &[i64], nodes: usize, nat: N) -> Self {
+        let mut routes = (0..nodes).map(|i| (i as Address, Route::Node(i))).collect::<HashMap<_, _>>();
+        routes.insert(NAT_ADDRESS, Route::Nat);
+        let nodes = (0..nodes).map(|i| {
+            let mut nic = Computer::new(program);
+            nic.push_input(i as i64);
+            nic
+        }).collect::<Vec<_>>();
+        Network {
+            idle: vec![0; nodes.len()],
+            ready: (0..nodes.len()).collect(),
+            nodes,
+            routes,
+            schedule: Schedule::RoundRobin,
+            nat,
+            stopped: false,
+            tracing: false,
+            trace: Vec::new(),
+            turns: 0,
+        }
+    }
+
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    pub fn with_route(mut self, address: Address, route: Route) -> Self {
+        self.routes.insert(address, route);
+        self
+    }
+
+    pub fn tracing(mut self) -> Self {
+        self.tracing = true;
+        self
+    }
+
+    fn record(&mut self, event: Event) {
+        if self.tracing {
+            self.trace.push(event);
+        }
+    }
+
+    fn act(&mut self, action: Action) {
+        match action {
+            Action::Nothing => (),
+            Action::Send(packet) => self.deliver(packet),
+            Action::Stop => self.stopped = true,
+        }
+    }
+
+    fn deliver(&mut self, packet: Packet) {
+        self.record(Event::Packet(packet));
+        let route = *self.routes.get(&packet.to)
+            .unwrap_or_else(|| panic!("no route to {} for {:?}", packet.to, packet));
+        match route {
+            Route::Node(i) => {
+                self.nodes[i].push_input(packet.x);
+                self.nodes[i].push_input(packet.y);
+                if self.schedule == Schedule::EventDriven && !self.ready.contains(&i) {
+                    self.ready.push_back(i);
+                }
+            },
+            Route::Nat => {
+                let action = self.nat.receive(packet);
+                self.act(action);
+            },
+            Route::Drop => (),
+        }
+    }
+
+    // run node i until it wants more input, giving it -1 if nothing is waiting; event driven
+    // nodes get one -1 after their packets too, as they won't be polled again until the next one
+    fn turn(&mut self, i: usize) {
+        self.turns += 1;
+        let nic = &mut self.nodes[i];
+        let fed = !nic.inputs.is_empty();
+        if !fed || self.schedule == Schedule::EventDriven {
+            nic.push_input(-1);
+        }
+        let state = nic.run_buffered();
+        assert_eq!(state, RunState::NeedInput, "node {} stopped", i);
+
+        // a packet half written stays in the buffer until the rest comes out
+        let mut packets = Vec::new();
+        while nic.outputs.len() >= 3 {
+            let mut next = || nic.outputs.pop_front().unwrap();
+            packets.push(Packet { from: i as Address, to: next(), x: next(), y: next() });
+        }
+        self.idle[i] = if fed || !packets.is_empty() { 0 } else { self.idle[i] + 1 };
+        for packet in packets {
+            self.deliver(packet);
+        }
+    }
+
+    fn quiet(&self) -> bool {
+        self.nodes.iter().all(|nic| nic.inputs.is_empty()) && self.idle.iter().all(|&n| n >= IDLE_TURNS)
+    }
+
+    // until the NAT says stop, or the network is idle and the NAT does nothing about it
+    pub fn run(&mut self) {
+        while !self.stopped {
+            match self.schedule {
+                Schedule::RoundRobin => {
+                    for i in 0..self.nodes.len() {
+                        self.turn(i);
+                        if self.stopped {
+                            return;
+                        }
+                    }
+                    if !self.quiet() {
+                        continue;
+                    }
+                },
+                Schedule::EventDriven => {
+                    if let Some(i) = self.ready.pop_front() {
+                        self.turn(i);
+                        continue;
+                    }
+                },
+            }
+            self.record(Event::Idle);
+            match self.nat.idle() {
+                Action::Nothing => return,
+                action => self.act(action),
+            }
+        }
+    }
+}
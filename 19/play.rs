@@ -0,0 +1,272 @@
+// Interactive front-end for the Intcode programs that talk ASCII, like the text adventure of day
+// 25. Typed lines go to the program, its text comes back line by line, and values that aren't
+// characters show up on their own as "= 123". Lines starting with ! @ or : are for the front-end,
+// type ":help" for those.
+//
+// 19/play 19/25.input
+// 19/play -o session.txt 19/25.input    record a transcript of the session
+// 19/play -r session.txt 19/25.input    replay a transcript, checking that the program still says
+//                                       the same things, then keep playing
+//
+// The program file may also be an Intcode snapshot, to continue from where it was saved. A
+// transcript has the commands as "> north", the program's lines as "| text" and values as "= 123".
+mod intcode;
+mod ascii;
+use ascii::{Output, Terminal};
+use intcode::Computer;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process;
+
+const HELP: &str = "\
+!!              repeat the last command
+!n              repeat command n from the history
+@name           run a macro
+:history        list the commands so far
+:def name a; b  define a macro of commands separated by semicolons, or list the macros
+:save file      write a snapshot of the machine
+:load file      continue from a snapshot
+:q              quit";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Entry {
+    Input(String),
+    Output(Output),
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // an empty line has nothing after the marker, not even the space; anything else is
+        // kept as is, trailing spaces too
+        let (marker, text) = match self {
+            Entry::Input(line) => ('>', line.clone()),
+            Entry::Output(Output::Line(line)) => ('|', line.clone()),
+            Entry::Output(Output::Value(value)) => ('=', value.to_string()),
+        };
+        if text.is_empty() {
+            write!(f, "{}", marker)
+        } else {
+            write!(f, "{} {}", marker, text)
+        }
+    }
+}
+
+fn parse_transcript(text: &str) -> Result<Vec<Entry>, String> {
+    text.lines().enumerate().filter(|(_, line)| !line.is_empty()).map(|(n, line)| {
+        let mut chars = line.chars();
+        let marker = chars.next();
+        let rest = chars.as_str();
+        let rest = rest.strip_prefix(' ').unwrap_or(rest);
+        match marker {
+            Some('>') => Ok(Entry::Input(rest.to_string())),
+            Some('|') => Ok(Entry::Output(Output::Line(rest.to_string()))),
+            Some('=') => rest.parse().map(|v| Entry::Output(Output::Value(v)))
+                .map_err(|_| format!("line {}: bad value {:?}", n + 1, rest)),
+            _ => Err(format!("line {}: unknown entry {:?}", n + 1, line)),
+        }
+    }).collect()
+}
+
+struct Session {
+    terminal: Terminal,
+    history: Vec<String>,
+    macros: BTreeMap<String, Vec<String>>,
+    record: Option<File>,
+}
+
+impl Session {
+    fn log(&mut self, entry: Entry) {
+        if let Some(file) = &mut self.record {
+            writeln!(file, "{}", entry).unwrap();
+        }
+    }
+
+    // everything the program says until it wants more input
+    fn flush(&mut self) -> Vec<Output> {
+        let outputs = self.terminal.read_all();
+        for out in &outputs {
+            match out {
+                Output::Line(line) => println!("{}", line),
+                Output::Value(value) => println!("= {}", value),
+            }
+            self.log(Entry::Output(out.clone()));
+        }
+        outputs
+    }
+
+    fn send(&mut self, line: &str) {
+        self.history.push(line.to_string());
+        self.log(Entry::Input(line.to_string()));
+        self.terminal.send_line(line);
+    }
+
+    fn send_and_show(&mut self, line: &str) {
+        self.send(line);
+        self.flush();
+    }
+
+    // the commands of the transcript with what the program said in between checked against
+    // what it says now; false if something differed
+    fn replay(&mut self, entries: &[Entry]) -> bool {
+        let mut same = true;
+        let mut expected = Vec::new();
+        let mut check = |session: &mut Session, expected: &mut Vec<Output>, sent: usize| {
+            let got = session.flush();
+            if got != *expected && same {
+                let at = got.iter().zip(expected.iter()).take_while(|(a, b)| a == b).count();
+                eprintln!("# transcript differs after {} commands: expected {:?}, got {:?}",
+                          sent, expected.get(at), got.get(at));
+                same = false;
+            }
+            expected.clear();
+        };
+        let mut sent = 0;
+        for entry in entries {
+            match entry {
+                Entry::Input(line) => {
+                    check(self, &mut expected, sent);
+                    self.send(line);
+                    sent += 1;
+                },
+                Entry::Output(out) => expected.push(out.clone()),
+            }
+        }
+        check(self, &mut expected, sent);
+        same
+    }
+
+    fn history_line(&self, which: &str) -> Option<String> {
+        let n = if which == "!" { self.history.len().checked_sub(1)? } else { which.parse().ok()? };
+        self.history.get(n).cloned()
+    }
+
+    // false to quit
+    fn command(&mut self, line: &str) -> bool {
+        // lines for the program go as typed, trailing spaces too, so that a transcript has them
+        let trimmed = line.trim_end();
+        if let Some(which) = trimmed.strip_prefix('!') {
+            match self.history_line(which) {
+                Some(line) => self.send_and_show(&line),
+                None => eprintln!("no such command in the history"),
+            }
+            return true;
+        }
+        if let Some(name) = trimmed.strip_prefix('@') {
+            match self.macros.get(name).cloned() {
+                Some(commands) => commands.iter().for_each(|c| self.send_and_show(c)),
+                None => eprintln!("no macro {}", name),
+            }
+            return true;
+        }
+        let meta = match trimmed.strip_prefix(':') {
+            Some(meta) => meta,
+            None => {
+                self.send_and_show(line);
+                return true;
+            }
+        };
+
+        let (cmd, arg) = meta.split_once(' ').map_or((meta, ""), |(c, a)| (c, a.trim()));
+        match (cmd, arg) {
+            ("history", "") => {
+                for (n, line) in self.history.iter().enumerate() {
+                    println!("{:4}  {}", n, line);
+                }
+            },
+            ("def", "") => {
+                for (name, commands) in &self.macros {
+                    println!("{}: {}", name, commands.join("; "));
+                }
+            },
+            ("def", def) => {
+                let (name, body) = def.split_once(' ').unwrap_or((def, ""));
+                let commands = body.split(';').map(str::trim).filter(|c| !c.is_empty())
+                    .map(String::from).collect::<Vec<_>>();
+                if commands.is_empty() {
+                    self.macros.remove(name);
+                } else {
+                    self.macros.insert(name.to_string(), commands);
+                }
+            },
+            ("save", path) if !path.is_empty() => {
+                if let Err(e) = self.terminal.cpu.save_snapshot(path) {
+                    eprintln!("{}", e);
+                }
+            },
+            ("load", path) if !path.is_empty() => match Computer::load_snapshot(path) {
+                Ok(computer) => {
+                    self.terminal = Terminal::new(computer);
+                    self.flush();
+                },
+                Err(e) => eprintln!("{}", e),
+            },
+            ("q", "") | ("quit", "") => return false,
+            ("h", "") | ("help", "") => println!("{}", HELP),
+            _ => eprintln!("unknown command, try :help"),
+        }
+        true
+    }
+}
+
+fn main() {
+    let mut program_path = None;
+    let mut replay_path = None;
+    let mut record_path = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-r" => replay_path = Some(args.next().expect("transcript to replay")),
+            "-o" => record_path = Some(args.next().expect("transcript to record")),
+            _ => program_path = Some(arg),
+        }
+    }
+    let text = fs::read_to_string(program_path.expect("usage: play [-r replay] [-o record] program")).unwrap();
+    let computer = if intcode::is_snapshot(&text) {
+        Computer::from_snapshot(&text).unwrap()
+    } else {
+        Computer::new(&intcode::parse_program(&text))
+    };
+
+    let mut session = Session {
+        terminal: Terminal::new(computer),
+        history: Vec::new(),
+        macros: BTreeMap::new(),
+        record: record_path.map(|path| File::create(path).unwrap()),
+    };
+    match replay_path {
+        Some(path) => {
+            let entries = parse_transcript(&fs::read_to_string(path).unwrap()).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
+            if !session.replay(&entries) {
+                eprintln!("# carrying on anyway");
+            }
+        },
+        None => { session.flush(); },
+    }
+
+    let stdin = io::stdin();
+    let interactive = stdin.is_terminal();
+    let mut lines = stdin.lock().lines();
+    while !session.terminal.halted() {
+        if interactive {
+            print!("> ");
+            io::stdout().flush().unwrap();
+        }
+        match lines.next() {
+            Some(line) => {
+                if !session.command(&line.unwrap()) {
+                    break;
+                }
+            },
+            None => break,
+        }
+    }
+    if session.terminal.halted() {
+        eprintln!("# the program halted");
+    }
+}
@@ -0,0 +1,250 @@
+// Boolean expressions over the hull sensors, like "!(A & B & C) & D", compiled to springscript
+// that ends with the jump decision in J. Springscript has only the T and J registers, so an
+// expression with two complicated sides of an and/or may not fit; those and programs over the
+// length limit are errors. The synthesis side looks for the smallest expression that agrees with
+// a table of hull patterns and whether to jump there.
+use std::collections::HashSet;
+use std::fmt;
+
+const MAX_INSTRUCTIONS: usize = 15;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Walk,
+    Run,
+}
+
+impl Mode {
+    fn sensors(self) -> usize {
+        match self {
+            Mode::Walk => 4,
+            Mode::Run => 9,
+        }
+    }
+
+    fn command(self) -> &'static str {
+        match self {
+            Mode::Walk => "WALK",
+            Mode::Run => "RUN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    // 0 for A, 1 for B and so on
+    Sensor(usize),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+use Expr::*;
+
+fn sensor_name(s: usize) -> char {
+    (b'A' + s as u8) as char
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // or binds loosest, so it's the only thing that needs parens inside an and
+        match self {
+            &Sensor(s) => write!(f, "{}", sensor_name(s)),
+            Not(e) => match **e {
+                Sensor(_) | Not(_) => write!(f, "!{}", e),
+                _ => write!(f, "!({})", e),
+            },
+            And(a, b) => {
+                let side = |e: &Expr, f: &mut fmt::Formatter| match e {
+                    Or(..) => write!(f, "({})", e),
+                    _ => write!(f, "{}", e),
+                };
+                side(a, f)?;
+                write!(f, " & ")?;
+                side(b, f)
+            },
+            Or(a, b) => write!(f, "{} | {}", a, b),
+        }
+    }
+}
+
+// or := and ('|' and)*, and := unary ('&' unary)*, unary := '!' unary | '(' or ')' | sensor
+pub fn parse(src: &str) -> Result<Expr, String> {
+    let tokens = src.chars().filter(|c| !c.is_whitespace()).collect::<Vec<_>>();
+    let mut pos = 0;
+    let expr = parse_or(&tokens, &mut pos)?;
+    if pos != tokens.len() {
+        return Err(format!("junk at {} in {:?}", pos, src));
+    }
+    Ok(expr)
+}
+
+fn parse_or(tokens: &[char], pos: &mut usize) -> Result<Expr, String> {
+    let mut expr = parse_and(tokens, pos)?;
+    while tokens.get(*pos) == Some(&'|') {
+        *pos += 1;
+        expr = Or(Box::new(expr), Box::new(parse_and(tokens, pos)?));
+    }
+    Ok(expr)
+}
+
+fn parse_and(tokens: &[char], pos: &mut usize) -> Result<Expr, String> {
+    let mut expr = parse_unary(tokens, pos)?;
+    while tokens.get(*pos) == Some(&'&') {
+        *pos += 1;
+        expr = And(Box::new(expr), Box::new(parse_unary(tokens, pos)?));
+    }
+    Ok(expr)
+}
+
+fn parse_unary(tokens: &[char], pos: &mut usize) -> Result<Expr, String> {
+    let c = *tokens.get(*pos).ok_or("unexpected end")?;
+    *pos += 1;
+    match c {
+        '!' => Ok(Not(Box::new(parse_unary(tokens, pos)?))),
+        '(' => {
+            let expr = parse_or(tokens, pos)?;
+            if tokens.get(*pos) != Some(&')') {
+                return Err(format!("missing ) at {}", pos));
+            }
+            *pos += 1;
+            Ok(expr)
+        },
+        'A' ..= 'I' => Ok(Sensor((c as u8 - b'A') as usize)),
+        _ => Err(format!("unexpected {:?} at {}", c, *pos - 1)),
+    }
+}
+
+fn max_sensor(e: &Expr) -> usize {
+    match e {
+        &Sensor(s) => s,
+        Not(e) => max_sensor(e),
+        And(a, b) | Or(a, b) => max_sensor(a).max(max_sensor(b)),
+    }
+}
+
+// registers it takes to get e into one; a sensor as the right side of and/or is free
+fn need(e: &Expr) -> usize {
+    let ordered = |first: &Expr, second: &Expr| match second {
+        Sensor(_) => need(first),
+        _ => need(first).max(1 + need(second)),
+    };
+    match e {
+        Sensor(_) => 1,
+        Not(e) => need(e),
+        And(a, b) | Or(a, b) => ordered(a, b).min(ordered(b, a)),
+    }
+}
+
+// the cheaper way around for a commutative op, the one to compute first first
+fn order<'a>(a: &'a Expr, b: &'a Expr) -> (&'a Expr, &'a Expr) {
+    let cost = |first: &Expr, second: &Expr| match second {
+        Sensor(_) => need(first),
+        _ => need(first).max(1 + need(second)),
+    };
+    if cost(a, b) <= cost(b, a) { (a, b) } else { (b, a) }
+}
+
+// puts e in dst, scribbling over spare if there is one
+fn gen(e: &Expr, dst: char, spare: Option<char>, out: &mut Vec<String>) -> Result<(), String> {
+    match e {
+        &Sensor(s) => {
+            out.push(format!("NOT {} {}", sensor_name(s), dst));
+            out.push(format!("NOT {} {}", dst, dst));
+        },
+        Not(inner) => match **inner {
+            Sensor(s) => out.push(format!("NOT {} {}", sensor_name(s), dst)),
+            Not(ref twice) => gen(twice, dst, spare, out)?,
+            _ => {
+                gen(inner, dst, spare, out)?;
+                out.push(format!("NOT {} {}", dst, dst));
+            },
+        },
+        And(a, b) | Or(a, b) => {
+            let op = if let And(..) = e { "AND" } else { "OR" };
+            let (first, second) = order(a, b);
+            gen(first, dst, spare, out)?;
+            match *second {
+                Sensor(s) => out.push(format!("{} {} {}", op, sensor_name(s), dst)),
+                _ => {
+                    let spare = spare.ok_or_else(|| format!("{} needs more than the two registers", e))?;
+                    gen(second, spare, None, out)?;
+                    out.push(format!("{} {} {}", op, spare, dst));
+                },
+            }
+        },
+    }
+    Ok(())
+}
+
+// the whole program, WALK or RUN included
+pub fn compile(expr: &Expr, mode: Mode) -> Result<String, String> {
+    if max_sensor(expr) >= mode.sensors() {
+        return Err(format!("{} can't see {} when {:?}", expr, sensor_name(max_sensor(expr)), mode));
+    }
+    let mut program = Vec::new();
+    gen(expr, 'J', Some('T'), &mut program)?;
+    if program.len() > MAX_INSTRUCTIONS {
+        return Err(format!("{} takes {} instructions, over {}", expr, program.len(), MAX_INSTRUCTIONS));
+    }
+    program.push(mode.command().to_string());
+    Ok(program.join("\n") + "\n")
+}
+
+// a pattern is what the sensors see from A on, '#' for hull and '.' for a hole
+fn pattern_bits(pattern: &str) -> Vec<bool> {
+    pattern.chars().map(|c| c == '#').collect()
+}
+
+fn eval(e: &Expr, sensors: &[bool]) -> bool {
+    match e {
+        &Sensor(s) => sensors[s],
+        Not(e) => !eval(e, sensors),
+        And(a, b) => eval(a, sensors) && eval(b, sensors),
+        Or(a, b) => eval(a, sensors) || eval(b, sensors),
+    }
+}
+
+// The smallest expression by node count that jumps exactly where the table says so, and that
+// compiles. Patterns that aren't in the table are free either way. Expressions are built bottom
+// up by size, and of those that behave the same on the table only the first is kept.
+pub fn synthesize(table: &[(&str, bool)], mode: Mode, max_size: usize) -> Option<Expr> {
+    let rows = table.iter().map(|&(pattern, _)| pattern_bits(pattern)).collect::<Vec<_>>();
+    let target = table.iter().map(|&(_, jump)| jump).collect::<Vec<_>>();
+    let sensors = rows.iter().map(|r| r.len()).max()?.min(mode.sensors());
+
+    let mut seen = HashSet::new();
+    let mut by_size: Vec<Vec<(Vec<bool>, Expr)>> = vec![Vec::new()];
+    for size in 1..=max_size {
+        let mut candidates = Vec::new();
+        if size == 1 {
+            candidates.extend((0..sensors).map(Sensor));
+        } else {
+            candidates.extend(by_size[size - 1].iter().map(|(_, e)| Not(Box::new(e.clone()))));
+            for left in 1..size - 1 {
+                let right = size - 1 - left;
+                if left > right {
+                    break;
+                }
+                for (_, a) in &by_size[left] {
+                    for (_, b) in &by_size[right] {
+                        candidates.push(And(Box::new(a.clone()), Box::new(b.clone())));
+                        candidates.push(Or(Box::new(a.clone()), Box::new(b.clone())));
+                    }
+                }
+            }
+        }
+
+        let mut level = Vec::new();
+        for e in candidates {
+            let behavior = rows.iter().map(|r| eval(&e, r)).collect::<Vec<_>>();
+            if behavior == target && compile(&e, mode).is_ok() {
+                return Some(e);
+            }
+            if seen.insert(behavior.clone()) {
+                level.push((behavior, e));
+            }
+        }
+        by_size.push(level);
+    }
+    None
+}
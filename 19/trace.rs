@@ -0,0 +1,112 @@
+// Runs a program on recorded inputs and notes where it actually went: each executed instruction,
+// each transfer from one instruction to the next, and each write into memory that the program
+// came with. The disassembler uses it to fill in what it can't see statically, like return
+// addresses, computed jumps and instructions the program rewrites for itself.
+use crate::asm;
+use crate::intcode::{self, Computer, Step};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+// enough for all the 2019 programs on real inputs; something that loops forever gets cut here
+const STEP_LIMIT: usize = 10_000_000;
+
+pub struct Trace {
+    // instruction address -> its size when it ran
+    pub executed: BTreeMap<usize, usize>,
+    // the words of each instruction the first time it ran
+    pub ran: BTreeMap<usize, Vec<i64>>,
+    // (from, to) instruction addresses, fallthroughs included
+    pub flow: HashSet<(usize, usize)>,
+    // address in the original program -> the instructions that wrote there
+    pub writes: BTreeMap<usize, BTreeSet<usize>>,
+    pub steps: usize,
+    pub end: &'static str,
+}
+
+impl Trace {
+    // the program with the instructions that only appeared at runtime written in, so that the
+    // disassembler can show them; what decodes already stays as it is
+    pub fn patched(&self, program: &[i64]) -> Vec<i64> {
+        let mut image = program.to_vec();
+        for (&ip, words) in &self.ran {
+            if ip + words.len() <= image.len() && asm::disassemble(program, ip).is_none() {
+                image[ip..ip + words.len()].copy_from_slice(words);
+            }
+        }
+        image
+    }
+}
+
+// numbers separated by commas or whitespace, or else ascii text like the springscript and
+// movement routine puzzles take
+pub fn parse_inputs(text: &str) -> Vec<i64> {
+    let numbers = text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.parse())
+        .collect::<Result<Vec<i64>, _>>();
+    numbers.unwrap_or_else(|_| text.bytes().map(|b| b as i64).collect())
+}
+
+// where the instruction at ip will write, if it writes
+fn dest_addr(computer: &Computer) -> Option<usize> {
+    let ip = computer.ip();
+    let (opcode, modes) = intcode::decode(computer.read(ip))?;
+    let n = match opcode {
+        1 | 2 | 7 | 8 => 2,
+        3 => 0,
+        _ => return None,
+    };
+    let raw = computer.read(ip + 1 + n);
+    match modes[n] {
+        intcode::Mode::Position => Some(raw as usize),
+        intcode::Mode::Relative => Some((raw + computer.base()) as usize),
+        intcode::Mode::Immediate => None,
+    }
+}
+
+pub fn trace(program: &[i64], inputs: &[i64]) -> Trace {
+    let mut computer = Computer::new(program);
+    let mut input = inputs.iter().copied();
+    let mut trace = Trace {
+        executed: BTreeMap::new(),
+        ran: BTreeMap::new(),
+        flow: HashSet::new(),
+        writes: BTreeMap::new(),
+        steps: 0,
+        end: "step limit",
+    };
+
+    while trace.steps < STEP_LIMIT {
+        let ip = computer.ip();
+        let dest = dest_addr(&computer);
+        let size = intcode::decode(computer.read(ip))
+            .and_then(|(opcode, _)| intcode::instruction_size(opcode));
+        match size {
+            Some(size) => {
+                trace.executed.insert(ip, size);
+                trace.ran.entry(ip).or_insert_with(|| (ip..ip + size).map(|addr| computer.read(addr)).collect());
+            },
+            None => {
+                trace.end = "bad instruction";
+                break;
+            },
+        }
+        match computer.step_with(&mut input) {
+            Step::NeedInput => {
+                trace.end = "out of input";
+                break;
+            },
+            Step::Halted => {
+                trace.end = "halted";
+                break;
+            },
+            _ => (),
+        }
+        trace.steps += 1;
+        trace.flow.insert((ip, computer.ip()));
+        if let Some(addr) = dest.filter(|&addr| addr < program.len()) {
+            trace.writes.entry(addr).or_insert_with(BTreeSet::new).insert(ip);
+        }
+    }
+
+    trace
+}
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn accounting(expenses: &[u32]) -> u32 {
@@ -16,9 +18,10 @@ fn three_entry_accounting(expenses: &[u32]) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let expense_report: Vec<u32> = io::stdin().lock().lines()
         .map(|cashline| cashline.unwrap().parse().unwrap())
         .collect();
-    println!("{}", accounting(&expense_report));
-    println!("{}", three_entry_accounting(&expense_report));
+    answer::part1(accounting(&expense_report));
+    answer::part2(three_entry_accounting(&expense_report));
 }
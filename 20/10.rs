@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn use_adapters(adapters: &[u32]) -> (u32, u32) {
@@ -46,16 +48,18 @@ fn ways_to_arrange(adapters: &[u32], i: usize, ways: &mut [Option<usize>]) -> us
 }
 
 fn main() {
+    let _session = answer::start();
     let mut adapters: Vec<u32> = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse().unwrap())
         .collect();
     adapters.sort_unstable();
     let one_three = use_adapters(&adapters);
-    println!("{} {} {}", one_three.0, one_three.1, one_three.0 * one_three.1);
+    println!("{} {}", one_three.0, one_three.1);
+    answer::part1(one_three.0 * one_three.1);
 
     // avoid special case with the arrangement, could have this for the part 1 too though...
     adapters.insert(0, 0);
     // no need to add the device for this, by definition it's reachable only from the last so it
     // would not contribute to path count
-    println!("{}", ways_to_arrange(&adapters, 0, &mut vec![None; adapters.len()]));
+    answer::part2(ways_to_arrange(&adapters, 0, &mut vec![None; adapters.len()]));
 }
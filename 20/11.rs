@@ -1,98 +1,43 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{Map, Pos, DIRS8, add};
+#[path = "../lib/life.rs"]
+mod life;
+use life::Life;
 use std::io::{self, BufRead};
 
+const FLOOR: u8 = b'.';
 const EMPTY: u8 = b'L';
 const OCCUPIED: u8 = b'#';
 
-type Map = Vec<Vec<u8>>;
-
-fn w(map: &Map) -> usize {
-    map[0].len()
-}
-
-fn h(map: &Map) -> usize {
-    map.len()
-}
-
-fn rule1(map: &Map, x: i32, y: i32) -> u8 {
-    let mut occupied_adjacent = 0;
-    for &(nx, ny) in &[
-        (x - 1, y - 1),
-        (x    , y - 1),
-        (x + 1, y - 1),
-        (x - 1, y    ),
-        (x + 1, y    ),
-        (x - 1, y + 1),
-        (x    , y + 1),
-        (x + 1, y + 1),
-    ] {
-        if let Some(&thing) = map.get(ny as usize).and_then(|row| row.get(nx as usize)) {
-            if thing == OCCUPIED {
-                occupied_adjacent += 1;
-            }
-        }
-    }
-
-    match map[y as usize][x as usize] {
+fn rule(seat: u8, around: &[u8], tolerance: usize) -> u8 {
+    let occupied_adjacent = around.iter().filter(|&&thing| thing == OCCUPIED).count();
+    match seat {
         EMPTY if occupied_adjacent == 0 => OCCUPIED,
-        OCCUPIED if occupied_adjacent >= 4 => EMPTY,
+        OCCUPIED if occupied_adjacent >= tolerance => EMPTY,
         x => x
     }
 }
 
-fn rule2(map: &Map, x: i32, y: i32) -> u8 {
-    let mut occupied_adjacent = 0;
-    for &(dx, dy) in &[
-        ( - 1,   - 1),
-        (   0,   - 1),
-        (   1,   - 1),
-        ( - 1,     0),
-        (   1,     0),
-        ( - 1,     1),
-        (   0,     1),
-        (   1,     1),
-    ] {
-        let mut nx = x;
-        let mut ny = y;
-        loop {
-            nx += dx;
-            ny += dy;
-            if let Some(&thing) = map.get(ny as usize).and_then(|row| row.get(nx as usize)) {
-                if thing == OCCUPIED {
-                    occupied_adjacent += 1;
-                    break;
-                } else if thing == EMPTY {
-                    // can't see further
-                    break;
-                }
-            } else {
-                break;
-            }
+// the first seat in each direction; the floor never changes so these don't either
+fn visible_seats(map: &Map<u8>, pos: Pos) -> Vec<Pos> {
+    DIRS8.iter().filter_map(|&d| {
+        let mut p = add(pos, d);
+        while map.get(p) == Some(FLOOR) {
+            p = add(p, d);
         }
-    }
-
-    match map[y as usize][x as usize] {
-        EMPTY if occupied_adjacent == 0 => OCCUPIED,
-        OCCUPIED if occupied_adjacent >= 5 => EMPTY,
-        x => x
-    }
-}
-
-fn simulate(map: &Map, rule: fn(&Map, i32, i32) -> u8) -> Map {
-    let mut new_map = vec![vec![b'?'; w(map)]; h(map)];
-    for y in 0..h(map) {
-        for x in 0..w(map) {
-            new_map[y][x] = rule(map, x as i32, y as i32);
-        }
-    }
-    new_map
+        map.contains(p).then(|| p)
+    }).collect()
 }
 
-fn num_seated(map: &Map) -> usize {
-    map.iter().map(|row| row.iter().filter(|&&x| x == OCCUPIED).count()).sum()
+fn num_seated(map: &Map<u8>) -> usize {
+    map.find_all(OCCUPIED).count()
 }
 
-fn dump(map: &Map) {
-    for row in map {
+fn dump(map: &Map<u8>) {
+    for row in &map.0 {
         for &ch in row {
             print!("{}", ch as char);
         }
@@ -101,24 +46,26 @@ fn dump(map: &Map) {
     println!();
 }
 
-fn stable_state_seated(mut map: Map, rule: fn(&Map, i32, i32) -> u8) -> usize {
-    let mut next;
+fn stable_state_seated<I>(map: &Map<u8>, neighbors: impl Fn(Pos) -> I, tolerance: usize) -> usize
+where I: IntoIterator<Item = Pos> {
+    // outside the map counts as floor
+    let mut life = Life::new(map.clone(), FLOOR, neighbors).tracking();
     loop {
         if false {
-            dump(&map);
+            dump(&life.world);
         }
-        next = simulate(&map, rule);
-        if next == map {
-            return num_seated(&map);
+        if life.step(|_, seat, around| rule(seat, around, tolerance)) == 0 {
+            return num_seated(&life.world);
         }
-        map = next;
     }
 }
 
 fn main() {
-    let map: Map = io::stdin().lock().lines()
+    let _session = answer::start();
+    let map = Map::new(io::stdin().lock().lines()
         .map(|line| line.unwrap().into_bytes())
-        .collect();
-    println!("{}", stable_state_seated(map.clone(), rule1));
-    println!("{}", stable_state_seated(map, rule2));
+        .collect());
+    answer::part1(stable_state_seated(&map, |p| DIRS8.into_iter().map(move |d| add(p, d)), 4));
+    let visible = map.map(|p, _| visible_seats(&map, p));
+    answer::part2(stable_state_seated(&map, |p| visible[p].clone(), 5));
 }
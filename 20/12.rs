@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::str::FromStr;
 
@@ -93,13 +95,16 @@ fn execute_with_wp(instructions: &[Instruction]) -> (i32, i32) {
 }
 
 fn main() {
+    let _session = answer::start();
     let program: Vec<Instruction> = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse().unwrap())
         .collect();
 
     let endpos = execute(&program);
-    println!("{:?} {}", endpos, endpos.0.abs() + endpos.1.abs());
+    println!("{:?}", endpos);
+    answer::part1(endpos.0.abs() + endpos.1.abs());
 
     let endpos = execute_with_wp(&program);
-    println!("{:?} {}", endpos, endpos.0.abs() + endpos.1.abs());
+    println!("{:?}", endpos);
+    answer::part2(endpos.0.abs() + endpos.1.abs());
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn next_bus(timestamp: u64, schedule: &[Option<u64>]) -> (u64, u64) {
@@ -35,6 +37,7 @@ fn bus_race(schedule: &[Option<u64>]) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let stdin = io::stdin();
     // aaargh
     let timestamp = stdin.lock().lines().next().unwrap().unwrap().parse::<u64>().unwrap();
@@ -43,6 +46,7 @@ fn main() {
     let schedule: Vec<Option<_>> = schedule.split(",").map(|b| b.parse::<u64>().ok()).collect();
 
     let bus = next_bus(timestamp, &schedule);
-    println!("{} minutes for bus {} = {}", bus.0, bus.1, bus.0 * bus.1);
-    println!("{}", bus_race(&schedule));
+    println!("{} minutes for bus {}", bus.0, bus.1);
+    answer::part1(bus.0 * bus.1);
+    answer::part2(bus_race(&schedule));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -108,9 +110,10 @@ fn execute_docking_v2(program: &[Instruction]) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let program: Vec<Instruction> = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse().unwrap())
         .collect();
-    println!("{}", execute_docking(&program));
-    println!("{}", execute_docking_v2(&program));
+    answer::part1(execute_docking(&program));
+    answer::part2(execute_docking_v2(&program));
 }
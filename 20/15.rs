@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
@@ -35,8 +37,9 @@ fn nth_number(start: &[usize], n: usize, trace: bool) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let start: Vec<usize> = io::stdin().lock().lines().next().unwrap().unwrap()
         .split(',').map(|n| n.parse().unwrap()).collect();
-    println!("{}", nth_number(&start, 2020, false));
-    println!("{}", nth_number(&start, 30000000, false));
+    answer::part1(nth_number(&start, 2020, false));
+    answer::part2(nth_number(&start, 30000000, false));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 #[derive(Debug)]
@@ -139,6 +141,7 @@ fn parse_rule(line: &str) -> Rule {
 }
 
 fn main() {
+    let _session = answer::start();
     let notes: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
@@ -150,10 +153,10 @@ fn main() {
     let nearby_tickets: Vec<Ticket> = sections.next().unwrap().iter().skip(1).map(|x| {
         x.split(',').map(|x| x.parse().unwrap()).collect::<Vec<_>>()
     }).collect();
-    println!("{}", invalid_fields(&nearby_tickets, &rules));
+    answer::part1(invalid_fields(&nearby_tickets, &rules));
     let fixed_tickets: Vec<Ticket> = nearby_tickets.into_iter().filter(|ticket| {
         ticket.iter().all(|&field| validate_field(field, &rules))
     }).collect();
     let mapping = resolve_field_mapping(&fixed_tickets, &rules);
-    println!("{}", departure_product(&own_ticket, &rules, &mapping));
+    answer::part2(departure_product(&own_ticket, &rules, &mapping));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn calc(stream: &[u8], operator_precedence: bool) -> (u64, usize) {
@@ -79,9 +81,10 @@ fn evaluate2(expression: &str) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let expressions: Vec<String> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
-    println!("{}", expressions.iter().map(|e| evaluate(e)).sum::<u64>());
-    println!("{}", expressions.iter().map(|e| evaluate2(e)).sum::<u64>());
+    answer::part1(expressions.iter().map(|e| evaluate(e)).sum::<u64>());
+    answer::part2(expressions.iter().map(|e| evaluate2(e)).sum::<u64>());
 }
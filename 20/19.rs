@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 extern crate regex;
@@ -191,6 +193,7 @@ fn parse_rule(input: &str) -> (usize, Rule) {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
@@ -207,7 +210,7 @@ fn main() {
     // now that they exist, do both algorithms to cross-check
     let regexed = build_total_regex(&rules);
     let good_message_count = messages.iter().filter(|m| regexed.is_match(m)).count();
-    println!("{}", good_message_count);
+    answer::part1(good_message_count);
     let good_message_count = messages.iter().filter(|m| message_matches(&rules, m)).count();
     println!("{}", good_message_count);
 
@@ -216,7 +219,7 @@ fn main() {
     fix_rules_native(&mut rules_native);
     let regexed = build_total_regex(&rules);
     let good_message_count = messages.iter().filter(|m| regexed.is_match(m)).count();
-    println!("{}", good_message_count);
+    answer::part2(good_message_count);
     let good_message_count = messages.iter().filter(|m| message_matches(&rules_native, m)).count();
     println!("{}", good_message_count);
 }
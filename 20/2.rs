@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 extern crate regex;
@@ -32,9 +34,10 @@ fn officially_validate_pw((policy, pw): &&(CorporatePolicy, String)) -> bool {
 }
 
 fn main() {
+    let _session = answer::start();
     let database: Vec<_> = io::stdin().lock().lines()
         .map(|pwline| parse_pwline(&pwline.unwrap()))
         .collect();
-    println!("{}", database.iter().filter(validate_pw).count());
-    println!("{}", database.iter().filter(officially_validate_pw).count());
+    answer::part1(database.iter().filter(validate_pw).count());
+    answer::part2(database.iter().filter(officially_validate_pw).count());
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -378,6 +380,7 @@ fn parse_tile(input: &[String]) -> (Tile, Image) {
 // note: my input has 144 tiles - that would be 12*12, or 9*16, or 8*18, or 6*24, etc. but the
 // specs hint that the final arrangement will be square
 fn main() {
+    let _session = answer::start();
     assert!(flipbits(1) == (1 << 9));
     assert!(flipbits(1 << 9) == (1));
     assert!(flipbits(0x1e) == (0x0f << 5));
@@ -408,7 +411,7 @@ fn main() {
         state.at(0,       dim - 1).unwrap().name as u64,
         state.at(dim - 1, dim - 1).unwrap().name as u64,
     ];
-    println!("{}", corners[0] * corners[1] * corners[2] * corners[3]);
+    answer::part1(corners[0] * corners[1] * corners[2] * corners[3]);
 
     // indexed by name for easier lookup
     let tilemap: HashMap<u16, &(Tile, Image)> = tiles.iter().map(|ti| {
@@ -416,5 +419,5 @@ fn main() {
     }).collect();
 
     let sea = form_actual_image(&tilemap, &state);
-    println!("{}", water_roughness(&sea));
+    answer::part2(water_roughness(&sea));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::hash_map::{HashMap, Entry};
 use std::collections::HashSet;
@@ -50,6 +52,7 @@ fn parse_food(line: &str) -> Food {
 }
 
 fn main() {
+    let _session = answer::start();
     let foods: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_food(&line.unwrap()))
         .collect();
@@ -101,7 +104,7 @@ fn main() {
     let safe_occurrences = foods.iter().map(|food| {
         food.ingredients.intersection(&safe_ingredients).count()
     }).sum::<usize>();
-    println!("safe times: {}", safe_occurrences);
+    answer::part1(safe_occurrences);
 
     println!("initially plausible:");
     for a in &plausible_ingred {
@@ -112,7 +115,7 @@ fn main() {
     final_mapping.sort_unstable();
     let ingredient_list: Vec<String> = final_mapping.into_iter().map(|(_k, v)| v).collect();
     let canonical_dangerous_ingredient_list = ingredient_list.join(",");
-    println!("{}", canonical_dangerous_ingredient_list);
+    answer::part2(canonical_dangerous_ingredient_list);
 
     // dtb,zgk,pxr,cqnl,xkclg,xtzh,jpnv,lsvlx
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -65,12 +67,13 @@ fn recursive_combat(mut deck0: Vec<u32>, mut deck1: Vec<u32>) -> (u32, bool) {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let mut decks = lines.split(|x| x == "");
     let deck0: Vec<u32> = decks.next().unwrap().iter().skip(1).map(|x| x.parse().unwrap()).collect();
     let deck1: Vec<u32> = decks.next().unwrap().iter().skip(1).map(|x| x.parse().unwrap()).collect();
-    println!("{}", winning_score(deck0.clone(), deck1.clone()));
-    println!("{}", recursive_combat(deck0, deck1).0);
+    answer::part1(winning_score(deck0.clone(), deck1.clone()));
+    answer::part2(recursive_combat(deck0, deck1).0);
 }
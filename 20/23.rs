@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 // a simple type to minimize accidental messing up with indices vs values
@@ -79,9 +81,10 @@ fn long_game(labeling: &str, n: usize, total_cups: usize) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: String = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse().unwrap())
         .next().unwrap();
-    println!("{}", short_game(&input, 100));
-    println!("{}", long_game(&input, 10_000_000, 1_000_000));
+    answer::part1(short_game(&input, 100));
+    answer::part2(long_game(&input, 10_000_000, 1_000_000));
 }
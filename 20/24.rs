@@ -1,3 +1,11 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::add;
+#[path = "../lib/life.rs"]
+mod life;
+use life::{Life, Sparse, HEX};
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -57,76 +65,33 @@ fn tiles_flipped(directions: &[Vec<Coord>]) -> usize {
     black_tiles_up(&floor)
 }
 
-// could also expand the world by one in each step, can't grow more than that
-fn dimensions(floor: &Floor) -> ((i32, i32), (i32, i32)) {
-    // the world starts at level 0 so all of these are ok to use, in the worst case this would just
-    // be too conservative but wouldn't break anything
-    let (mut x0, mut x1, mut y0, mut y1) = (0, 0, 0, 0);
-    for (&pos, &color) in floor.iter() {
-        if color {
-            x0 = x0.min(pos.0);
-            x1 = x1.max(pos.0);
-            y0 = y0.min(pos.1);
-            y1 = y1.max(pos.1);
-        }
-    }
-    ((x0, x1), (y0, y1))
-}
-
-fn neighbourhood(floor: &Floor, pos: Coord) -> usize {
-    let friends = &[
-        (1, 0),
-        (0, 1),
-        (-1, 1),
-        (-1, 0),
-        (0, -1),
-        (1, -1),
-    ];
-    friends.iter().filter(|f| {
-        floor.get(&(pos.0 + f.0, pos.1 + f.1)) == Some(&true)
-    }).count()
-}
-
-fn update_cell(floor: &Floor, pos: Coord) -> bool {
-    let n = neighbourhood(floor, pos);
-    if floor.get(&pos) == Some(&true) {
-        // was black
+fn update_cell(black: bool, neighbours: &[bool]) -> bool {
+    let n = neighbours.iter().filter(|&&b| b).count();
+    if black {
         !(n == 0 || n > 2)
     } else {
-        // was white
         n == 2
     }
 }
 
-fn animate(floor: &Floor) -> Floor {
-    let dim = dimensions(floor);
-    // this doesn't seem to be very exactly needed; a simple floor.len() would also result in a
-    // nice speedup from no initial capacity.
-    let (w, h) = (dim.0.1 + - dim.0.0 + 2, dim.1.1 + - dim.1.0 + 2);
-    let mut next = Floor::with_capacity((w * h) as usize);
-    for x in (dim.0.0 - 1)..=(dim.0.1 + 1) {
-        for y in (dim.1.0 - 1)..=(dim.1.1 + 1) {
-            let state = update_cell(floor, (x, y));
-            next.insert((x, y), state);
-        }
-    }
-    return next;
-}
-
 fn animated_tiles(directions: &[Vec<Coord>], days: usize) -> usize {
-    let mut floor = flip_tiles(directions);
+    let floor = flip_tiles(directions);
+    // only the black ones; white is the default
+    let black = Sparse::with(floor.into_iter().filter(|&(_, color)| color).map(|(pos, _)| pos), true);
+    let mut life = Life::new(black, false, |pos| HEX.into_iter().map(move |d| add(pos, d))).tracking();
     for _day in 0..days {
-        floor = animate(&floor);
+        life.step(|_, black, neighbours| update_cell(black, neighbours));
     }
-    black_tiles_up(&floor)
+    life.count(true)
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(walk_direction(&parse_direction("esew")), (1, -1));
     assert_eq!(walk_direction(&parse_direction("nwwswee")), (0, 0));
     let directions: Vec<Vec<Coord>> = io::stdin().lock().lines()
         .map(|line| parse_direction(&line.unwrap()))
         .collect();
-    println!("{}", tiles_flipped(&directions));
-    println!("{}", animated_tiles(&directions, 100));
+    answer::part1(tiles_flipped(&directions));
+    answer::part2(animated_tiles(&directions, 100));
 }
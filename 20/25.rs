@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn reverse_engineer_loops(pubkey: u64, divider: u64) -> u64 {
@@ -26,7 +28,8 @@ fn solve(card_pubkey: u64, door_pubkey: u64) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let card_pubkey: u64 = io::stdin().lock().lines().next().unwrap().unwrap().parse().unwrap();
     let door_pubkey: u64 = io::stdin().lock().lines().next().unwrap().unwrap().parse().unwrap();
-    println!("{}", solve(card_pubkey, door_pubkey));
+    answer::part1(solve(card_pubkey, door_pubkey));
 }
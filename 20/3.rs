@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 const TREE: u8 = b'#';
@@ -18,10 +20,11 @@ fn tree_pattern(map: &Map, x_slope: usize, y_slope: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let map: Map = io::stdin().lock().lines()
         .map(|line| line.unwrap().into_bytes())
         .collect();
-    println!("{}", tree_pattern(&map, 3, 1));
+    answer::part1(tree_pattern(&map, 3, 1));
     let slopes = &[
         (1, 1),
         (3, 1),
@@ -31,5 +34,5 @@ fn main() {
     ];
     let tree_product = slopes.iter()
         .fold(1, |prod, &(x, y)| prod * tree_pattern(&map, x, y));
-    println!("{}", tree_product);
+    answer::part2(tree_product);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 
 fn check_four_digits(x: &str, min: u32, max: u32) -> bool {
@@ -65,6 +67,7 @@ fn check_pid(x: &str) -> bool {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut batch_file = String::new();
     io::stdin().read_to_string(&mut batch_file).unwrap();
     batch_file.truncate(batch_file.len() - 1); // strip off last newline
@@ -79,7 +82,7 @@ fn main() {
     let pp_has_field = |pp: &[(&str, &str)], field: &str| pp.iter().any(|&(ppfield, _ppval)| ppfield == field);
     let pp_is_valid = |pp: &[(&str, &str)]| required_fields.iter().all(|&field| pp_has_field(pp, field));
     let valid_passports = passports.iter().filter(|&pp| pp_is_valid(pp)).count();
-    println!("{}", valid_passports);
+    answer::part1(valid_passports);
 
     let field_checks: &[(&str, fn(&str) -> bool)] = &[
         ("byr", check_byr),
@@ -95,5 +98,5 @@ fn main() {
     });
     let pp_is_extra_valid = |pp: &[(&str, &str)]| field_checks.iter().all(|(field, chk)| pp_has_valid_field(pp, field, *chk));
     let extra_valid_passports = passports.iter().filter(|&pp| pp_is_extra_valid(pp)).count();
-    println!("{}", extra_valid_passports);
+    answer::part2(extra_valid_passports);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn bsp(input: &str, one: char) -> usize {
@@ -16,19 +18,20 @@ fn seat_id(boarding_pass: &str) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let boarding_passes: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     // why no coercion with map(seat_id) :(
     let max_seat_id = boarding_passes.iter().map(|p| seat_id(p)).max().unwrap();
-    println!("{}", max_seat_id);
+    answer::part1(max_seat_id);
 
     let mut ids: Vec<_> = boarding_passes.iter().map(|p| seat_id(p)).collect();
     ids.sort();
     for pair in ids.windows(2) {
         // note: our place is never in the front or back by definition
         if pair[1] != pair[0] + 1 {
-            println!("{}", pair[0] + 1);
+            answer::part2(pair[0] + 1);
             // there should be only one, but continue looping to double check
         }
     }
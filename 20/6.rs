@@ -1,7 +1,10 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::collections::HashSet;
 
 fn main() {
+    let _session = answer::start();
     let mut group_questions = String::new();
     io::stdin().read_to_string(&mut group_questions).unwrap();
     group_questions.truncate(group_questions.len() - 1); // strip off last newline
@@ -17,7 +20,7 @@ fn main() {
         }
         anyone_yes.len()
     }).sum();
-    println!("{}", sum_someyes);
+    answer::part1(sum_someyes);
 
     let sum_fullyes: usize = groups.iter().map(|people_results| {
         let mut all_yes: HashSet<_> = people_results[0].chars().collect();
@@ -27,5 +30,5 @@ fn main() {
         }
         all_yes.len()
     }).sum();
-    println!("{}", sum_fullyes);
+    answer::part2(sum_fullyes);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, HashSet};
 
@@ -53,6 +55,7 @@ fn parse_bag(line: &str) -> (String, Vec<(u32, String)>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let bags: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_bag(&line.unwrap()))
         .collect();
@@ -62,9 +65,9 @@ fn main() {
             bag_owners.entry(organ_name).or_insert(Vec::new()).push(owner);
         }
     }
-    println!("{}", eventually_contained(&bag_owners, "shiny gold"));
+    answer::part1(eventually_contained(&bag_owners, "shiny gold"));
 
     let bag_contents: HashMap<&str, &Vec<(u32, String)>> = bags.iter()
         .map(|(bag, contents)| (bag as &str, contents)).collect();
-    println!("{}", bags_inside(&bag_contents, "shiny gold"));
+    answer::part2(bags_inside(&bag_contents, "shiny gold"));
 }
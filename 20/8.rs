@@ -1,6 +1,10 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/regmachine.rs"]
+mod regmachine;
 use std::io::{self, BufRead};
-use std::collections::HashSet;
-use std::str::FromStr;
+
+use regmachine::{Effect, Execute, Machine, Parser, Pc, Stop};
 
 #[derive(Debug, Copy, Clone)]
 enum Instruction {
@@ -10,46 +14,34 @@ enum Instruction {
 }
 use Instruction::*;
 
-#[derive(Debug)]
-struct InstructionParseError;
+fn parser() -> Parser<'static, Instruction> {
+    use regmachine::{alt, num, op};
+    alt(vec![
+        op("acc", num(), Acc),
+        op("jmp", num(), Jmp),
+        op("nop", num(), Nop),
+    ])
+}
 
-impl FromStr for Instruction {
-    type Err = InstructionParseError;
+// the state is just the accumulator
+impl Execute<i32> for Instruction {
+    type Event = ();
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut sp = s.split(' ');
-        let op = sp.next().unwrap();
-        let arg = sp.next().unwrap().parse::<i32>().unwrap();
-        match op {
-            "acc" => Ok(Acc(arg)),
-            "jmp" => Ok(Jmp(arg)),
-            "nop" => Ok(Nop(arg)),
-            _ => Err(InstructionParseError),
+    fn execute(&self, accumulator: &mut i32, _pc: Pc) -> Effect<()> {
+        match *self {
+            Acc(n) => *accumulator += n,
+            Jmp(n) => return Effect::Jump(n as i64),
+            Nop(_) => {},
         }
+        Effect::Next
     }
 }
 
+// true if it ran off the end, false if it got into a loop
 fn execute(program: &[Instruction]) -> (i32, bool) {
-    let mut pc = 0;
-    let mut accumulator = 0;
-    let mut visited = HashSet::new();
-    while pc < program.len() as i32 {
-        if visited.contains(&pc) {
-            return (accumulator, false);
-        }
-        visited.insert(pc);
-
-        match program[pc as usize] {
-            Acc(n) => accumulator += n,
-            Jmp(n) => {
-                pc += n;
-                continue;
-            }
-            Nop(_) => {},
-        }
-        pc += 1;
-    }
-    (accumulator, true)
+    let mut mach = Machine::new(program, 0).detecting_loops();
+    let stop = mach.run();
+    (mach.state, stop == Stop::Halted)
 }
 
 fn execute_fixed_program(program: &[Instruction]) -> i32 {
@@ -71,9 +63,11 @@ fn execute_fixed_program(program: &[Instruction]) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
+    let parser = parser();
     let program: Vec<Instruction> = io::stdin().lock().lines()
-        .map(|line| line.unwrap().parse().unwrap())
+        .map(|line| regmachine::parse_line(&parser, &line.unwrap()))
         .collect();
-    println!("{}", execute(&program).0);
-    println!("{}", execute_fixed_program(&program));
+    answer::part1(execute(&program).0);
+    answer::part2(execute_fixed_program(&program));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::cmp::Ordering;
 
@@ -53,13 +55,17 @@ fn contiguous_sum(input: &[u64], x: u64) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let numbers: Vec<u64> = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse().unwrap())
         .collect();
     let a5 = first_with_bad_property(&numbers, 5);
     let a25 = first_with_bad_property(&numbers, 25);
+    // the sample has a preamble of 5
     println!("{:?}", a5);
-    println!("{:?}", a25);
     println!("{:?}", contiguous_sum(&numbers, a5.unwrap()));
-    println!("{:?}", a25.map(|a25| contiguous_sum(&numbers, a25)));
+    if let Some(a25) = a25 {
+        answer::part1(a25);
+        answer::part2(contiguous_sum(&numbers, a25));
+    }
 }
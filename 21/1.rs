@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn deepenings<I, V>(it: I) -> usize
@@ -16,9 +18,10 @@ fn increase_count_windowed(depths: &[u32]) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let sonar_report: Vec<u32> = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse().unwrap())
         .collect();
-    println!("{}", increase_count(&sonar_report));
-    println!("{}", increase_count_windowed(&sonar_report));
+    answer::part1(increase_count(&sonar_report));
+    answer::part2(increase_count_windowed(&sonar_report));
 }
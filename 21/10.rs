@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn syntax_error_score(input: &str) -> u64 {
@@ -74,9 +76,10 @@ fn incompleteness_winner_score(input: &[String]) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
-    println!("{}", total_syntax_error_score(&lines));
-    println!("{}", incompleteness_winner_score(&lines));
+    answer::part1(total_syntax_error_score(&lines));
+    answer::part2(incompleteness_winner_score(&lines));
 }
@@ -1,42 +1,50 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{Map, Pos, DIRS8, add};
+#[path = "../lib/life.rs"]
+mod life;
+use life::Life;
 use std::io::{self, BufRead};
-use std::collections::HashSet;
 
-fn flash(octos: &mut [Vec<u8>], x: i32, y: i32, flash_set: &mut HashSet::<(i32, i32)>) {
-    if flash_set.contains(&(x, y)) {
-        return;
-    }
-    flash_set.insert((x, y));
-    for &(nx, ny) in [
-        (x - 1, y - 1), (x, y - 1), (x + 1, y - 1),
-        (x - 1, y),                 (x + 1, y),
-        (x - 1, y + 1), (x, y + 1), (x + 1, y + 1),
-    ].iter().filter(|p| p.0 >= 0 && p.0 <= 9 && p.1 >= 0 && p.1 <= 9) {
-        octos[ny as usize][nx as usize] += 1;
-        if octos[ny as usize][nx as usize] > 9 {
-            flash(octos, nx, ny, flash_set);
-        }
-    }
-    octos[y as usize][x as usize] = 0;
+// spent for this step, can't gain energy before the next one
+const FLASHED: u8 = u8::MAX;
+
+fn neighbours(p: Pos) -> impl Iterator<Item = Pos> {
+    DIRS8.into_iter().map(move |d| add(p, d))
 }
 
-fn iterate(octos: &mut [Vec<u8>]) -> usize {
-    octos.iter_mut().for_each(|row| row.iter_mut().for_each(|oct| *oct += 1));
-    let mut flash_set = HashSet::<(i32, i32)>::new();
-    for y in 0..10 {
-        for x in 0..10 {
-            if octos[y as usize][x as usize] > 9 {
-                flash(octos, x, y, &mut flash_set);
+fn charging(octo: u8) -> bool {
+    octo > 9 && octo != FLASHED
+}
+
+fn iterate<N, I>(octos: &mut Life<Pos, u8, Map<u8>, N>) -> usize
+where N: Fn(Pos) -> I, I: IntoIterator<Item = Pos> {
+    octos.touch_all();
+    octos.step(|_, octo, _| octo + 1);
+    // flashes spread until nobody's left over nine; only around the last flashes after the first
+    // round
+    loop {
+        let flashing = octos.step(|_, octo, around| {
+            if octo == FLASHED || charging(octo) {
+                FLASHED
+            } else {
+                octo + around.iter().filter(|&&o| charging(o)).count() as u8
             }
+        });
+        if flashing == 0 {
+            break;
         }
     }
-    for &(x, y) in flash_set.iter() {
-        octos[y as usize][x as usize] = 0;
-    }
-    flash_set.len()
+    let flashes = octos.count(FLASHED);
+    octos.touch_all();
+    octos.step(|_, octo, _| if octo == FLASHED { 0 } else { octo });
+    flashes
 }
 
-fn visualize(octos: &[Vec<u8>]) {
-    for row in octos {
+fn visualize(octos: &Map<u8>) {
+    for row in &octos.0 {
         for &octo in row {
             print!("{}", (b'0' + octo) as char);
         }
@@ -45,24 +53,25 @@ fn visualize(octos: &[Vec<u8>]) {
     println!();
 }
 
-fn total_flashes(octos: &mut [Vec<u8>], iterations: usize) -> (usize, usize) {
+fn total_flashes(octos: Map<u8>, iterations: usize) -> (usize, usize) {
     println!("Before any steps:");
-    visualize(octos);
+    visualize(&octos);
+    // nothing outside the edges
+    let mut octos = Life::new(octos, 0, neighbours).tracking();
     let mut first_sync = None;
     let mut requested_flash_count = 0;
     for step in 1.. {
-        let these_flashes = iterate(octos);
+        let these_flashes = iterate(&mut octos);
         if step <= iterations {
             requested_flash_count += these_flashes;
         }
 
         println!("After step {}:", step);
-        visualize(octos);
+        visualize(&octos.world);
 
-        let sync_attempt = octos[0][0];
-        let syncing = octos.iter()
-            .flat_map(|row| row.iter().copied())
-            .all(|octo| octo == sync_attempt);
+        let sync_attempt = octos.world[(0, 0)];
+        let syncing = octos.world.iter()
+            .all(|(_, octo)| octo == sync_attempt);
 
         if first_sync.is_none() && syncing {
             first_sync = Some(step);
@@ -75,8 +84,11 @@ fn total_flashes(octos: &mut [Vec<u8>], iterations: usize) -> (usize, usize) {
 }
 
 fn main() {
-    let mut octos: Vec<Vec<u8>> = io::stdin().lock().lines()
+    let _session = answer::start();
+    let octos = Map::new(io::stdin().lock().lines()
         .map(|line| line.unwrap().bytes().map(|b| b - b'0').collect())
-        .collect();
-    println!("{:?}", total_flashes(&mut octos, 100));
+        .collect());
+    let (flashes, sync_step) = total_flashes(octos, 100);
+    answer::part1(flashes);
+    answer::part2(sync_step);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 use std::collections::HashMap;
@@ -61,10 +63,11 @@ fn suitable_paths_one_double(sys: &Graph) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let spec: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let sys = Graph::from_spec(&spec);
-    println!("{:?}", suitable_paths(&sys));
-    println!("{:?}", suitable_paths_one_double(&sys));
+    answer::part1(suitable_paths(&sys));
+    answer::part2(suitable_paths_one_double(&sys));
 }
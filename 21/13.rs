@@ -1,4 +1,6 @@
 #![feature(hash_drain_filter)]
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -87,10 +89,12 @@ fn parse_origami(paper: &[String]) -> (HashSet<(i32, i32)>, Vec<(bool, i32)>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let paper: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let (dots, folds) = parse_origami(&paper);
-    println!("{:?}", dots_after_one_fold(dots.clone(), &folds));
+    answer::part1(dots_after_one_fold(dots.clone(), &folds));
+    // part 2 is the code that gets displayed
     fold_fully_and_display(dots, &folds);
 }
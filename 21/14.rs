@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -81,11 +83,12 @@ fn parse_poly(input: &[String]) -> (Vec<char>, HashMap<(char, char), char>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let (template, rules) = parse_poly(&input);
     println!("{:?}", result_quantity_code(template.clone(), &rules, 10));
-    println!("{:?}", result_quantity_code_fast(template.clone(), &rules, 10));
-    println!("{:?}", result_quantity_code_fast(template, &rules, 40));
+    answer::part1(result_quantity_code_fast(template.clone(), &rules, 10));
+    answer::part2(result_quantity_code_fast(template, &rules, 40));
 }
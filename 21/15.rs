@@ -1,35 +1,22 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/search.rs"]
+mod search;
 use std::io::{self, BufRead};
-use std::collections::{HashMap, BinaryHeap};
+use std::collections::HashMap;
 
 fn chiton_dijkstra(map: &HashMap<(i32, i32), i32>, origin: (i32, i32), destination: (i32, i32)) -> i32 {
-    let mut heap: BinaryHeap::<(i32, (i32, i32))> = BinaryHeap::new(); // -dist, coords
-    let mut distances = HashMap::<(i32, i32), i32>::new(); // coord to cost
-    heap.push((0, origin));
-
-    while let Some(current) = heap.pop() {
-        let (dist_i, (xi, yi)) = current;
-        let dist_i = -dist_i;
-
-        let neighs = &[
+    let search = search::dijkstra([origin], |&(xi, yi)| {
+        let neighs = [
             (xi - 1, yi),
             (xi + 1, yi),
             (xi, yi - 1),
             (xi, yi + 1),
         ];
+        neighs.into_iter().filter_map(|pos| map.get(&pos).map(|&risk| (pos, risk as usize)))
+    }, |&pos| pos == destination);
 
-        for &(xj, yj) in neighs {
-            if let Some(dist_ij) = map.get(&(xj, yj)) {
-                let dist_j = dist_i + dist_ij;
-
-                if dist_j < *distances.get(&(xj, yj)).unwrap_or(&std::i32::MAX) {
-                    heap.push((-dist_j, (xj, yj)));
-                    distances.insert((xj, yj), dist_j);
-                }
-            }
-        }
-    }
-
-    *distances.get(&destination).unwrap()
+    search.distance(&destination).unwrap() as i32
 }
 
 fn total_risk(chitons: &[Vec<u8>]) -> i32 {
@@ -70,9 +57,10 @@ fn total_risk_full(chitons: &[Vec<u8>]) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let chitons: Vec<Vec<u8>> = io::stdin().lock().lines()
         .map(|line| line.unwrap().bytes().map(|b| b - b'0').collect())
         .collect();
-    println!("{:?}", total_risk(&chitons));
-    println!("{:?}", total_risk_full(&chitons));
+    answer::part1(total_risk(&chitons));
+    answer::part2(total_risk_full(&chitons));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 const BITS_PER_ELEM: usize = 4;
@@ -173,10 +175,11 @@ fn compute(bits: &[u8]) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let bits: Vec<u8> = io::stdin().lock().lines()
         .next().unwrap().unwrap()
         .chars().map(|b| "0123456789ABCDEF".chars().position(|c| c == b).unwrap() as u8) // or from_str_radix
         .collect();
-    println!("{}", version_sum(&bits));
-    println!("{}", compute(&bits));
+    answer::part1(version_sum(&bits));
+    answer::part2(compute(&bits));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn iterate(yvel0: i32, y0: i32, y1: i32) -> Option<(i32, i32)> {
@@ -70,8 +72,9 @@ fn parse_target_area(line: &str) -> (i32, i32, i32, i32) {
 }
 
 fn main() {
+    let _session = answer::start();
     let target_area = parse_target_area(&io::stdin().lock().lines()
         .next().unwrap().unwrap());
-    println!("{}", highest_shot(target_area));
-    println!("{}", shot_count(target_area));
+    answer::part1(highest_shot(target_area));
+    answer::part2(shot_count(target_area));
 }
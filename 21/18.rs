@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::str::Chars;
 
@@ -204,6 +206,7 @@ fn parse_whole_number(line: &str) -> Number {
 }
 
 fn main() {
+    let _session = answer::start();
     assert_eq!(explosion(parse_whole_number("[[[[[9,8],1],2],3],4]")), parse_whole_number("[[[[0,9],2],3],4]"));
     assert_eq!(explosion(parse_whole_number("[7,[6,[5,[4,[3,2]]]]]")), parse_whole_number("[7,[6,[5,[7,0]]]]"));
     assert_eq!(explosion(parse_whole_number("[[6,[5,[4,[3,2]]]],1]")), parse_whole_number("[[6,[5,[7,0]]],3]"));
@@ -229,6 +232,6 @@ fn main() {
     let numbers: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_whole_number(&line.unwrap()))
         .collect();
-    println!("{:?}", sum_magnitude(&numbers));
-    println!("{:?}", largest_pair_sum_magnitude(&numbers));
+    answer::part1(sum_magnitude(&numbers));
+    answer::part2(largest_pair_sum_magnitude(&numbers));
 }
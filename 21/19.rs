@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -143,10 +145,13 @@ fn parse_reports(reports: &[String]) -> Vec<HashSet<Coord>> {
 }
 
 fn main() {
+    let _session = answer::start();
     // TODO use iterator directly, not vec
     let raw_reports: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let reports = parse_reports(&raw_reports);
-    println!("{:?}", total_beacon_count_and_ocean_span(reports));
+    let (beacons, span) = total_beacon_count_and_ocean_span(reports);
+    answer::part1(beacons);
+    answer::part2(span);
 }
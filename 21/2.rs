@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 struct Sub {
@@ -53,8 +55,11 @@ fn final_scores_aiming(course_plan: &[String]) -> (i32, i32) {
 }
 
 fn main() {
+    let _session = answer::start();
     let course_plan: Vec<String> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
-    println!("{:?}", final_scores_aiming(&course_plan));
+    let (simple, aimed) = final_scores_aiming(&course_plan);
+    answer::part1(simple);
+    answer::part2(aimed);
 }
@@ -1,50 +1,35 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{Pos, add};
+#[path = "../lib/life.rs"]
+mod life;
+use life::{Life, Sparse};
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
-const GROWTH: i32 = 2;
-
 type Image = HashSet<(i32, i32)>;
 
-// FIXME the growing image should be a struct that would hold the extents and bg color
+// the pixel itself is in the middle, and the code reads these like a binary number
+const SQUARE: [Pos; 9] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1,  0), (0,  0), (1,  0),
+    (-1,  1), (0,  1), (1,  1),
+];
 
-fn color(image: &Image, x: i32, y: i32, extents: (i32, i32, i32, i32), bg_color: bool) -> bool {
-    let (minx, maxx, miny, maxy) = extents;
-    if x < minx || x > maxx || y < miny || y > maxy {
-        bg_color
-    } else {
-        image.contains(&(x, y))
-    }
+fn image_code(pixels: &[bool]) -> usize {
+    pixels.iter().fold(0, |result, &lit| result << 1 | (lit as usize))
 }
 
-fn image_code(image: &Image, x: i32, y: i32, extents: (i32, i32, i32, i32), bg_color: bool) -> usize {
-    let coords = [
-        (x - 1, y - 1), (x, y - 1), (x + 1, y - 1),
-        (x - 1, y    ), (x, y    ), (x + 1, y    ),
-        (x - 1, y + 1), (x, y + 1), (x + 1, y + 1),
-    ];
-    coords.iter().fold(0, |result, coords| {
-        result << 1 | (color(image, coords.0, coords.1, extents, bg_color) as usize)
-    })
-}
-
-fn iterate(image: &Image, algo: &[bool], extents: (i32, i32, i32, i32), bg_color: bool) -> Image {
-    let (minx, maxx, miny, maxy) = extents;
-    let mut output = Image::new();
-    for y in miny-GROWTH..=maxy+GROWTH {
-        for x in minx-GROWTH..=maxx+GROWTH {
-            if algo[image_code(image, x, y, extents, bg_color)] {
-                output.insert((x, y));
-            }
-        }
-    }
-    output
-}
-
-fn debugdump(image: &Image, extents: (i32, i32, i32, i32)) {
-    let (minx, maxx, miny, maxy) = extents;
+fn debugdump(image: &Sparse<Pos, bool>) {
+    let minx = image.0.keys().map(|p| p.0).min().unwrap();
+    let maxx = image.0.keys().map(|p| p.0).max().unwrap();
+    let miny = image.0.keys().map(|p| p.1).min().unwrap();
+    let maxy = image.0.keys().map(|p| p.1).max().unwrap();
     for y in miny..=maxy {
         for x in minx..=maxx {
-            let ch = if image.contains(&(x, y)) { '#' } else { '.' };
+            let ch = if image.0.contains_key(&(x, y)) { '#' } else { '.' };
             print!("{}", ch);
         }
         println!();
@@ -52,30 +37,21 @@ fn debugdump(image: &Image, extents: (i32, i32, i32, i32)) {
     println!();
 }
 
-fn pixels_after(mut image: Image, algo: &[bool], iterations: usize) -> usize {
-    let mut minx = image.iter().map(|(x, _)| *x).min().unwrap();
-    let mut maxx = image.iter().map(|(x, _)| *x).max().unwrap();
-    let mut miny = image.iter().map(|(_, y)| *y).min().unwrap();
-    let mut maxy = image.iter().map(|(_, y)| *y).max().unwrap();
+fn pixels_after(image: Image, algo: &[bool], iterations: usize) -> usize {
+    // the infinite void may blink if algo[0] is set
+    let (from_dark, from_lit) = (algo[0], algo[511]);
+    let mut life = Life::new(Sparse::with(image, true), false, |p| SQUARE.into_iter().map(move |d| add(p, d)))
+        .with_evolving_background(move |lit| if lit { from_lit } else { from_dark });
     if false {
-        debugdump(&image, (minx, maxx, miny, maxy));
+        debugdump(&life.world);
     }
-    let mut bg_color = false;
     for _ in 0..iterations {
-        image = iterate(&image, algo, (minx, maxx, miny, maxy), bg_color);
-        minx -= GROWTH;
-        miny -= GROWTH;
-        maxx += GROWTH;
-        maxy += GROWTH;
+        life.step(|_, _, pixels| algo[image_code(pixels)]);
         if false {
-            debugdump(&image, (minx, maxx, miny, maxy));
-        }
-        // blink
-        if algo[0] && !algo[511] {
-            bg_color = !bg_color;
+            debugdump(&life.world);
         }
     }
-    image.len()
+    life.count(true)
 }
 
 fn parse_spec(spec: &[String]) -> (Vec<bool>, Image) {
@@ -96,11 +72,12 @@ fn parse_spec(spec: &[String]) -> (Vec<bool>, Image) {
 }
 
 fn main() {
+    let _session = answer::start();
     // TODO: iterator
     let spec: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let (algo, image) = parse_spec(&spec);
-    println!("{:?}", pixels_after(image.clone(), &algo, 2));
-    println!("{:?}", pixels_after(image, &algo, 50));
+    answer::part1(pixels_after(image.clone(), &algo, 2));
+    answer::part2(pixels_after(image, &algo, 50));
 }
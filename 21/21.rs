@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -91,9 +93,10 @@ fn play_dirac_game(apos: i32, bpos: i32) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let positions: Vec<i32> = io::stdin().lock().lines()
         .map(|line| line.unwrap().split("position: ").skip(1).next().unwrap().parse().unwrap())
         .collect();
-    println!("{:?}", play_game(positions[0], positions[1]));
-    println!("{:?}", play_dirac_game(positions[0], positions[1]));
+    answer::part1(play_game(positions[0], positions[1]));
+    answer::part2(play_dirac_game(positions[0], positions[1]));
 }
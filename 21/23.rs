@@ -1,6 +1,9 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/search.rs"]
+mod search;
 use std::io::{self, BufRead};
-use std::collections::{HashSet, HashMap, BinaryHeap};
-use std::cmp::Reverse;
+use std::collections::{HashSet, HashMap};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 struct Node {
@@ -104,7 +107,6 @@ fn empty_map(extended: bool) -> Graph {
 
 // also the return type of walk_distance()
 type Cost = i32;
-const MAX_COST: Cost = std::i32::MAX;
 
 fn step_energy(pod_name: char) -> Cost {
     match pod_name {
@@ -277,93 +279,70 @@ fn goal_heuristic(state: &State) -> Cost {
     state.pods.iter().map(|p| p.cost_heuristic()).sum::<Cost>()
 }
 
-fn amphipod_dijkstra(world: &Graph, origin: PodList) -> Cost {
-    // estimated total path to goal with heuristic, remaining cost heuristic to goal, realized dist, pod state
-    let mut heap: BinaryHeap::<(Reverse<i32>, Reverse<i32>, Reverse<i32>, State)> = BinaryHeap::new();
-    let mut distances = HashMap::<State, Cost>::new();
-    let origstate = State { pods: origin };
-    distances.insert(origstate.clone(), 0);
-    heap.push((Reverse(goal_heuristic(&origstate)), Reverse(goal_heuristic(&origstate)), Reverse(0), origstate));
-
-    let debug = false;
+// every move that the stubborn rules allow from this state, with the energy it takes
+fn amphipod_moves(world: &Graph, state_i: &State) -> Vec<(State, Cost)> {
+    let mut moves = Vec::new();
+    for (podi, pod) in state_i.pods.iter().enumerate() {
+        // stay put if this is a destination spot
+        // final_looking_spot is needed because a pod might need to jump out to help a
+        // foreigner out first, like D in the example
+        if !hallway_node(pod.node) && own_room(pod.node, pod.name) && final_looking_spot(world, state_i, *pod) {
+            continue;
+        }
 
-    let mut parents = HashMap::<State, State>::new();
-
-    while let Some((Reverse(_goal_heuristic_i), Reverse(cost_heuristic_i), Reverse(dist_i), state_i)) = heap.pop() {
-        if cost_heuristic_i == 0 {
-            if debug {
-                println!("FOUND PATH!");
-                dump_state(world, &state_i.pods);
-                println!("dist of this ^ is {:?}", dist_i);
-                let mut here = &state_i;
-                while let Some(par) = parents.get(&here) {
-                    println!();
-                    dump_state(world, &par.pods);
-                    println!("dist of this ^ is {:?} deltacost is {}", distances.get(&par).unwrap(), distances.get(&here).unwrap() - distances.get(&par).unwrap());
-                    here = &par;
+        // don't move into a node that has something already
+        // (building the entire map every time is not wise though, but the world is small)
+        let pod_neighs = unobstructed_destinations(world, state_i, pod.node);
+
+        for (&next_node, &walk_distance) in pod_neighs.iter() {
+            assert!(!state_i.occupied(next_node));
+            let podj = PodState::new(pod.name, next_node);
+
+            // stubborn rule #2: from hallway to only own room.
+            // note that the source doesn't have to be hallway; a path from a room always
+            // visits a hallway anyway
+            if !hallway_node(next_node) {
+                if own_room(next_node, pod.name)
+                        && safe_looking_room(state_i, next_node, *pod)
+                        && final_looking_spot(world, state_i, podj) {
+                    // okay to enter
+                } else {
+                    // not ours, or someone bothering, or empty gap in between
+                    continue;
                 }
             }
-            return dist_i;
-        }
 
-        for (podi, pod) in state_i.pods.iter().enumerate() {
-            // stay put if this is a destination spot
-            // final_looking_spot is needed because a pod might need to jump out to help a
-            // foreigner out first, like D in the example
-            if !hallway_node(pod.node) && own_room(pod.node, pod.name) && final_looking_spot(world, &state_i, *pod) {
+            // stubborn rule #3: don't move from hallway to hallway
+            if hallway_node(pod.node) && hallway_node(next_node) {
                 continue;
             }
 
-            // don't move into a node that has something already
-            // (building the entire map every time is not wise though, but the world is small)
-            let pod_neighs = unobstructed_destinations(world, &state_i, pod.node);
-
-            for (&next_node, &walk_distance) in pod_neighs.iter() {
-                assert!(!state_i.occupied(next_node));
-                let podj = PodState::new(pod.name, next_node);
-
-                // stubborn rule #2: from hallway to only own room.
-                // note that the source doesn't have to be hallway; a path from a room always
-                // visits a hallway anyway
-                if !hallway_node(next_node) {
-                    if own_room(next_node, pod.name)
-                            && safe_looking_room(&state_i, next_node, *pod)
-                            && final_looking_spot(world, &state_i, podj) {
-                        // okay to enter
-                    } else {
-                        // not ours, or someone bothering, or empty gap in between
-                        continue;
-                    }
-                }
-
-                // stubborn rule #3: don't move from hallway to hallway
-                if hallway_node(pod.node) && hallway_node(next_node) {
-                    continue;
-                }
-
-                let dist_ij = walk_distance * step_energy(pod.name);
-                let dist_j = dist_i + dist_ij;
-                let mut state_j = state_i.clone();
-                state_j.pods[podi] = podj;
-
-                if dist_j < *distances.get(&state_j).unwrap_or(&MAX_COST) {
-                    let cost_heuristic_j = goal_heuristic(&state_j);
-                    let goal_heuristic_j = dist_j + cost_heuristic_j;
-                    heap.push((
-                            Reverse(goal_heuristic_j),
-                            Reverse(cost_heuristic_j),
-                            Reverse(dist_j),
-                            state_j.clone()));
-                    if debug {
-                        parents.insert(state_j.clone(), state_i.clone());
-                    }
-                    distances.insert(state_j, dist_j);
-                }
-            }
+            let mut state_j = state_i.clone();
+            state_j.pods[podi] = podj;
+            moves.push((state_j, walk_distance * step_energy(pod.name)));
         }
     }
+    moves
+}
 
-    panic!("no route to goal");
+fn amphipod_dijkstra(world: &Graph, origin: PodList) -> Cost {
+    let debug = false;
+    let found = search::astar(
+        [State { pods: origin }],
+        |state| amphipod_moves(world, state).into_iter().map(|(s, cost)| (s, cost as search::Cost)),
+        |state| goal_heuristic(state) as search::Cost,
+        |state| goal_heuristic(state) == 0);
+    let goal = found.goal.as_ref().expect("no route to goal");
+
+    if debug {
+        println!("FOUND PATH!");
+        for state in found.path(goal).unwrap().iter().rev() {
+            dump_state(world, &state.pods);
+            println!("dist of this ^ is {:?}", found.dist[state]);
+            println!();
+        }
+    }
+    found.dist[goal] as Cost
 }
 
 fn collect_map(map: &[String], world: &Graph) -> PodList {
@@ -384,11 +363,12 @@ fn organize_amphipods(raw_map: &[String]) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut raw_map: Vec<String> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
-    println!("{:?}", organize_amphipods(&raw_map));
+    answer::part1(organize_amphipods(&raw_map));
     raw_map.insert(3, String::from("  #D#C#B#A#"));
     raw_map.insert(4, String::from("  #D#B#A#C#"));
-    println!("{:?}", organize_amphipods(&raw_map));
+    answer::part2(organize_amphipods(&raw_map));
 }
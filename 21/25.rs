@@ -1,74 +1,58 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::Map;
+#[path = "../lib/life.rs"]
+mod life;
+use life::Life;
 use std::io::{self, BufRead};
 
-type Floor = Vec<Vec<char>>;
-
-fn move_cucumbers(floor: &Floor) -> Floor {
-    let w = floor[0].len();
-    let h = floor.len();
-    let mut next_floor = vec![vec!['.'; w]; h];
-    for (y, row) in floor.iter().enumerate() {
-        for (x, ch) in row.iter().enumerate() {
-            match ch {
-                '.' => (),
-                '>' => {
-                    if floor[y][(x + 1) % w] == '.' {
-                        next_floor[y][(x + 1) % w] = '>';
-                    } else {
-                        next_floor[y][x] = '>';
-                    }
-                },
-                'v' => (),
-                _ => panic!("bad floor")
-            }
-        }
+// both herds move in turn, each one looking at the floor the other just left
+fn move_east(ch: char, around: &[char]) -> char {
+    let (left, right) = (around[0], around[1]);
+    match ch {
+        '>' if right == '.' => '.',
+        '.' if left == '>' => '>',
+        _ => ch,
     }
-    for (y, row) in floor.iter().enumerate() {
-        for (x, ch) in row.iter().enumerate() {
-            match ch {
-                '.' => (),
-                '>' => (),
-                'v' => {
-                    if floor[(y + 1) % h][x] != 'v' && next_floor[(y + 1) % h][x] != '>' {
-                        next_floor[(y + 1) % h][x]= 'v';
-                    } else {
-                        next_floor[y][x] = 'v';
-                    }
-                },
-                _ => panic!("bad floor")
-            }
-        }
-    }
-    next_floor
 }
 
-fn dump(floor: &Floor) {
-    for (_y, row) in floor.iter().enumerate() {
-        for (_x, ch) in row.iter().enumerate() {
-            print!("{}", ch);
-        }
-        println!();
+fn move_south(ch: char, around: &[char]) -> char {
+    let (up, down) = (around[2], around[3]);
+    match ch {
+        'v' if down == '.' => '.',
+        '.' if up == 'v' => 'v',
+        _ => ch,
     }
 }
 
-fn movement_duration(mut floor: Floor) -> usize {
+fn movement_duration(floor: Map) -> usize {
+    let (w, h) = (floor.w(), floor.h());
+    // the sea floor wraps around
+    let mut life = Life::new(floor, '.', |(x, y)| [
+        ((x + w - 1) % w, y),
+        ((x + 1) % w, y),
+        (x, (y + h - 1) % h),
+        (x, (y + 1) % h),
+    ]);
     for i in 0.. {
         if false {
             println!("{}:", i);
-            dump(&floor);
+            print!("{}", life.world);
             println!();
         }
-        let next_floor = move_cucumbers(&floor);
-        if next_floor == floor {
+        let moved = life.step(|_, ch, around| move_east(ch, around))
+            + life.step(|_, ch, around| move_south(ch, around));
+        if moved == 0 {
             return i + 1;
         }
-        floor = next_floor;
     }
     unreachable!();
 }
 
 fn main() {
-    let floor_scan: Vec<Vec<char>> = io::stdin().lock().lines()
-        .map(|line| line.unwrap().chars().collect())
-        .collect();
-    println!("{:?}", movement_duration(floor_scan));
+    let _session = answer::start();
+    let floor_scan = Map::parse(io::stdin().lock().lines().map(|line| line.unwrap()));
+    answer::part1(movement_duration(floor_scan));
 }
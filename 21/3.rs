@@ -1,5 +1,7 @@
 #![feature(iter_partition_in_place)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::ops::{BitAnd, Shl};
 use std::cmp::PartialEq;
@@ -51,6 +53,7 @@ fn life_support(mut diagnostic_report: &mut [u32], length: usize) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut length: usize = 0;
     let mut diagnostic_report: Vec<u32> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
@@ -59,6 +62,6 @@ fn main() {
             u32::from_str_radix(&line, 2).unwrap()
         })
         .collect();
-    println!("{}", power_consumption(&diagnostic_report, length));
-    println!("{}", life_support(&mut diagnostic_report, length));
+    answer::part1(power_consumption(&diagnostic_report, length));
+    answer::part2(life_support(&mut diagnostic_report, length));
 }
@@ -1,6 +1,8 @@
 #![allow(incomplete_features)]
 #![feature(generic_const_exprs)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 #[derive(Clone)]
@@ -86,12 +88,13 @@ fn squid_score(boards: &mut [SquidBoard], drawn_numbers: &[u32]) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<String> = io::stdin().lock().lines().map(|l| l.unwrap()).collect();
     let drawn_numbers: Vec<u32> = lines[0].split(',')
         .map(|num| num.parse().unwrap()).collect();
     let mut boards: Vec<SquidBoard> = lines[1..].chunks(6)
         .map(SquidBoard::from_string_spec)
         .collect();
-    println!("{}", winning_score(&mut boards.clone(), &drawn_numbers));
-    println!("{}", squid_score(&mut boards, &drawn_numbers));
+    answer::part1(winning_score(&mut boards.clone(), &drawn_numbers));
+    answer::part2(squid_score(&mut boards, &drawn_numbers));
 }
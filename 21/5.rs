@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -55,9 +57,10 @@ fn parse_line(input: &str) -> Line {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<Line> = io::stdin().lock().lines()
         .map(|input| parse_line(&input.unwrap()))
         .collect();
-    println!("{}", dangerous_sum(&lines));
-    println!("{}", full_dangerous_sum(&lines));
+    answer::part1(dangerous_sum(&lines));
+    answer::part2(full_dangerous_sum(&lines));
 }
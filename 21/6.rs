@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn fish_population(school: &[u64], sim_length: usize) -> u64 {
@@ -18,10 +20,11 @@ fn fish_population(school: &[u64], sim_length: usize) -> u64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<u64> = io::stdin().lock().lines()
         .next().unwrap().unwrap().split(',')
         .map(|n| n.parse().unwrap())
         .collect();
-    println!("{}", fish_population(&input, 80));
-    println!("{}", fish_population(&input, 256));
+    answer::part1(fish_population(&input, 80));
+    answer::part2(fish_population(&input, 256));
 }
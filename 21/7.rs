@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn min_fuel_search<F>(crabs: &[i32], move_cost: F) -> i32
@@ -30,10 +32,11 @@ fn min_fuel_arith(crabs: &[i32]) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<i32> = io::stdin().lock().lines()
         .next().unwrap().unwrap().split(',')
         .map(|n| n.parse().unwrap())
         .collect();
-    println!("{}", min_fuel(&input));
-    println!("{}", min_fuel_arith(&input));
+    answer::part1(min_fuel(&input));
+    answer::part2(min_fuel_arith(&input));
 }
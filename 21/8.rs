@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -97,9 +99,10 @@ fn parse_line(line: &str) -> (String, String) {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<(String, String)> = io::stdin().lock().lines()
         .map(|input| parse_line(&input.unwrap()))
         .collect();
-    println!("{}", easy_digits_count(&input));
-    println!("{}", output_values(&input));
+    answer::part1(easy_digits_count(&input));
+    answer::part2(output_values(&input));
 }
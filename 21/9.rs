@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 type Map = Vec<Vec<u32>>;
@@ -72,10 +74,11 @@ fn basin_product(map: &Map) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let map: Map = io::stdin().lock().lines()
         .map(|input| input.unwrap().bytes().map(|b| (b - b'0') as u32).collect())
         .collect();
-    println!("{}", low_point_risk_level(&map));
-    println!("{}", basin_product(&map));
+    answer::part1(low_point_risk_level(&map));
+    answer::part2(basin_product(&map));
 }
 
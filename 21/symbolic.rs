@@ -0,0 +1,381 @@
+// Symbolic execution of ALU programs. Each variable holds an expression over the input digits,
+// simplified as it's built with the help of the value range of each part; that's enough to see
+// through the mod/div by 26 that the MONAD uses as a stack. An eql that can go both ways splits
+// the run in two paths, one where it holds and one where it doesn't. The digits are then searched
+// path by path, with the ranges pruning everything that can't end with z == 0.
+use crate::regmachine::Arg;
+use crate::{Instruction, Opcode, VAR_Z};
+use std::fmt;
+use std::rc::Rc;
+
+type Range = (i64, i64);
+
+const DIGITS: Range = (1, 9);
+
+#[derive(Debug, PartialEq)]
+pub enum Expr {
+    Const(i64),
+    // the nth digit of the input
+    Input(usize),
+    Add(Rc<Expr>, Rc<Expr>),
+    Mul(Rc<Expr>, Rc<Expr>),
+    Div(Rc<Expr>, Rc<Expr>),
+    Mod(Rc<Expr>, Rc<Expr>),
+    Eql(Rc<Expr>, Rc<Expr>),
+}
+use Expr::*;
+
+type E = Rc<Expr>;
+
+fn konst(n: i64) -> E {
+    Rc::new(Const(n))
+}
+
+fn as_const(e: &Expr) -> Option<i64> {
+    match *e {
+        Const(n) => Some(n),
+        _ => None,
+    }
+}
+
+// the values that e can have when the first digits are known
+fn range(e: &Expr, digits: &[i64]) -> Range {
+    match e {
+        &Const(n) => (n, n),
+        &Input(i) => digits.get(i).map_or(DIGITS, |&d| (d, d)),
+        Add(a, b) => {
+            let (a, b) = (range(a, digits), range(b, digits));
+            (a.0.saturating_add(b.0), a.1.saturating_add(b.1))
+        },
+        Mul(a, b) => {
+            let (a, b) = (range(a, digits), range(b, digits));
+            let corners = [a.0.saturating_mul(b.0), a.0.saturating_mul(b.1),
+                a.1.saturating_mul(b.0), a.1.saturating_mul(b.1)];
+            (*corners.iter().min().unwrap(), *corners.iter().max().unwrap())
+        },
+        Div(a, b) => match range(b, digits) {
+            // truncating division by a positive number keeps the order
+            (k, k2) if k == k2 && k > 0 => {
+                let a = range(a, digits);
+                (a.0 / k, a.1 / k)
+            },
+            _ => (i64::MIN, i64::MAX),
+        },
+        Mod(a, b) => match range(b, digits) {
+            (k, k2) if k == k2 && k > 0 => match range(a, digits) {
+                a if a.0 >= 0 && a.1 < k => a,
+                a if a.0 >= 0 => (0, k - 1),
+                a if a.1 <= 0 => (-(k - 1), 0),
+                _ => (-(k - 1), k - 1),
+            },
+            _ => (i64::MIN, i64::MAX),
+        },
+        Eql(a, b) => {
+            let (a, b) = (range(a, digits), range(b, digits));
+            if a.0 == a.1 && a == b {
+                (1, 1)
+            } else if a.1 < b.0 || b.1 < a.0 {
+                (0, 0)
+            } else {
+                (0, 1)
+            }
+        },
+    }
+}
+
+fn within(e: &Expr, lo: i64, hi: i64) -> bool {
+    let r = range(e, &[]);
+    r.0 >= lo && r.1 <= hi
+}
+
+// x * k + y with y in [0, k - 1] and x not negative: a stack of base k digits with y on top
+fn split_digit(e: &Expr, k: i64) -> Option<(E, E)> {
+    match e {
+        Add(xk, y) if within(y, 0, k - 1) => match &**xk {
+            Mul(x, kk) if as_const(kk) == Some(k) && within(x, 0, i64::MAX) => Some((x.clone(), y.clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn add(a: E, b: E) -> E {
+    match (&*a, &*b) {
+        (&Const(x), &Const(y)) => konst(x + y),
+        (Const(0), _) => b,
+        (_, Const(0)) => a,
+        (Const(_), _) => add(b, a),
+        // constants stay together on the right
+        (Add(x, c), &Const(d)) if as_const(c).is_some() => add(x.clone(), konst(as_const(c).unwrap() + d)),
+        _ => Rc::new(Add(a, b)),
+    }
+}
+
+fn mul(a: E, b: E) -> E {
+    match (&*a, &*b) {
+        (&Const(x), &Const(y)) => konst(x * y),
+        (Const(0), _) | (_, Const(0)) => konst(0),
+        (Const(1), _) => b,
+        (_, Const(1)) => a,
+        (Const(_), _) => mul(b, a),
+        _ => Rc::new(Mul(a, b)),
+    }
+}
+
+fn div(a: E, b: E) -> E {
+    let k = match as_const(&b) {
+        Some(k) if k > 0 => k,
+        _ => return Rc::new(Div(a, b)),
+    };
+    if let Some(x) = as_const(&a) {
+        return konst(x / k);
+    }
+    if k == 1 {
+        return a;
+    }
+    if within(&a, 0, k - 1) {
+        return konst(0);
+    }
+    if let Some((x, _)) = split_digit(&a, k) {
+        return x;
+    }
+    match &*a {
+        Mul(x, kk) if as_const(kk) == Some(k) => x.clone(),
+        _ => Rc::new(Div(a, b)),
+    }
+}
+
+fn modulo(a: E, b: E) -> E {
+    let k = match as_const(&b) {
+        Some(k) if k > 0 => k,
+        _ => return Rc::new(Mod(a, b)),
+    };
+    if let Some(x) = as_const(&a) {
+        return konst(x % k);
+    }
+    if within(&a, 0, k - 1) {
+        return a;
+    }
+    if let Some((_, y)) = split_digit(&a, k) {
+        return y;
+    }
+    match &*a {
+        Mul(_, kk) if as_const(kk) == Some(k) => konst(0),
+        _ => Rc::new(Mod(a, b)),
+    }
+}
+
+// an Eql node if it can go both ways
+fn eql(a: E, b: E) -> E {
+    if a == b {
+        return konst(1);
+    }
+    match range(&Eql(a.clone(), b.clone()), &[]) {
+        (0, 0) => konst(0),
+        (1, 1) => konst(1),
+        _ => Rc::new(Eql(a, b)),
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // sums need parens inside products and such
+        let operand = |e: &Expr, f: &mut fmt::Formatter| match e {
+            Add(..) => write!(f, "({})", e),
+            _ => write!(f, "{}", e),
+        };
+        let binary = |a: &Expr, op: &str, b: &Expr, f: &mut fmt::Formatter| {
+            operand(a, f)?;
+            write!(f, " {} ", op)?;
+            operand(b, f)
+        };
+        match self {
+            Const(n) => write!(f, "{}", n),
+            Input(i) => write!(f, "d{}", i),
+            Add(a, b) => match as_const(b) {
+                Some(n) if n < 0 => write!(f, "{} - {}", a, -n),
+                _ => write!(f, "{} + {}", a, b),
+            },
+            Mul(a, b) => binary(a, "*", b, f),
+            Div(a, b) => binary(a, "/", b, f),
+            Mod(a, b) => binary(a, "%", b, f),
+            Eql(a, b) => write!(f, "({} == {})", a, b),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Constraint {
+    // how many digits were read when this came up
+    at: usize,
+    a: E,
+    b: E,
+    equal: bool,
+}
+
+impl Constraint {
+    fn feasible(&self, digits: &[i64]) -> bool {
+        let (a, b) = (range(&self.a, digits), range(&self.b, digits));
+        if self.equal {
+            a.0 <= b.1 && b.0 <= a.1
+        } else {
+            !(a.0 == a.1 && a == b)
+        }
+    }
+}
+
+// one way through the program
+#[derive(Clone)]
+pub struct Path {
+    vars: [E; 4],
+    inputs: usize,
+    constraints: Vec<Constraint>,
+    // z each time an input is read
+    z_at_input: Vec<E>,
+}
+
+impl Path {
+    // can the rest of the digits still make it valid
+    fn feasible(&self, digits: &[i64]) -> bool {
+        let (lo, hi) = range(&self.vars[VAR_Z], digits);
+        lo <= 0 && 0 <= hi && self.constraints.iter().all(|c| c.feasible(digits))
+    }
+}
+
+pub fn explore(program: &[Instruction]) -> Vec<Path> {
+    let start = Path {
+        vars: [konst(0), konst(0), konst(0), konst(0)],
+        inputs: 0,
+        constraints: Vec::new(),
+        z_at_input: Vec::new(),
+    };
+    let mut work = vec![(0, start)];
+    let mut paths = Vec::new();
+
+    while let Some((mut pc, mut path)) = work.pop() {
+        while let Some(inst) = program.get(pc) {
+            let a = path.vars[inst.a].clone();
+            let b = match inst.b {
+                Arg::Reg(v) => path.vars[v].clone(),
+                Arg::Num(n) => konst(n),
+            };
+            path.vars[inst.a] = match inst.opcode {
+                Opcode::Inp => {
+                    path.z_at_input.push(path.vars[VAR_Z].clone());
+                    path.inputs += 1;
+                    Rc::new(Input(path.inputs - 1))
+                },
+                Opcode::Add => add(a, b),
+                Opcode::Mul => mul(a, b),
+                Opcode::Div => div(a, b),
+                Opcode::Mod => modulo(a, b),
+                Opcode::Eql => match *eql(a.clone(), b.clone()) {
+                    Eql(..) => {
+                        // this one goes on where it doesn't hold, later
+                        let mut other = path.clone();
+                        other.constraints.push(Constraint { at: path.inputs, a: a.clone(), b: b.clone(), equal: false });
+                        other.vars[inst.a] = konst(0);
+                        work.push((pc + 1, other));
+                        path.constraints.push(Constraint { at: path.inputs, a, b, equal: true });
+                        konst(1)
+                    },
+                    ref decided => konst(as_const(decided).unwrap()),
+                },
+            };
+            pc += 1;
+        }
+        paths.push(path);
+    }
+
+    paths
+}
+
+fn search(path: &Path, digits: &mut Vec<i64>, order: &[i64]) -> bool {
+    if !path.feasible(digits) {
+        return false;
+    }
+    // all known makes the ranges exact
+    if digits.len() == path.inputs {
+        return true;
+    }
+    for &d in order {
+        digits.push(d);
+        if search(path, digits, order) {
+            return true;
+        }
+        digits.pop();
+    }
+    false
+}
+
+// the largest or smallest digits that end with z == 0, and the path that takes
+pub fn solve(paths: &[Path], largest: bool) -> Option<(Vec<i64>, &Path)> {
+    let order = if largest {
+        (DIGITS.0..=DIGITS.1).rev().collect::<Vec<_>>()
+    } else {
+        (DIGITS.0..=DIGITS.1).collect::<Vec<_>>()
+    };
+    let solutions = paths.iter().filter_map(|path| {
+        let mut digits = Vec::new();
+        if search(path, &mut digits, &order) { Some((digits, path)) } else { None }
+    });
+    if largest {
+        solutions.max_by(|a, b| a.0.cmp(&b.0))
+    } else {
+        solutions.min_by(|a, b| a.0.cmp(&b.0))
+    }
+}
+
+// z as a stack of base k digits, bottom first
+fn stack(z: &E, k: i64) -> Vec<E> {
+    if let Some(0) = as_const(z) {
+        return Vec::new();
+    }
+    match split_digit(z, k) {
+        Some((rest, top)) => {
+            let mut s = stack(&rest, k);
+            s.push(top);
+            s
+        },
+        None => vec![z.clone()],
+    }
+}
+
+// the base that z is used as a stack in, if it is
+fn stack_base(path: &Path) -> Option<i64> {
+    fn find(e: &Expr) -> Option<i64> {
+        match e {
+            Add(xk, y) => match &**xk {
+                Mul(_, k) if as_const(k).map_or(false, |k| within(y, 0, k - 1)) => as_const(k),
+                _ => find(xk).or_else(|| find(y)),
+            },
+            Mul(a, b) | Div(a, b) | Mod(a, b) | Eql(a, b) => find(a).or_else(|| find(b)),
+            _ => None,
+        }
+    }
+    path.z_at_input.iter().chain(Some(&path.vars[VAR_Z])).find_map(|z| find(z))
+}
+
+// what each digit does to the stack in z, and what it must satisfy, on this path
+pub fn print_structure(path: &Path) {
+    let k = match stack_base(path) {
+        Some(k) => k,
+        None => {
+            println!("z isn't used as a stack here; final z = {}", path.vars[VAR_Z]);
+            return;
+        },
+    };
+    println!("z is a stack of base {} numbers", k);
+    for i in 0..path.inputs {
+        let before = stack(&path.z_at_input[i], k);
+        let after = stack(path.z_at_input.get(i + 1).unwrap_or(&path.vars[VAR_Z]), k);
+        let common = before.iter().zip(&after).take_while(|(a, b)| a == b).count();
+        let mut ops = Vec::new();
+        ops.extend(before[common..].iter().rev().map(|e| format!("pop {}", e)));
+        ops.extend(after[common..].iter().map(|e| format!("push {}", e)));
+        println!("d{}: {}", i, ops.join("; "));
+        for c in path.constraints.iter().filter(|c| c.at == i + 1) {
+            println!("    {} {} {}", c.a, if c.equal { "==" } else { "!=" }, c.b);
+        }
+    }
+}
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn calorie_totals(calories_spec: &[String]) -> Vec<usize> {
@@ -17,10 +19,11 @@ fn tot_top_three_cals(mut cal_totals: Vec<usize>) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let calories_spec: Vec<String> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let elf_totals = calorie_totals(&calories_spec);
-    println!("{}", max_cals(&elf_totals));
-    println!("{}", tot_top_three_cals(elf_totals));
+    answer::part1(max_cals(&elf_totals));
+    answer::part2(tot_top_three_cals(elf_totals));
 }
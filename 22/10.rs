@@ -1,5 +1,11 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/regmachine.rs"]
+mod regmachine;
 use std::io::{self, BufRead};
 
+use regmachine::{Effect, Execute, Machine, Parser, Pc};
+
 #[derive(Clone, Copy, Debug)]
 enum Instruction {
     Noop,
@@ -7,67 +13,72 @@ enum Instruction {
 }
 use Instruction::*;
 
-struct Machine {
+struct Cpu {
     x: i32,
     pipeline_stage: bool,
 }
 
-fn step_instruction(mach: &mut Machine, inst: Instruction) -> bool {
-    match (inst, mach.pipeline_stage) {
-        ( Noop, _) => (),
-        (Addx(_), false) => mach.pipeline_stage = true,
-        (Addx(x), true) => { mach.x += x; mach.pipeline_stage = false; },
-    };
-    mach.pipeline_stage
+// one cycle per step; addx stays for a second one
+impl Execute<Cpu> for Instruction {
+    type Event = ();
+
+    fn execute(&self, cpu: &mut Cpu, _pc: Pc) -> Effect<()> {
+        match (*self, cpu.pipeline_stage) {
+            (Noop, _) => (),
+            (Addx(_), false) => {
+                cpu.pipeline_stage = true;
+                return Effect::Stay;
+            },
+            (Addx(x), true) => { cpu.x += x; cpu.pipeline_stage = false; },
+        };
+        Effect::Next
+    }
 }
 
 fn execute(program: &[Instruction]) -> (i32, String) {
-    let mut mach = Machine { x: 1, pipeline_stage: false };
+    let mut mach = Machine::new(program, Cpu { x: 1, pipeline_stage: false });
     let mut strength_sum = 0;
     let mut cycle = 1;
     let mut gfx = Vec::<char>::new();
-    for &inst in program.iter() {
-        loop {
-            if cycle <= 120 && cycle == 20 || (cycle - 20) % 40 == 0 {
-                let signal_strength = cycle * mach.x;
-                strength_sum += signal_strength;
-            }
-
-            let xpos = (cycle - 1) % 40;
-            gfx.push(if (xpos - mach.x).abs() <= 1 {
-                '#'
-            } else {
-                '.'
-            });
-            if xpos == 39 {
-                gfx.push('\n');
-            }
+    while !mach.halted() {
+        let x = mach.state.x;
+        if cycle <= 120 && cycle == 20 || (cycle - 20) % 40 == 0 {
+            let signal_strength = cycle * x;
+            strength_sum += signal_strength;
+        }
 
-            let delay = step_instruction(&mut mach, inst);
-            cycle += 1;
-            if !delay {
-                break;
-            }
+        let xpos = (cycle - 1) % 40;
+        gfx.push(if (xpos - x).abs() <= 1 {
+            '#'
+        } else {
+            '.'
+        });
+        if xpos == 39 {
+            gfx.push('\n');
         }
+
+        mach.step();
+        cycle += 1;
     }
 
     (strength_sum, gfx.iter().collect())
 }
 
-fn parse_instruction(input: &str) -> Instruction {
-    let mut sp = input.split(' ');
-    match sp.next().unwrap() {
-        "noop" => Noop,
-        "addx" => Addx(sp.next().unwrap().parse().unwrap()),
-        _ => panic!("bad opcode"),
-    }
+fn parser() -> Parser<'static, Instruction> {
+    use regmachine::{alt, none, num, op};
+    alt(vec![
+        op("noop", none(), |()| Noop),
+        op("addx", num(), Addx),
+    ])
 }
 
 fn main() {
+    let _session = answer::start();
+    let parser = parser();
     let program: Vec<_> = io::stdin().lock().lines()
-        .map(|line| parse_instruction(&line.unwrap()))
+        .map(|line| regmachine::parse_line(&parser, &line.unwrap()))
         .collect();
     let (signal_strength, gfx) = execute(&program);
-    println!("{}", signal_strength);
-    println!("{}", gfx);
+    answer::part1(signal_strength);
+    answer::part2(gfx);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::collections::VecDeque;
 
@@ -108,9 +110,10 @@ fn parse_monkeys(input: &str) -> Vec<Monkey> {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut input = String::new();
     io::stdin().read_to_string(&mut input).unwrap();
     let monkeys = parse_monkeys(&input);
-    println!("{}", monkey_business(monkeys.clone(), 20, 3));
-    println!("{}", monkey_business(monkeys, 10000, 1));
+    answer::part1(monkey_business(monkeys.clone(), 20, 3));
+    answer::part2(monkey_business(monkeys, 10000, 1));
 }
@@ -1,80 +1,64 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/search.rs"]
+mod search;
 use std::io::{self, BufRead};
-use std::collections::{HashSet, VecDeque};
 
 type Map = [Vec<u8>];
 type Node = (usize, usize);
 
-fn bfs(map: &Map, start: Node, end: Option<Node>) -> (u32, u32) {
-    let w = map[0].len();
-    let h = map.len();
-    let mut queue = VecDeque::new();
-    let mut visited = HashSet::new();
-    queue.push_back((start, 0));
-    visited.insert(start);
-    let mut min_a = std::u32::MAX;
-
-    let mut push = |q: &mut VecDeque<_>, pos: Node, next: Node, d: u32| {
-        let pos_elev = if pos == start { b'z' } else { map[pos.1][pos.0] };
-        let next_elev = if Some(next) == end { b'a' } else { map[next.1][next.0] };
-        // traversal happens in inverse order wrt. mission instructions
-        let reachable = next_elev + 1 == pos_elev || next_elev >= pos_elev;
-        let seen = visited.contains(&next);
-        if reachable && !seen {
-            q.push_back((next, d));
-            visited.insert(next);
-        }
-    };
-
-    while let Some((pos, dist)) = queue.pop_front() {
-        if Some(pos) == end {
-            return (dist, min_a);
-        }
-        if map[pos.1][pos.0] == b'a' {
-            min_a = min_a.min(dist);
-        }
-
-        if pos.0 > 0 {
-            push(&mut queue, pos, (pos.0 - 1, pos.1), dist + 1);
-        }
-        if pos.1 > 0 {
-            push(&mut queue, pos, (pos.0, pos.1 - 1), dist + 1);
-        }
-        if pos.0 < w - 1 {
-            push(&mut queue, pos, (pos.0 + 1, pos.1), dist + 1);
-        }
-        if pos.1 < h - 1 {
-            push(&mut queue, pos, (pos.0, pos.1 + 1), dist + 1);
-        }
+fn elevation(cell: u8) -> u8 {
+    match cell {
+        b'S' => b'a',
+        b'E' => b'z',
+        _ => cell,
     }
+}
 
-    (std::u32::MAX, min_a)
+// climbing at most one up at a time, down as much as it takes
+fn climb(map: &Map, starts: Vec<Node>) -> u32 {
+    let w = map[0].len();
+    let h = map.len();
+    let end = find_pos(map, b'E');
+    let search = search::bfs(starts, |&(x, y)| {
+        let here = elevation(map[y][x]);
+        let neighs = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        neighs.into_iter().filter(move |&(nx, ny)| nx < w && ny < h && elevation(map[ny][nx]) <= here + 1)
+    }, |&pos| pos == end);
+    search.distance(&end).unwrap() as u32
 }
 
 fn find_pos(map: &Map, item: u8) -> Node {
+    find_all(map, item)[0]
+}
+
+fn find_all(map: &Map, item: u8) -> Vec<Node> {
     map.iter().enumerate().flat_map(|(y, row)| {
         row.iter().enumerate().map(move |(x, &cell)| ((x, y), cell))
-    }).find(|&(_, cell)| cell == item).unwrap().0
+    }).filter(|&(_, cell)| cell == item).map(|(pos, _)| pos).collect()
 }
 
 fn path_to_end(map: &Map) -> u32 {
-    let start = find_pos(map, b'E');
-    let end = find_pos(map, b'S');
-
-    bfs(map, start, Some(end)).0
+    climb(map, vec![find_pos(map, b'S')])
 }
 
 fn best_path_to_end(map: &Map) -> u32 {
-    let start = find_pos(map, b'E');
-
-    // could use the same bfs above and just not exit early,
-    // but this is separate on purpose
-    bfs(map, start, None).1
+    // all the low spots at once
+    let mut starts = find_all(map, b'a');
+    starts.push(find_pos(map, b'S'));
+    climb(map, starts)
 }
 
 fn main() {
+    let _session = answer::start();
     let map: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap().into_bytes())
         .collect();
-    println!("{}", path_to_end(&map));
-    println!("{}", best_path_to_end(&map));
+    answer::part1(path_to_end(&map));
+    answer::part2(best_path_to_end(&map));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::str;
 use std::cmp::Ordering;
@@ -153,9 +155,10 @@ fn parse_pairs(input: &str) -> Vec<(String, String)> {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut input = String::new();
     io::stdin().read_to_string(&mut input).unwrap();
     let pairs = parse_pairs(&input);
-    println!("{}", right_order_sum(&pairs));
-    println!("{}", decoder_key(&pairs));
+    answer::part1(right_order_sum(&pairs));
+    answer::part2(decoder_key(&pairs));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 // x0, x1, y0, y1 (inclusive)
@@ -165,6 +167,7 @@ fn pour(g: &mut Ground) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let veins: Vec<_> = io::stdin().lock().lines()
         .flat_map(|line| parse_line(&line.unwrap()).into_iter())
         .collect();
@@ -183,8 +186,8 @@ fn main() {
     if false {
         dunp(&ground);
     }
-    println!("{:?}", score);
-    println!("{:?}", floor_score);
+    answer::part1(score);
+    answer::part2(floor_score);
     if false {
         dump_ppm(&ground);
     }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, hash_map::Entry, BinaryHeap};
 
@@ -395,13 +397,14 @@ fn parse_valve(line: &str) -> Valve {
 }
 
 fn main() {
+    let _session = answer::start();
     let valves: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_valve(&line.unwrap()))
         .collect();
     if false {
         dump_map(&valves);
     } else {
-        println!("{}", most_pressure(&valves));
-        println!("{}", most_pressure_with_elephant(&valves));
+        answer::part1(most_pressure(&valves));
+        answer::part2(most_pressure_with_elephant(&valves));
     }
 }
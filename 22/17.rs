@@ -1,3 +1,8 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/cycle.rs"]
+mod cycle;
+use cycle::CycledSignal;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -57,55 +62,6 @@ fn drop<D: Iterator<Item=i64>>(map: &mut Map, shape: &[(i64, i64)], dirs: &mut D
     map.1 - m1
 }
 
-struct CycledSignal<T>(Vec<T>);
-
-struct Cycle<'a, T>(usize, &'a [T]);
-
-impl<T: Eq>
-CycledSignal<T> {
-    fn new() -> Self {
-        Self(Vec::new())
-    }
-
-    fn test(&self, window: usize, n: usize) -> bool {
-        if window * n == 0 || self.0.len() < window * n {
-            false
-        } else {
-            // sanity check: len 8, 4 windows of size 2 each, windows begin at 0
-            let begin = &self.0[self.0.len() - n * window..];
-            (0..n-1).all(|i| {
-                let left  = &begin[(i+0) * window..(i+1) * window];
-                let right = &begin[(i+1) * window..(i+2) * window];
-                left == right
-            })
-        }
-    }
-
-    fn feed_and_test<'a>(&'a mut self, v: T) -> Option<Cycle<'a, T>> {
-        // now a cycle, if any, ends at this index; another begins at len()
-        self.0.push(v);
-        // test at least a few cycles to be sure, maybe a big cycle includes subcycles?
-        // also ignore the first half to allow the signal to settle
-        let four = (self.0.len() / 2) / 4;
-        let three = (self.0.len() / 2) / 3;
-        (four..three)
-            .find(|&len| self.test(len, 3))
-            .map(|len| Cycle(len, &self.0[self.0.len() - len ..]))
-    }
-}
-
-impl<'a, T: Copy + From<i64> + std::ops::Add<Output = T> + std::ops::Mul<Output = T> + std::iter::Sum>
-Cycle<'a, T> {
-    // the part between [start, end]
-    fn extrapolate(&'a self, start: usize, end: usize) -> T {
-        let ncycles = (end - start) / self.0;
-        let remaining = (end - start) % self.0;
-
-        T::from(ncycles as i64) * self.1.iter().copied().sum::<T>()
-            + self.1.iter().copied().take(remaining).sum::<T>()
-    }
-}
-
 fn end_height(directions: &[i64], rocks_limit: usize) -> i64 {
     // left and bottom is 0; x goes right, y goes up
     let shapes: [&[(i64, i64)]; 5] = [
@@ -155,6 +111,7 @@ fn end_height(directions: &[i64], rocks_limit: usize) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let directions: Vec<i64> = io::stdin().lock().lines().next().unwrap().unwrap()
         .as_bytes().iter().map(|b| {
             match b {
@@ -164,6 +121,6 @@ fn main() {
             }
         })
         .collect();
-    println!("{}", end_height(&directions, 2022));
-    println!("{}", end_height(&directions, 1000000000000));
+    answer::part1(end_height(&directions, 2022));
+    answer::part2(end_height(&directions, 1000000000000));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashSet};
 
@@ -155,11 +157,12 @@ fn parse_blueprint(line: &str) -> Blueprint {
 }
 
 fn main() {
+    let _session = answer::start();
     let blueprints: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_blueprint(&line.unwrap()))
         .collect();
     if true {
-        println!("{}", quality_level_sum(&blueprints));
+        answer::part1(quality_level_sum(&blueprints));
     }
-    println!("{}", first_three(&blueprints));
+    answer::part2(first_three(&blueprints));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 type Rps = i32;
@@ -59,9 +61,10 @@ fn decode_strategy(l: &str) -> (Rps, Rps) {
 }
 
 fn main() {
+    let _session = answer::start();
     let games: Vec<_> = io::stdin().lock().lines()
         .map(|line| decode_strategy(&line.unwrap()))
         .collect();
-    println!("{}", play_games_exactly(&games));
-    println!("{}", play_games_by_plan(&games));
+    answer::part1(play_games_exactly(&games));
+    answer::part2(play_games_by_plan(&games));
 }
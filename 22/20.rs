@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 type Lidx = u16;
@@ -124,9 +126,10 @@ fn mix_result_keyed(file: &mut [i64]) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file: Vec<i64> = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse().unwrap())
         .collect();
-    println!("{}", mix_result(&file, 1));
-    println!("{}", mix_result_keyed(&mut file));
+    answer::part1(mix_result(&file, 1));
+    answer::part2(mix_result_keyed(&mut file));
 }
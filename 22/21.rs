@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -140,10 +142,11 @@ fn parse_monkey(input: &str) -> Monkey {
 }
 
 fn main() {
+    let _session = answer::start();
     let monkeys: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_monkey(&line.unwrap()))
         .collect();
     let monkeys_map: Monkeys = monkeys.iter().map(|m| (m.name.clone(), m.clone())).collect();
-    println!("{}", root_number(&monkeys_map));
-    println!("{}", root_equality_test(monkeys_map));
+    answer::part1(root_number(&monkeys_map));
+    answer::part2(root_equality_test(monkeys_map));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap};
 
@@ -415,10 +417,11 @@ fn parse_notes(data: &[String]) -> Notes {
 }
 
 fn main() {
+    let _session = answer::start();
     let data: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let notes = parse_notes(&data);
-    println!("{}", final_password_flat(&notes));
-    println!("{}", final_password_cube(notes));
+    answer::part1(final_password_flat(&notes));
+    answer::part2(final_password_cube(notes));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashSet, HashMap};
 
@@ -125,8 +127,9 @@ fn parse_elves(lines: &[String]) -> Elves {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<_> = io::stdin().lock().lines().map(|l| l.unwrap()).collect();
     let elves = parse_elves(&lines);
-    println!("{}", empty_files_after(elves.clone(), 10));
-    println!("{}", rounds_needed(elves));
+    answer::part1(empty_files_after(elves.clone(), 10));
+    answer::part2(rounds_needed(elves));
 }
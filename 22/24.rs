@@ -1,6 +1,9 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/search.rs"]
+mod search;
 use std::io::{self, BufRead};
-use std::collections::{HashMap, HashSet, BinaryHeap};
-use std::cmp::Reverse;
+use std::collections::HashMap;
 
 // as the inner area in the picture: (0,0) is the top left ground in the valley
 type Coords = (i32, i32);
@@ -86,111 +89,46 @@ impl Map {
     }
 }
 
-#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
-struct Score {
-    // consumed time, smaller better
-    minutes: Reverse<i32>,
-    // heuristic distance, not actual; just to make the heap prioritize spots close to goal
-    dist_to_goal: Reverse<i32>,
-}
-
-#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Hash)]
-struct Node {
-    minutes: Reverse<i32>, // just the third axis in the world map
-    pos: Coords, // expedition is too hard to type this morning
-}
-
-#[derive(Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
-struct State {
-    score: Score,
-    node: Node,
-}
-
 // in manhattan
 fn distance(a: Coords, b: Coords) -> i32 {
     (a.0 - b.0).abs() + (a.1 - b.1).abs()
 }
 
-fn dijkstra(map: &Map, entry_pos: Coords, exit_pos: Coords, entry_minutes: i32) -> i32 {
+// the minutes are just the third axis in the world map; a step and a wait both cost one minute
+fn shortest_trip(map: &Map, entry_pos: Coords, exit_pos: Coords, entry_minutes: i32) -> i32 {
     let debug = false;
-    let startscore = Score { minutes: Reverse(0), dist_to_goal: Reverse(distance(exit_pos, entry_pos)) };
-    let startnode = Node { minutes: Reverse(entry_minutes), pos: entry_pos };
-    let mut heap: BinaryHeap<State> = BinaryHeap::new();
-    let mut visited = HashSet::new();
-    heap.push(State { score: startscore, node: startnode });
-    visited.insert(startnode);
-
-    let mut push = |h: &mut BinaryHeap<_>, state: State, best| {
-        let score = state.score;
-        let node = state.node;
-
-        // heuristic: can't possibly make it better?
-        if node.minutes.0 + score.dist_to_goal.0 >= best {
-            return;
-        }
-
-        if visited.insert(node) {
-            h.push(state);
-        }
-    };
-
-    // goal not yet discovered
-    // some big number but still allows adding more
-    let mut best_minutes = std::i32::MAX / 2;
-
-    let next_delta = &[(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)];
-    while let Some(state) = heap.pop() {
-        let (score, node) = (state.score, state.node);
+    let next_delta = [(0, 0), (1, 0), (-1, 0), (0, 1), (0, -1)];
+    let found = search::astar([(entry_minutes, entry_pos)], |&(minutes, pos)| {
         if debug {
-            println!("visit {:?} minutes {} goaldist {}, going to {:?}",
-                     node.pos, node.minutes.0, score.dist_to_goal.0, exit_pos);
-            map.print(node.minutes.0, node.pos);
-        }
-
-        if score.dist_to_goal.0 == 0 {
-            // the first found goal is the best one though,
-            // could return right away
-            best_minutes = best_minutes.min(node.minutes.0);
-            if debug {
-                println!("found a goal of {}", best_minutes);
-            }
-            // no more traversal
-            continue;
-        }
-
-        for nextpos in next_delta.iter().map(|&d| sum(node.pos, d)) {
-            let special = nextpos == exit_pos || nextpos == entry_pos;
-            let out = map.out_of_bounds(nextpos);
-            let blizzard_free = map.empty_cell(nextpos, node.minutes.0 + 1);
-
-            if special || (!out && blizzard_free) {
-                push(&mut heap, State {
-                    score: Score {
-                        minutes: Reverse(node.minutes.0 + 1),
-                        dist_to_goal: Reverse(distance(exit_pos, nextpos)),
-                    },
-                    node: Node {
-                        minutes: Reverse(node.minutes.0 + 1),
-                        pos: nextpos,
-                    }
-                }, best_minutes);
-            }
+            println!("visit {:?} minutes {}, going to {:?}", pos, minutes, exit_pos);
+            map.print(minutes, pos);
         }
+        next_delta.iter()
+            .map(move |&d| sum(pos, d))
+            .filter(move |&nextpos| {
+                let special = nextpos == exit_pos || nextpos == entry_pos;
+                special || (!map.out_of_bounds(nextpos) && map.empty_cell(nextpos, minutes + 1))
+            })
+            .map(move |nextpos| ((minutes + 1, nextpos), 1))
+    }, |&(_, pos)| distance(exit_pos, pos) as search::Cost, |&(_, pos)| pos == exit_pos);
+
+    let (minutes, _) = found.goal.expect("no way through the valley");
+    if debug {
+        println!("found a goal of {}", minutes);
     }
-
-    best_minutes
+    minutes
 }
 
 fn fewest_minutes(map: &Map) -> i32 {
-    dijkstra(map, (0, -1), (map.ground_size.0 - 1, map.ground_size.1), 0)
+    shortest_trip(map, (0, -1), (map.ground_size.0 - 1, map.ground_size.1), 0)
 }
 
 fn fewest_minutes_with_snacks(map: &Map) -> i32 {
     let start = (0, -1);
     let goal = (map.ground_size.0 - 1, map.ground_size.1);
-    let there = dijkstra(map, start, goal, 0);
-    let back = dijkstra(map, goal, start, there);
-    let there_again = dijkstra(map, start, goal, back);
+    let there = shortest_trip(map, start, goal, 0);
+    let back = shortest_trip(map, goal, start, there);
+    let there_again = shortest_trip(map, start, goal, back);
     there_again
 }
 
@@ -224,6 +162,7 @@ fn parse_map(lines: &[String]) -> Map {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
@@ -231,6 +170,6 @@ fn main() {
     if false {
         map.print(0, (0, -1));
     }
-    println!("{}", fewest_minutes(&map));
-    println!("{}", fewest_minutes_with_snacks(&map));
+    answer::part1(fewest_minutes(&map));
+    answer::part2(fewest_minutes_with_snacks(&map));
 }
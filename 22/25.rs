@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn to_snafu(mut num: i64) -> String {
@@ -44,8 +46,9 @@ fn from_snafu(snafu: &str) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let snafus: Vec<_> = io::stdin().lock().lines()
         .map(|line| from_snafu(&line.unwrap()))
         .collect();
-    println!("{}", to_snafu(snafus.iter().sum()));
+    answer::part1(to_snafu(snafus.iter().sum()));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -37,9 +39,10 @@ fn group_priority_sums(sacks: &[String]) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let rucksacks: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
-    println!("{}", priority_sums(&rucksacks));
-    println!("{}", group_priority_sums(&rucksacks));
+    answer::part1(priority_sums(&rucksacks));
+    answer::part2(group_priority_sums(&rucksacks));
 }
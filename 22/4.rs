@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 type Sects = (i32, i32);
@@ -31,9 +33,10 @@ fn parse_assignments(line: &str) -> Assignment {
 }
 
 fn main() {
+    let _session = answer::start();
     let section_assignments: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_assignments(&line.unwrap()))
         .collect();
-    println!("{}", num_fully_contained(&section_assignments));
-    println!("{}", num_partially_contained(&section_assignments));
+    answer::part1(num_fully_contained(&section_assignments));
+    answer::part2(num_partially_contained(&section_assignments));
 }
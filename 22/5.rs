@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 #[derive(Clone)]
@@ -73,10 +75,11 @@ fn parse_game(input: &[String]) -> Hanoi {
 }
 
 fn main() {
+    let _session = answer::start();
     let input: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let game = parse_game(&input);
-    println!("{}", top_stack_result(game.clone()));
-    println!("{}", top_stack_result_9001(game));
+    answer::part1(top_stack_result(game.clone()));
+    answer::part2(top_stack_result_9001(game));
 }
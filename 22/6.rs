@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -8,7 +10,8 @@ fn marker_offset(datastream: &[u8], marker_len: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let datastream: String = io::stdin().lock().lines().next().unwrap().unwrap();
-    println!("{}", marker_offset(datastream.as_bytes(), 4));
-    println!("{}", marker_offset(datastream.as_bytes(), 14));
+    answer::part1(marker_offset(datastream.as_bytes(), 4));
+    answer::part2(marker_offset(datastream.as_bytes(), 14));
 }
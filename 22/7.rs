@@ -1,5 +1,7 @@
 #![feature(cell_leak)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -229,10 +231,11 @@ fn parse_listing(lines: &[String]) -> Entry {
 }
 
 fn main() {
+    let _session = answer::start();
     let terminal_listing: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let fs = parse_listing(&terminal_listing);
-    println!("{}", simple_compute_dirs(&fs, 100000));
-    println!("{}", smallest_to_delete(&fs, 70000000, 30000000));
+    answer::part1(simple_compute_dirs(&fs, 100000));
+    answer::part2(smallest_to_delete(&fs, 70000000, 30000000));
 }
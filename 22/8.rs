@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::iter::repeat;
 
@@ -69,9 +71,10 @@ fn best_scenic_score(trees: &Trees) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let trees: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap().as_bytes().to_vec())
         .collect();
-    println!("{}", visible_outside(&trees));
-    println!("{}", best_scenic_score(&trees));
+    answer::part1(visible_outside(&trees));
+    answer::part2(best_scenic_score(&trees));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -98,9 +100,10 @@ fn parse_move(line: &str) -> Move {
 }
 
 fn main() {
+    let _session = answer::start();
     let moves: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse_move(&line.unwrap()))
         .collect();
-    println!("{}", tail_visits_once(&moves, 2));
-    println!("{}", tail_visits_once(&moves, 10));
+    answer::part1(tail_visits_once(&moves, 2));
+    answer::part2(tail_visits_once(&moves, 10));
 }
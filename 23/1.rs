@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 extern crate regex;
@@ -44,9 +46,10 @@ fn calibration_sum(calories_spec: &[String]) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<String> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
-    println!("{}", calibration_sum(&lines));
-    println!("{}", calibration_sum_letters(&lines));
+    answer::part1(calibration_sum(&lines));
+    answer::part2(calibration_sum_letters(&lines));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::collections::HashMap;
 
@@ -151,10 +153,11 @@ fn parse(file: &str) -> (Sketch, Map, Coord) {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let (sketch, map, spos) = parse(&file);
     let positions = loop_positions(&map, spos);
-    println!("{}", positions.len() / 2);
-    println!("{}", inside(&sketch, &positions));
+    answer::part1(positions.len() / 2);
+    answer::part2(inside(&sketch, &positions));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 type Map = Vec<(i64, i64)>;
@@ -28,6 +30,7 @@ fn short_paths(map: &Map) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut map: Map = io::stdin().lock().lines()
         .enumerate()
         .fold(Map::new(), |mut map, (y, line)| {
@@ -41,9 +44,9 @@ fn main() {
     let mut map2 = map.clone();
     let mut map3 = map.clone();
     expand(&mut map, 1);
-    println!("{}", short_paths(&map));
+    answer::part1(short_paths(&map));
     expand(&mut map2, 9);
     println!("10x: {}", short_paths(&map2));
     expand(&mut map3, 1_000_000 - 1);
-    println!("{}", short_paths(&map3));
+    answer::part2(short_paths(&map3));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -93,9 +95,10 @@ fn parse_row(row: &str) -> Spec {
 }
 
 fn main() {
+    let _session = answer::start();
     let rows = io::stdin().lock().lines()
         .map(|row| parse_row(&row.unwrap()))
         .collect::<Vec<_>>();
-    println!("{}", sum_arrangements(&rows));
-    println!("{}", sum_arrangements(&unfold_rows(&rows)));
+    answer::part1(sum_arrangements(&rows));
+    answer::part2(sum_arrangements(&unfold_rows(&rows)));
 }
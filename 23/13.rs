@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::collections::HashMap;
 
@@ -90,9 +92,10 @@ fn parse(file: &str) -> Vec<Pattern> {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let notes = parse(&file);
-    println!("{}", all_summary(&notes));
-    println!("{}", all_summary_smudgy(&notes));
+    answer::part1(all_summary(&notes));
+    answer::part2(all_summary_smudgy(&notes));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn focusing_power(init_seq: &str) -> u32 {
@@ -41,7 +43,8 @@ fn hash_steps(init_seq: &str) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let init_sequence: String = io::stdin().lock().lines().next().unwrap().unwrap();
-    println!("{}", hash_steps(&init_sequence));
-    println!("{}", focusing_power(&init_sequence));
+    answer::part1(hash_steps(&init_sequence));
+    answer::part2(focusing_power(&init_sequence));
 }
@@ -1,3 +1,8 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{cw, ccw};
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -6,14 +11,6 @@ type Map = Vec<Vec<char>>;
 // x right, y down
 type Coord = (i32, i32);
 
-fn cw(pos: Coord) -> Coord {
-    (-pos.1, pos.0)
-}
-
-fn ccw(pos: Coord) -> Coord {
-    (pos.1, -pos.0)
-}
-
 fn energize(map: &Map, visited: &mut HashSet<(Coord, Coord)>, mut pos: Coord, mut dir: Coord) {
     let w = map[0].len() as i32;
     let h = map.len() as i32;
@@ -67,9 +64,10 @@ fn max_tiles_energized(map: &Map) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let map = io::stdin().lock().lines()
         .map(|row| row.unwrap().chars().collect::<Vec<_>>())
         .collect::<Vec<_>>();
-    println!("{}", tiles_energized(&map, (0, 0), (1, 0)));
-    println!("{}", max_tiles_energized(&map));
+    answer::part1(tiles_energized(&map, (0, 0), (1, 0)));
+    answer::part2(max_tiles_energized(&map));
 }
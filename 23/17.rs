@@ -1,19 +1,17 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{cw, ccw};
+#[path = "../lib/search.rs"]
+mod search;
 use std::io::{self, BufRead};
-use std::collections::{HashMap, BinaryHeap};
-use std::cmp::Reverse;
+use std::collections::HashMap;
 
 type Coords = (i32, i32);
 
 type Map = HashMap<Coords, u8>;
 
-fn cw(pos: Coords) -> Coords {
-    (-pos.1, pos.0)
-}
-
-fn ccw(pos: Coords) -> Coords {
-    (pos.1, -pos.0)
-}
-
 fn sum(a: Coords, b: Coords) -> Coords {
     (a.0 + b.0, a.1 + b.1)
 }
@@ -22,26 +20,12 @@ fn _diff(a: Coords, b: Coords) -> Coords {
     (a.0 - b.0, a.1 - b.1)
 }
 
-#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
-struct Score {
-    // incurred loss, smaller better
-    heat_loss: Reverse<i32>,
-    // heuristic distance, not actual; just to make the heap prioritize spots close to goal
-    dist_to_goal: Reverse<i32>,
-}
-
 #[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd, Hash)]
 struct Node {
     pos: Coords,
     heading: Coords,
 }
 
-#[derive(Debug, Clone, Copy, Eq, Ord, PartialEq, PartialOrd)]
-struct State {
-    score: Score,
-    node: Node,
-}
-
 // in manhattan
 fn distance(a: Coords, b: Coords) -> i32 {
     (a.0 - b.0).abs() + (a.1 - b.1).abs()
@@ -57,62 +41,27 @@ fn walk(map: &Map, mut pos: Coords, delta: Coords, i: i32) -> i32 {
 }
 
 fn dijkstra(map: &Map, entry_node: &[Node], exit_pos: Coords, minstraight: i32, maxstraight: i32) -> i32 {
-    let mut heap: BinaryHeap<State> = BinaryHeap::new();
-    let mut parent = HashMap::new();
-    let mut dists = HashMap::new();
-    for &node in entry_node {
-        let startscore = Score { heat_loss: Reverse(0), dist_to_goal: Reverse(distance(exit_pos, node.pos)) };
-        heap.push(State { score: startscore, node: node });
-        dists.insert(node, 0);
-    }
-
-    let mut push = |h: &mut BinaryHeap<_>, dists: &mut HashMap<_, _>, state: State, prev: Node| {
-        let score = state.score;
-        let node = state.node;
-
-        dists.insert(node, score.heat_loss.0);
-        parent.insert(node, prev);
-        h.push(state);
-    };
-
-    while let Some(state) = heap.pop() {
-        let (score, node) = (state.score, state.node);
-
-        if score.dist_to_goal.0 == 0 {
-            // optimal search, the first found goal is the best one
-            if false {
-                let mut pos = node;
-                while let Some(&next) = parent.get(&pos) {
-                    println!("pos {:?}", pos);
-                    pos = next;
-                }
-            }
-            return score.heat_loss.0;
-        }
-
+    let found = search::astar(entry_node.iter().copied(), |node: &Node| {
+        let node = *node;
         let next_dir = [cw(node.heading), ccw(node.heading)];
-        for dir in next_dir {
-            for i in minstraight..=maxstraight {
+        next_dir.into_iter().flat_map(move |dir| {
+            (minstraight..=maxstraight).filter_map(move |i| {
                 let next_pos = sum(node.pos, (i * dir.0, i * dir.1));
-                let next_node = Node { pos: next_pos, heading: dir };
-                if let Some(&_edge_cost) = map.get(&next_pos) {
-                    let next_loss = score.heat_loss.0 + walk(map, node.pos, dir, i);
-                    if *dists.get(&next_node).unwrap_or(&std::i32::MAX) <= next_loss {
-                        continue;
-                    }
-                    push(&mut heap, &mut dists, State {
-                        score: Score {
-                            heat_loss: Reverse(next_loss),
-                            dist_to_goal: Reverse(distance(exit_pos, next_pos)),
-                        },
-                        node: next_node,
-                    }, node);
-                }
-            }
+                map.get(&next_pos)?;
+                Some((Node { pos: next_pos, heading: dir }, walk(map, node.pos, dir, i) as usize))
+            })
+        })
+    // just to make the queue prioritize spots close to goal; each step loses at least one
+    }, |node| distance(exit_pos, node.pos) as usize, |node| node.pos == exit_pos);
+
+    let goal = found.goal.expect("no route");
+    if false {
+        for node in found.path(&goal).unwrap() {
+            println!("pos {:?}", node);
         }
+        println!("expanded {} of {} queued", found.expanded, found.queued);
     }
-
-    panic!("no route");
+    found.dist[&goal] as i32
 }
 
 fn search(map: &Map, minstraight: i32, maxstraight: i32) -> i32 {
@@ -145,10 +94,11 @@ fn parse_map(rows: &[String]) -> Map {
 }
 
 fn main() {
+    let _session = answer::start();
     let rows = io::stdin().lock().lines()
         .map(|r| r.unwrap())
         .collect::<Vec<_>>();
     let map = parse_map(&rows);
-    println!("{}", least_heat_loss(&map));
-    println!("{}", least_heat_loss_ultra(&map));
+    answer::part1(least_heat_loss(&map));
+    answer::part2(least_heat_loss_ultra(&map));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 use std::i64;
@@ -163,9 +165,10 @@ fn parse_dig(line: &str) -> DigStep {
 }
 
 fn main() {
+    let _session = answer::start();
     let dig_plan = io::stdin().lock().lines()
         .map(|row| parse_dig(&row.unwrap()))
         .collect::<Vec<_>>();
-    println!("{}", lava_amount(&dig_plan));
-    println!("{}", corrected_lava_amount(&dig_plan));
+    answer::part1(lava_amount(&dig_plan));
+    answer::part2(corrected_lava_amount(&dig_plan));
 }
@@ -1,3 +1,8 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/interval.rs"]
+mod interval;
+use interval::{Block, Interval};
 use std::io::{self, Read};
 use std::collections::HashMap;
 
@@ -62,62 +67,35 @@ fn accepted_part_numbers(workflows: &[Workflow], parts: &[Part]) -> i32 {
         .sum()
 }
 
-fn splice_left(mut lo: Part, mut hi: Part, idx: usize, less_than: i32) -> (Part, Part) {
-    // a < 2006: --lo--<++hi++
-    // [old lo, new hi] matches, on the left
-    hi[idx] = hi[idx].min(less_than - 1);
-    // [new lo, old hi] remaining, on the right
-    lo[idx] = less_than;
-    (hi, lo)
-}
-
-fn splice_right(mut lo: Part, mut hi: Part, idx: usize, greater_than: i32) -> (Part, Part) {
-    // a > 2006: --lo-->++hi++
-    // [old lo, new hi] remaining, on the left
-    hi[idx] = greater_than;
-    // [new lo, old hi] matches, on the right
-    lo[idx] = lo[idx].max(greater_than + 1);
-    (hi, lo)
-}
-
-fn range_count(workflows: &HashMap<&str, &Workflow>, current: &str, mut lo: Part, mut hi: Part) -> usize {
-    assert!(lo[0] <= hi[0]);
-    assert!(lo[1] <= hi[1]);
-    assert!(lo[2] <= hi[2]);
-    assert!(lo[3] <= hi[3]);
-
+// each rule splits the parts in two along one property, the matching and the remaining ones
+fn range_count(workflows: &HashMap<&str, &Workflow>, current: &str, parts: Block<4>) -> usize {
     if current == "A" {
         // accept
-        let a = lo.iter().zip(hi).map(|(l, h)| h - l + 1).fold(1, |acc, x| acc * x as usize);
-        a
+        parts.volume() as usize
     } else if current == "R" {
         // reject
         0
     } else {
         let mut count = 0;
+        let mut rest = Some(parts);
         for r in &workflows.get(current).unwrap().rules {
-            match r.cmp_type {
-                Cmp::Lt => {
-                    if lo[r.part_prop] < r.cmp {
-                        let (mid_left, mid_right) = splice_left(lo, hi, r.part_prop, r.cmp);
-                        // seek [lo, mid-1]
-                        count += range_count(workflows, &r.dest, lo, mid_left);
-                        // remaining with [mid, hi]
-                        lo = mid_right;
-                    }
-                },
+            let Some(parts) = rest else {
+                break;
+            };
+            let cmp = r.cmp as i64;
+            let matching;
+            (matching, rest) = match r.cmp_type {
+                // a < 2006: ++match++<--rest--
+                Cmp::Lt => parts.split(r.part_prop, cmp),
+                // a > 2006: --rest-->++match++
                 Cmp::Gt => {
-                    if hi[r.part_prop] > r.cmp {
-                        let (mid_left, mid_right) = splice_right(lo, hi, r.part_prop, r.cmp);
-                        // seek [mid+1, hi]
-                        count += range_count(workflows, &r.dest, mid_right, hi);
-                        // remaining with [lo, mid]
-                        hi = mid_left;
-                    }
+                    let (rest, matching) = parts.split(r.part_prop, cmp + 1);
+                    (matching, rest)
                 },
-                Cmp::Nop => {
-                    count += range_count(workflows, &r.dest, lo, hi);
-                }
+                Cmp::Nop => (Some(parts), None),
+            };
+            if let Some(matching) = matching {
+                count += range_count(workflows, &r.dest, matching);
             }
         }
         count
@@ -128,7 +106,7 @@ fn accepted_combinations(workflows: &[Workflow]) -> usize {
     let workflows = workflows.iter()
         .map(|wf| (&wf.name as &str, wf))
         .collect::<HashMap<_, _>>();
-    range_count(&workflows, "in", [1, 1, 1, 1], [4000, 4000, 4000, 4000])
+    range_count(&workflows, "in", Block([Interval::inclusive(1, 4000); 4]))
 }
 
 fn parse_workflows(inp: &str) -> Vec<Workflow> {
@@ -181,9 +159,10 @@ fn parse(file: &str) -> (Vec<Workflow>, Vec<Part>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let (workflows, parts) = parse(&file);
-    println!("{}", accepted_part_numbers(&workflows, &parts));
-    println!("{}", accepted_combinations(&workflows));
+    answer::part1(accepted_part_numbers(&workflows, &parts));
+    answer::part2(accepted_combinations(&workflows));
 }
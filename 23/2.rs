@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 extern crate regex;
@@ -37,10 +39,11 @@ fn parse_game(inp: &str) -> Game {
 }
 
 fn main() {
+    let _session = answer::start();
     let games: Vec<Game> = io::stdin().lock().lines()
         .map(|line| parse_game(&line.unwrap()))
         .collect();
-    println!("{}", games.iter().filter(|x| x.ok).map(|x| x.id).sum::<u32>());
-    println!("{}", games.iter().map(|x| x.power).sum::<u32>());
+    answer::part1(games.iter().filter(|x| x.ok).map(|x| x.id).sum::<u32>());
+    answer::part2(games.iter().map(|x| x.power).sum::<u32>());
 }
 
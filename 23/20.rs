@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, VecDeque};
 
@@ -155,9 +157,10 @@ fn parse_module(line: &str) -> Module {
 }
 
 fn main() {
+    let _session = answer::start();
     let config = io::stdin().lock().lines()
         .map(|row| parse_module(&row.unwrap()))
         .collect::<Vec<_>>();
-    println!("{}", pulses(&config));
-    println!("{}", rx_low_pulse_time(&config));
+    answer::part1(pulses(&config));
+    answer::part2(rx_low_pulse_time(&config));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead, Write};
 use std::collections::{HashMap, VecDeque, HashSet};
 use std::fs::File;
@@ -162,12 +164,13 @@ fn search_infinite(map: &Map, spos: Coord, steps: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines = io::stdin().lock().lines()
         .map(|row| row.unwrap())
         .collect::<Vec<_>>();
     let (map, start) = parse(&lines);
 
     println!("{}", search(&map, start, 6));
-    println!("{}", search(&map, start, 64));
-    println!("{}", search_infinite(&map, start, 26501365));
+    answer::part1(search(&map, start, 64));
+    answer::part2(search_infinite(&map, start, 26501365));
 }
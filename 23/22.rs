@@ -1,28 +1,26 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/interval.rs"]
+mod interval;
+use interval::Interval;
+#[path = "../lib/csg.rs"]
+mod csg;
+use csg::{Cuboid, Solid};
 use std::io::{self, BufRead};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 extern crate regex;
 use regex::Regex;
 
-type Coord = (u32, u32, u32);
-// begin, end coords; inclusive range by the input spec
-type Brick = (Coord, Coord);
+type Brick = Cuboid;
 
-fn overlap(a: (u32, u32), b: (u32, u32)) -> Option<(u32, u32)> {
-    let left = a.0.max(b.0);
-    let right = a.1.min(b.1);
-    if left <= right {
-        Some((left, right))
-    } else {
-        None
-    }
+fn overlap_xy(a: &Brick, b: &Brick) -> bool {
+    a.0[0].intersect(b.0[0]).is_some() && a.0[1].intersect(b.0[1]).is_some()
 }
 
 // some overlap in x,y plane and just touching in z
-fn supports(below: Brick, above: Brick) -> bool {
-    below.0.2.max(below.1.2) + 1 == above.0.2.min(above.1.2) &&
-        overlap((below.0.0, below.1.0), (above.0.0, above.1.0)).is_some() &&
-        overlap((below.0.1, below.1.1), (above.0.1, above.1.1)).is_some()
+fn supports(below: &Brick, above: &Brick) -> bool {
+    below.0[2].hi == above.0[2].lo && overlap_xy(below, above)
 }
 
 fn disintegratable(bricks: &[Brick]) -> usize {
@@ -32,7 +30,7 @@ fn disintegratable(bricks: &[Brick]) -> usize {
     let mut supp_by = HashMap::<Brick, usize>::new();
     for below in bricks {
         for above in bricks {
-            if supports(*below, *above) {
+            if supports(below, above) {
                 supp_map.entry(*below).or_insert(Vec::new()).push(*above);
                 *supp_by.entry(*above).or_insert(0) += 1;
             }
@@ -55,66 +53,72 @@ fn disintegratable(bricks: &[Brick]) -> usize {
 }
 
 // quick tetris mode
-fn drop_brick(b: Brick, z: u32) -> Brick {
-    ((b.0.0, b.0.1, z), (b.1.0, b.1.1, z + (b.1.2 - b.0.2)))
+fn drop_brick(b: Brick, z: i64) -> Brick {
+    let mut d = b;
+    d.0[2] = b.0[2].shift(z - b.0[2].lo);
+    d
 }
 
-fn settle(mut bricks: Vec<Brick>) -> (Vec<Brick>, usize) {
+fn settle(mut bricks: Vec<Brick>) -> Vec<Brick> {
     let mut settled = Vec::<Brick>::new();
-    bricks.sort_unstable_by_key(|a| a.0.2.min(a.1.2));
-    let mut dropped = 0;
-    'outer: for b in bricks {
-        let z_min = b.0.2.min(b.1.2);
-        let b_xrange = (b.0.0, b.1.0);
-        let b_yrange = (b.0.1, b.1.1);
-        for below_z in (1..z_min).rev() {
-            if settled.iter().any(|s| {
-                s.0.2.max(s.1.2) == below_z &&
-                    overlap(b_xrange, (s.0.0, s.1.0)).is_some() &&
-                    overlap(b_yrange, (s.0.1, s.1.1)).is_some()
-            }) {
-                let d = drop_brick(b, below_z + 1);
-                if d != b {
-                    dropped += 1;
-                }
-                settled.push(d);
-                continue 'outer;
-            }
-        }
-        // no bricks found so it hit the floor
-        // (and dug itself underground, no problem)
-        let d = drop_brick(b, 1);
-        if d != b {
-            dropped += 1;
+    let mut pile = Solid::new();
+    bricks.sort_unstable_by_key(|b| b.0[2].lo);
+    for b in bricks {
+        // everything right under this one; the floor is at 1. most are already resting on
+        // something so look at just the next layer first
+        let mut column = b;
+        column.0[2] = Interval::at(b.0[2].lo - 1, 1);
+        if pile.overlapping(&column).is_empty() {
+            column.0[2] = Interval::new(1, b.0[2].lo);
         }
+        let z = pile.overlapping(&column).iter().map(|s| s.0[2].hi).max().unwrap_or(1);
+        let d = drop_brick(b, z);
+        pile.add(d);
         settled.push(d);
     }
-    (settled, dropped)
+    settled
 }
 
-fn sum_falls(bricks: &Vec<Brick>) -> usize {
-    let mut n = 0;
-    for i in 0..bricks.len() {
-        let mut bs = bricks.clone();
-        bs.remove(i);
-        n += settle(bs).1;
+// settle() leaves the bricks in an order where each comes after all that it rests on, so a pass
+// from the start sees every brick after what's under it has fallen or not
+fn sum_falls(bricks: &[Brick]) -> usize {
+    let mut resting_on = HashMap::<Brick, Vec<Brick>>::new();
+    for below in bricks {
+        for above in bricks {
+            if supports(below, above) {
+                resting_on.entry(*above).or_insert(Vec::new()).push(*below);
+            }
+        }
     }
-    n
+    bricks.iter().map(|removed| {
+        let mut fallen = HashSet::from([*removed]);
+        for b in bricks {
+            if let Some(under) = resting_on.get(b) {
+                if under.iter().all(|u| fallen.contains(u)) {
+                    fallen.insert(*b);
+                }
+            }
+        }
+        fallen.len() - 1
+    }).sum()
 }
 
 fn parse_brick(line: &str) -> Brick {
     let re = Regex::new(r"(\d+),(\d+),(\d+)~(\d+),(\d+),(\d+)").unwrap();
     let cap = re.captures(line).unwrap();
-    let g = |i| cap.get(i).unwrap().as_str().parse().unwrap();
-    ((g(1), g(2), g(3)), (g(4), g(5), g(6)))
+    let g = |i| cap.get(i).unwrap().as_str().parse::<i64>().unwrap();
+    // begin, end coords; inclusive range by the input spec
+    let (a, b) = ([g(1), g(2), g(3)], [g(4), g(5), g(6)]);
+    csg::inclusive([0, 1, 2].map(|i| a[i].min(b[i])), [0, 1, 2].map(|i| a[i].max(b[i])))
 }
 
 fn main() {
+    let _session = answer::start();
     let bricks = io::stdin().lock().lines()
         .map(|row| parse_brick(&row.unwrap()))
         .collect::<Vec<_>>();
 
-    let bricks = settle(bricks).0;
-    println!("{}", disintegratable(&bricks));
-    println!("{}", sum_falls(&bricks));
+    let bricks = settle(bricks);
+    answer::part1(disintegratable(&bricks));
+    answer::part2(sum_falls(&bricks));
 }
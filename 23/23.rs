@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, HashSet};
 
@@ -95,10 +97,11 @@ fn longest_hike_uphill(map: &TileMap) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let tiles = io::stdin().lock().lines()
         .map(|row| row.unwrap().chars().collect::<Vec<_>>())
         .collect::<Vec<_>>();
 
-    println!("{}", longest_hike(&tiles));
-    println!("{}", longest_hike_uphill(&tiles));
+    answer::part1(longest_hike(&tiles));
+    answer::part2(longest_hike_uphill(&tiles));
 }
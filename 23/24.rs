@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 extern crate regex;
@@ -119,11 +121,12 @@ fn parse_hail(line: &str) -> Hail {
 }
 
 fn main() {
+    let _session = answer::start();
     let storm = io::stdin().lock().lines()
         .map(|row| parse_hail(&row.unwrap()))
         .collect::<Vec<_>>();
 
     println!("{}", intersecting(&storm, 7.0, 27.0));
-    println!("{}", intersecting(&storm, 200_000_000_000_000.0, 400_000_000_000_000.0));
-    println!("{}", perfect_rock(&storm));
+    answer::part1(intersecting(&storm, 200_000_000_000_000.0, 400_000_000_000_000.0));
+    answer::part2(perfect_rock(&storm));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -27,6 +29,7 @@ fn remove(edges: &mut Vec<(&str, &str)>, a: &str, b: &str) {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines = io::stdin().lock().lines()
         .map(|row| row.unwrap())
         .collect::<Vec<_>>();
@@ -54,5 +57,5 @@ fn main() {
         remove(&mut edges, "rrl", "pcs");
         remove(&mut edges, "mbk", "qnd");
     }
-    println!("{}", connected_pair(&mut edges));
+    answer::part1(connected_pair(&mut edges));
 }
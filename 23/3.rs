@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -63,11 +65,12 @@ fn gear_ratios(map: &GearMap) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<String> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let mut map = HashMap::new();
-    println!("{}", part_number_sum(&lines, &mut map));
-    println!("{}", gear_ratios(&map));
+    answer::part1(part_number_sum(&lines, &mut map));
+    answer::part2(gear_ratios(&map));
 }
 
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -41,9 +43,10 @@ fn game(cards: &[Card]) -> u32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let cards: Vec<Card> = io::stdin().lock().lines()
         .map(|line| parse_card(&line.unwrap()))
         .collect();
-    println!("{}", cards.iter().map(points).sum::<u32>());
-    println!("{}", game(&cards));
+    answer::part1(cards.iter().map(points).sum::<u32>());
+    answer::part2(game(&cards));
 }
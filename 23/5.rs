@@ -1,3 +1,8 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/interval.rs"]
+mod interval;
+use interval::{Interval, IntervalSet, Translation};
 use std::io::{self, Read};
 
 extern crate regex;
@@ -6,107 +11,31 @@ use regex::Regex;
 // consume 3m46s to do naive computation vs 0.008 seconds with ranges
 const DOUBLE_CHECK: bool = false;
 
-#[derive(Debug)]
-struct MapRange {
-    dst_start: u32,
-    src_start: u32,
-    len: u32,
-}
-
-type Map = Vec<MapRange>;
-
 #[derive(Debug)]
 struct Almanac {
-    seeds: Vec<u32>,
-    maps: Vec<Map>,
-}
-
-fn map_lookup(map: &Map, property: u32) -> u32 {
-    for range in map {
-        if property >= range.src_start {
-            let delta = property - range.src_start;
-            if delta < range.len {
-                return range.dst_start + delta;
-            }
-        }
-    }
-    return property;
+    seeds: Vec<i64>,
+    maps: Vec<Translation>,
 }
 
-fn map_to_location(almanac: &Almanac, property: u32, lookup: usize) -> u32 {
-    if lookup == almanac.maps.len() {
-        return property;
-    }
-    let next = map_lookup(&almanac.maps[lookup], property);
-    map_to_location(almanac, next, lookup + 1)
+fn map_to_location(almanac: &Almanac, property: i64) -> i64 {
+    almanac.maps.iter().fold(property, |prop, map| map.apply(prop))
 }
 
-fn overlap(a: (u32, u32), b: (u32, u32)) -> Option<(u32, u32)> {
-    let left = a.0.max(b.0);
-    let right = a.1.min(b.1);
-    if left <= right {
-        Some((left, right))
-    } else {
-        None
-    }
+fn lowest_location(almanac: &Almanac) -> i64 {
+    almanac.seeds.iter().map(|&s| map_to_location(almanac, s)).min().unwrap()
 }
 
-fn try_overlap_range(almanac: &Almanac, prop_start: u32, prop_end: u32, lookup: usize) -> Option<u32> {
-    for range in &almanac.maps[lookup] {
-        let src_end = range.src_start + (range.len - 1);
-        if let Some(together) = overlap((prop_start, prop_end), (range.src_start, src_end)) {
-            // found a match
-            let off_l = together.0 - range.src_start;
-            let off_r = together.1 - range.src_start;
-            let mut smol = map_to_location_range(almanac, range.dst_start + off_l, range.dst_start + off_r, lookup + 1);
-
-            if prop_start < range.src_start {
-                // split the range and retry the left only
-                smol = smol.min(map_to_location_range(almanac, prop_start, range.src_start - 1, lookup));
-            }
-
-            if prop_end > src_end {
-                // split the range and retry the right only
-                smol = smol.min(map_to_location_range(almanac, src_end + 1, prop_end, lookup));
-            }
-
-            // no need to continue the loop, ranges do not overlap so this was the only one
-            // (plus possibly the splits)
-            return Some(smol);
-        }
-    }
-
-    None
-}
-
-// end is inclusive
-fn map_to_location_range(almanac: &Almanac, prop_start: u32, prop_end: u32, lookup: usize) -> u32 {
-    if lookup == almanac.maps.len() {
-        return prop_start;
-    }
-    if let Some(smol) = try_overlap_range(almanac, prop_start, prop_end, lookup) {
-        smol
-    } else {
-        // nothing matched, entire range maps 1:1
-        map_to_location_range(almanac, prop_start, prop_end, lookup + 1)
-    }
-
-}
-
-fn lowest_location(almanac: &Almanac) -> u32 {
-    almanac.seeds.iter().map(|&s| map_to_location(almanac, s, 0)).min().unwrap()
-}
-
-fn _lowest_location_ranged_naive(almanac: &Almanac) -> u32 {
+fn _lowest_location_ranged_naive(almanac: &Almanac) -> i64 {
     almanac.seeds.chunks(2).map(|spec| {
-        (spec[0] .. spec[0] + spec[1]).map(|s| map_to_location(almanac, s, 0)).min().unwrap()
+        (spec[0] .. spec[0] + spec[1]).map(|s| map_to_location(almanac, s)).min().unwrap()
     }).min().unwrap()
 }
 
-fn lowest_location_ranged(almanac: &Almanac) -> u32 {
-    let ret = almanac.seeds.chunks(2).map(|spec| {
-        map_to_location_range(almanac, spec[0], spec[0] + spec[1] - 1, 0)
-    }).min().unwrap();
+fn lowest_location_ranged(almanac: &Almanac) -> i64 {
+    // the ranges split up as they go through the maps, but there aren't many of them
+    let seeds = almanac.seeds.chunks(2).map(|spec| Interval::at(spec[0], spec[1])).collect::<IntervalSet>();
+    let locations = almanac.maps.iter().fold(seeds, |props, map| map.apply_set(&props));
+    let ret = locations.first().unwrap();
     if DOUBLE_CHECK {
         assert_eq!(ret, _lowest_location_ranged_naive(almanac));
     }
@@ -146,22 +75,25 @@ humidity-to-location map:
      * 52 50 48\n\n
      */
     let maps = (2..=8).map(|i| {
-        cap.get(i).unwrap().as_str().trim_end().split('\n').map(|rule_spec| {
+        let mut map = Translation::new();
+        for rule_spec in cap.get(i).unwrap().as_str().trim_end().split('\n') {
             let mut sp = rule_spec.split(' ');
-            let dst_start = sp.next().unwrap().parse().unwrap();
-            let src_start = sp.next().unwrap().parse().unwrap();
+            let dst_start: i64 = sp.next().unwrap().parse().unwrap();
+            let src_start: i64 = sp.next().unwrap().parse().unwrap();
             let len = sp.next().unwrap().parse().unwrap();
-            MapRange { dst_start, src_start, len }
-        }).collect::<Vec<_>>()
+            map.add(Interval::at(src_start, len), dst_start - src_start);
+        }
+        map
     }).collect::<Vec<_>>();
 
     Almanac { seeds, maps }
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let almanac = parse(&file);
-    println!("{}", lowest_location(&almanac));
-    println!("{}", lowest_location_ranged(&almanac));
+    answer::part1(lowest_location(&almanac));
+    answer::part2(lowest_location_ranged(&almanac));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 
 fn beats(time: i64, best_distance: i64) -> i64 {
@@ -31,10 +33,11 @@ fn parse(file: &str) -> Vec<(i64, i64)> {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let paper = parse(&file);
-    println!("{:}", ways_to_beat(&paper));
+    answer::part1(ways_to_beat(&paper));
     let fixed = fix_kerning(&paper);
-    println!("{:}", beats(fixed.0, fixed.1));
+    answer::part2(beats(fixed.0, fixed.1));
 }
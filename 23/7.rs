@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -113,9 +115,10 @@ fn parse_bid(line: &str) -> (Hand, i32) {
 }
 
 fn main() {
+    let _session = answer::start();
     let games: Vec<(Hand, i32)> = io::stdin().lock().lines()
         .map(|line| parse_bid(&line.unwrap()))
         .collect();
-    println!("{}", total_winnings(games.clone()));
-    println!("{}", total_winnings_jokery(games));
+    answer::part1(total_winnings(games.clone()));
+    answer::part2(total_winnings_jokery(games));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::collections::HashMap;
 
@@ -55,9 +57,10 @@ fn parse(file: &str) -> (Vec<bool>, Map) {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let (dirs, map) = parse(&file);
-    println!("{}", steps(&dirs, &map, "AAA", |x| x == "ZZZ"));
-    println!("{}", steps_ghost(&dirs, &map));
+    answer::part1(steps(&dirs, &map, "AAA", |x| x == "ZZZ"));
+    answer::part2(steps_ghost(&dirs, &map));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn find_prev_next(history: &[i32]) -> (i32, i32) {
@@ -25,10 +27,11 @@ fn parse_numbers(line: &str) -> Vec<i32> {
 }
 
 fn main() {
+    let _session = answer::start();
     let histories: Vec<Vec<i32>> = io::stdin().lock().lines()
         .map(|line| parse_numbers(&line.unwrap()))
         .collect();
     let pn = prev_next_sum(&histories);
-    println!("{}", pn.1);
-    println!("{}", pn.0);
+    answer::part1(pn.1);
+    answer::part2(pn.0);
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn total_distance(lists: &[(i32, i32)]) -> i32 {
@@ -22,9 +24,10 @@ fn parse(inp: &str) -> (i32, i32) {
 }
 
 fn main() {
+    let _session = answer::start();
     let lists: Vec<(i32, i32)> = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect();
-    println!("{}", total_distance(&lists));
-    println!("{}", similarity_score(&lists));
+    answer::part1(total_distance(&lists));
+    answer::part2(similarity_score(&lists));
 }
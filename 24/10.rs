@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -84,10 +86,11 @@ fn trailhead_measure<T: Mem + Clone>(map: &Map, mem: T) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let map = Map::new(io::stdin().lock().lines()
         .map(|line| line.unwrap()
              .bytes().map(|c| (c - b'0') as i32).collect::<Vec<_>>()
             ).collect::<Vec<_>>());
-    println!("{}", trailhead_measure(&map, MemSet(HashSet::new())));
-    println!("{}", trailhead_measure(&map, MemNop));
+    answer::part1(trailhead_measure(&map, MemSet(HashSet::new())));
+    answer::part2(trailhead_measure(&map, MemNop));
 }
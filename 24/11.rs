@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -57,12 +59,13 @@ fn blink_faster(stones: &[u128], blinks: usize) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let stones: Vec<_> = io::stdin().lock().lines()
         .next().unwrap().unwrap()
         .split(' ').map(|s| s.parse().unwrap())
         .collect();
     println!("{}", blink(stones.clone(), 6));
     println!("{} (backup)", blink(stones.clone(), 25));
-    println!("{}", blink_faster(&stones, 25));
-    println!("{}", blink_faster(&stones, 75));
+    answer::part1(blink_faster(&stones, 25));
+    answer::part2(blink_faster(&stones, 75));
 }
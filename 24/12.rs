@@ -1,45 +1,11 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{Map, Pos, add, right};
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
-type Data = char;
-struct Map(Vec<Vec<Data>>);
-type Pos = (i32, i32);
-
-impl Map {
-    fn new(v: Vec<Vec<Data>>) -> Self {
-        Self(v)
-    }
-    fn w(&self) -> i32 {
-        self.0[0].len() as i32
-    }
-    fn h(&self) -> i32 {
-        self.0.len() as i32
-    }
-    fn at(&self, p: Pos) -> Option<Data> {
-        if p.0 >= 0 && p.0 < self.w() && p.1 >= 0 && p.1 < self.h() {
-            Some(self.0[p.1 as usize][p.0 as usize])
-        } else {
-            None
-        }
-    }
-    fn iter(&self) -> impl Iterator<Item = (Pos, Data)> + '_ {
-        self.0.iter().enumerate()
-            .flat_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(move |(x, &h)| ((x as i32, y as i32), h))
-            })
-    }
-}
-
-fn add(a: Pos, b: Pos) -> Pos {
-    (a.0 + b.0, a.1 + b.1)
-}
-
-fn right(p: Pos) -> Pos {
-    (-p.1, p.0)
-}
-
 fn search(map: &Map, p: Pos, c: char, visits: &mut HashSet<Pos>, region: &mut HashSet<Pos>) -> (usize, usize) {
     if !visits.insert(p) {
         return (0, 0);
@@ -51,7 +17,7 @@ fn search(map: &Map, p: Pos, c: char, visits: &mut HashSet<Pos>, region: &mut Ha
     let mut perimeter = 4;
     for delta in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
         let neighpos = add(p, delta);
-        if map.at(neighpos) == Some(c) {
+        if map.get(neighpos) == Some(c) {
             perimeter -= 1;
             let (next_area, next_perimeter) = search(map, neighpos, c, visits, region);
             area += next_area;
@@ -135,10 +101,8 @@ fn total_fencing_price_discounted(map: &Map) -> usize {
 }
 
 fn main() {
-    let map = Map::new(io::stdin().lock().lines()
-        .map(|line| line.unwrap()
-             .chars().collect::<Vec<_>>()
-            ).collect::<Vec<_>>());
-    println!("{}", total_fencing_price(&map));
-    println!("{}", total_fencing_price_discounted(&map));
+    let _session = answer::start();
+    let map = Map::parse(io::stdin().lock().lines().map(|line| line.unwrap()));
+    answer::part1(total_fencing_price(&map));
+    answer::part2(total_fencing_price_discounted(&map));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 extern crate regex;
@@ -111,11 +113,12 @@ fn parse<'a>(specs: impl Iterator<Item=&'a [String]>) -> Vec<Game> {
 }
 
 fn main() {
+    let _session = answer::start();
     let lines: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect();
     let games = parse(lines.split(|l| l.is_empty()));
     println!("{}", fewest_tokens(&games));
-    println!("{}", fewest_tokens_fast(&games));
-    println!("{}", fewest_tokens_fast(&repair(&games)));
+    answer::part1(fewest_tokens_fast(&games));
+    answer::part2(fewest_tokens_fast(&repair(&games)));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 
@@ -96,10 +98,11 @@ fn parse(line: &str) -> Robot {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut robots: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect();
     println!("{}", safety_factor(&mut robots.clone(), 11, 7, 100));
-    println!("{}", safety_factor(&mut robots.clone(), 101, 103, 100));
-    println!("{}", christmas_tree(&mut robots, 101, 103));
+    answer::part1(safety_factor(&mut robots.clone(), 101, 103, 100));
+    answer::part2(christmas_tree(&mut robots, 101, 103));
 }
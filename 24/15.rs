@@ -1,66 +1,10 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/grid.rs"]
+mod grid;
+use grid::{Map, Pos, add, neg};
 use std::io::{self, Read};
 use std::collections::{VecDeque, HashSet};
-use std::ops::{Index, IndexMut};
-
-type Data = char;
-#[derive(Clone)]
-struct Map(Vec<Vec<Data>>);
-type Pos = (i32, i32);
-
-impl Map {
-    fn new(v: Vec<Vec<Data>>) -> Self {
-        Self(v)
-    }
-    fn w(&self) -> i32 {
-        self.0[0].len() as i32
-    }
-    fn h(&self) -> i32 {
-        self.0.len() as i32
-    }
-    fn at(&self, p: Pos) -> Option<&Data> {
-        if p.0 >= 0 && p.0 < self.w() && p.1 >= 0 && p.1 < self.h() {
-            Some(&self.0[p.1 as usize][p.0 as usize])
-        } else {
-            None
-        }
-    }
-    fn at_mut(&mut self, p: Pos) -> Option<&mut Data> {
-        if p.0 >= 0 && p.0 < self.w() && p.1 >= 0 && p.1 < self.h() {
-            Some(&mut self.0[p.1 as usize][p.0 as usize])
-        } else {
-            None
-        }
-    }
-    fn iter(&self) -> impl Iterator<Item = (Pos, Data)> + '_ {
-        self.0.iter().enumerate()
-            .flat_map(|(y, row)| {
-                row.iter()
-                    .enumerate()
-                    .map(move |(x, &h)| ((x as i32, y as i32), h))
-            })
-    }
-}
-
-impl Index<Pos> for Map {
-    type Output = Data;
-    fn index(&self, p: Pos) -> &Self::Output {
-        self.at(p).unwrap()
-    }
-}
-
-impl IndexMut<Pos> for Map {
-    fn index_mut(&mut self, p: Pos) -> &mut Data {
-        self.at_mut(p).unwrap()
-    }
-}
-
-fn add(a: Pos, b: Pos) -> Pos {
-    (a.0 + b.0, a.1 + b.1)
-}
-
-fn neg(a: Pos) -> Pos {
-    (-a.0, -a.1)
-}
 
 fn gps(p: Pos) -> i32 {
     100 * p.1 + p.0
@@ -166,7 +110,7 @@ fn dump(map: &Map, r: Pos) {
 }
 
 fn simulate(mut map: Map, moves: &[char]) -> Map {
-    let mut robot = map.iter().find(|&(_, ch)| ch == '@').unwrap().0;
+    let mut robot = map.find('@').unwrap();
     map[robot] = '.';
     for m in moves {
         if false {
@@ -214,10 +158,7 @@ fn widen_warehouse(map: Map) -> Map {
 
 fn parse(file: &str) -> (Map, Vec<char>) {
     let mut sp = file.split("\n\n");
-    let map = Map::new(sp.next().unwrap()
-        .lines()
-        .map(|l| l.chars().collect())
-        .collect());
+    let map = Map::parse(sp.next().unwrap().lines());
 
     let mut moves = Vec::new();
     for line in sp.next().unwrap().lines() {
@@ -227,9 +168,10 @@ fn parse(file: &str) -> (Map, Vec<char>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let (map, moves) = parse(&file);
-    println!("{:?}", predict(map.clone(), &moves));
-    println!("{:?}", predict(widen_warehouse(map), &moves));
+    answer::part1(predict(map.clone(), &moves));
+    answer::part2(predict(widen_warehouse(map), &moves));
 }
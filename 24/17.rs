@@ -1,86 +1,104 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+use std::env;
 use std::io::{self, Read};
 
-/*
- * Program: 2,4, 1,5, 7,5, 0,3, 4,1, 1,6, 5,5, 3,0
- * b = a & 7
- * b = b ^ 5
- * c = a / (1 << b)
- * a = a / 8
- * b = b ^ c
- * b = b ^ 6
- * output b & 7
- * if a != 0 goto start
- *
- * do {
- *     c = a / (1 << ((a % 8) ^ 5))
- *     b = (a % 8) ^ c ^ 3   // b = (a % 8) ^ 5 ^ c ^ 6
- *     a = a / 8
- *     output b & 7
- * } while (a != 0);
- *
- * - clearly one output per three bits (0..7)
- * - output is affected by low bits in a % 8 and whole thing in a / ...
- * - whole thing is divided by 2**(itself%8) and then % 8 which limits variation
- * - may need to try also 8 variations in steps of 2**7=128, and 8*128=1024, 10 bits
- */
-
-fn executefast(i: i64) -> Vec<i64> {
-    let mut a = i;
-    let mut b;
-    let mut c;
-    let mut out = Vec::new();
-    loop {
-        b = a & 7;
-        b = b ^ 5;
-        c = a / (1 << b);
-        a = a / 8;
-        b = b ^ c;
-        b = b ^ 6;
-        out.push((b & 7) as i64);
-        if a == 0 {
-            break;
-        }
+const MNEMONICS: [&str; 8] = ["adv", "bxl", "bst", "jnz", "bxc", "out", "bdv", "cdv"];
+const REG_NAMES: [&str; 3] = ["a", "b", "c"];
+
+// which operands are combo operands; the rest are literals, and bxc ignores its own
+fn has_combo(opcode: i64) -> bool {
+    matches!(opcode, 0 | 2 | 5 | 6 | 7)
+}
+
+fn combo_name(operand: i64) -> String {
+    match operand {
+        0 ..= 3 => operand.to_string(),
+        4 ..= 6 => REG_NAMES[operand as usize - 4].to_string(),
+        _ => "??".to_string(),
+    }
+}
+
+fn pseudocode(opcode: i64, operand: i64) -> String {
+    let combo = combo_name(operand);
+    match opcode {
+        0 => format!("a = a >> {}", combo),
+        1 => format!("b = b ^ {}", operand),
+        2 => format!("b = {} & 7", combo),
+        3 => format!("if a != 0 goto {}", operand),
+        4 => "b = b ^ c".to_string(),
+        5 => format!("output {} & 7", combo),
+        6 => format!("b = a >> {}", combo),
+        7 => format!("c = a >> {}", combo),
+        _ => "??".to_string(),
     }
-    out
 }
 
-// each 3 bits produce one digit but consume 10 bits because a/(2**(a%8)) that wraps % 8 after 1023
-// thus keep all high bits that match digits so far because those high bits affect the next two digits
-fn search(computer: &Computer, depth: usize) -> Vec<i64> {
-    if depth == computer.program.len() {
-        // no more bits here, boss. any more would produce extra digits
-        return vec![0];
+fn disassemble(program: &[i64]) -> String {
+    program.chunks(2).enumerate().map(|(i, insn)| {
+        let (opcode, operand) = (insn[0], *insn.get(1).unwrap_or(&0));
+        let shown = if has_combo(opcode) { combo_name(operand) } else { operand.to_string() };
+        let mnemonic = MNEMONICS.get(opcode as usize).unwrap_or(&"???");
+        format!("{:2}: {} {:<3} // {}\n", 2 * i, mnemonic, shown, pseudocode(opcode, operand))
+    }).collect()
+}
+
+// registers that an instruction reads and writes as bitmasks of a, b, c
+fn reads_writes(opcode: i64, operand: i64) -> (u8, u8) {
+    let combo = if has_combo(opcode) && (4 ..= 6).contains(&operand) { 1 << (operand - 4) } else { 0 };
+    match opcode {
+        0 => (1 | combo, 1),
+        1 => (2, 2),
+        2 => (combo, 2),
+        3 => (1, 0),
+        4 => (2 | 4, 2),
+        5 => (combo, 0),
+        6 => (1 | combo, 2),
+        _ => (1 | combo, 4),
     }
+}
 
-    let mut ret = Vec::new();
-    let upper = search(computer, depth + 1);
-    for u in upper {
-        for i in 0..8 {
-            let next = u + (i << (3 * depth));
-            let out = if true {
-                run([next, 0, 0], &computer.program).0
-            } else {
-                // just for my input, to double check things
-                executefast(next)
-            };
-            if out.len() < computer.program.len() {
-                continue;
-            } else {
-                assert_eq!(out.len(), computer.program.len());
-            }
-
-            if out[depth..] == computer.program[depth..] {
-                ret.push(next);
-            }
+// The solver needs one kind of program: a single loop back to the start that outputs one number
+// per round, shifts a by a constant, and sets b and c from a before it reads them so that a round
+// depends on nothing but a. Gives the shift.
+fn loop_shift(program: &[i64]) -> Option<i64> {
+    let insns = program.chunks(2).map(|i| (i[0], *i.get(1).unwrap_or(&0))).collect::<Vec<_>>();
+    let (body, last) = insns.split_at(insns.len().checked_sub(1)?);
+    if last[0] != (3, 0) {
+        return None;
+    }
+    let count = |opcode| body.iter().filter(|&&(op, _)| op == opcode).count();
+    if count(3) != 0 || count(5) != 1 || count(0) != 1 {
+        return None;
+    }
+    let mut written = 1;
+    for &(opcode, operand) in body {
+        let (reads, writes) = reads_writes(opcode, operand);
+        if reads & !written != 0 {
+            return None;
         }
+        written |= writes;
+    }
+    match body.iter().find(|&&(op, _)| op == 0) {
+        Some(&(_, shift)) if (1 ..= 3).contains(&shift) => Some(shift),
+        _ => None,
     }
-    ret
 }
 
+// the last round sees only the top bits of a, so build a from the top, one shift worth of bits
+// at a time, keeping each choice that still makes the tail of the output right
 fn solve(computer: &Computer) -> i64 {
-    // these arrive sorted, smallest first
-    let nums = search(computer, 0);
-    nums[0]
+    let shift = loop_shift(&computer.program).expect("program is not a simple output loop");
+    let program = &computer.program;
+    let mut candidates = vec![0];
+    for depth in (0..program.len()).rev() {
+        candidates = candidates.iter()
+            .flat_map(|&upper| (0..1 << shift).map(move |bits| (upper << shift) | bits))
+            .filter(|&a| run([a, 0, 0], program).0 == program[depth..])
+            .collect();
+    }
+    // a at zero would stop right after the first round
+    *candidates.iter().filter(|&&a| a != 0).min().expect("no quine")
 }
 
 struct Computer {
@@ -151,6 +169,7 @@ fn parse(file: &str) -> Computer {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let computer = parse(&file);
@@ -160,7 +179,12 @@ fn main() {
     assert_eq!(execute(&Computer { regs: [0, 29, 0], program: [1,7].to_vec() }).1[1], 26);
     assert_eq!(execute(&Computer { regs: [0, 2024, 43690], program: [4,0].to_vec() }).1[1], 44354);
     assert_eq!(execute(&Computer { regs: [117440, 0, 0], program: [0,3,5,4,3,0].to_vec() }).0, [0,3,5,4,3,0]);
+    assert_eq!(solve(&Computer { regs: [2024, 0, 0], program: [0,3,5,4,3,0].to_vec() }), 117440);
 
-    println!("{}", output_str(&computer));
-    println!("{}", solve(&computer));
+    // AOC_DISASM=1 to see what the program does first
+    if env::var("AOC_DISASM").is_ok() {
+        print!("{}", disassemble(&computer.program));
+    }
+    answer::part1(output_str(&computer));
+    answer::part2(solve(&computer));
 }
@@ -1,8 +1,9 @@
-#![feature(let_chains)]
-
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/search.rs"]
+mod search;
 use std::io::{self, BufRead};
-use std::collections::{HashSet, HashMap, BinaryHeap};
-use std::cmp::Reverse;
+use std::collections::HashSet;
 
 type Data = bool;
 type Pos = (i32, i32);
@@ -26,47 +27,24 @@ impl Map {
     }
 }
 
-type Distances = HashMap<Pos, usize>;
-
-fn dijkstra(map: &Map, start: Pos, end: Pos) -> Distances {
-    let mut heap: BinaryHeap::<(Reverse<usize>, Pos)> = BinaryHeap::new();
-    let mut distances = Distances::new();
-    heap.push((Reverse(0), start));
-    distances.insert(start, 0);
-
-    while let Some(current) = heap.pop() {
-        let (Reverse(dist_i), pi) = current;
-        if pi == end {
-            break;
-        }
-
-        let mut run = |pj: Pos, dist: usize| {
-            if dist < *distances.get(&pj).unwrap_or(&std::usize::MAX) {
-                heap.push((Reverse(dist), pj));
-                distances.insert(pj, dist);
-            }
-        };
-
-        for d in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
-            if let Some(cell) = map.at(add(pi, d)) && !cell {
-                run(add(pi, d), dist_i + 1);
-            }
-        }
-    }
-
-    distances
+fn shortest_path(map: &Map, start: Pos, end: Pos) -> Option<usize> {
+    let search = search::bfs([start], |&p| {
+        [(-1, 0), (1, 0), (0, -1), (0, 1)].into_iter()
+            .map(move |d| add(p, d))
+            .filter(|&q| map.at(q) == Some(false))
+    }, |&p| p == end);
+    search.distance(&end)
 }
 
 fn steps_after_fall(positions: &[Pos], size: i32, simulation: usize) -> Option<usize> {
     let map = Map::new(&positions[0..simulation.min(positions.len())], size);
     let end = (size - 1, size - 1);
-    let distances = dijkstra(&map, (0, 0), end);
-    distances.get(&end).map(|n| *n)
+    shortest_path(&map, (0, 0), end)
 }
 
 fn first_blocking_fall(positions: &[Pos], size: i32) -> Option<Pos> {
     let end = (size - 1, size - 1);
-    if dijkstra(&Map::new(&positions, size), (0, 0), end).contains_key(&end) {
+    if shortest_path(&Map::new(&positions, size), (0, 0), end).is_some() {
         // sample input on big map
         return None;
     }
@@ -78,7 +56,7 @@ fn first_blocking_fall(positions: &[Pos], size: i32) -> Option<Pos> {
     while lo <= hi {
         let mid = (lo + hi) / 2;
         let map = Map::new(&positions[0..=mid], size);
-        let path_found = dijkstra(&map, (0, 0), end).contains_key(&end);
+        let path_found = shortest_path(&map, (0, 0), end).is_some();
         if path_found {
             lo = mid + 1;
         } else {
@@ -95,11 +73,16 @@ fn parse(line: &str) -> Pos {
 }
 
 fn main() {
+    let _session = answer::start();
     let positions = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect::<Vec<_>>();
     println!("sample map {:?}", steps_after_fall(&positions, 7, 12));
     println!("sample map {:?}", first_blocking_fall(&positions, 7));
-    println!("{:?}", steps_after_fall(&positions, 71, 1024));
-    println!("{:?}", first_blocking_fall(&positions, 71));
+    if let Some(steps) = steps_after_fall(&positions, 71, 1024) {
+        answer::part1(steps);
+    }
+    if let Some((x, y)) = first_blocking_fall(&positions, 71) {
+        answer::part2(format!("{},{}", x, y));
+    }
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::collections::HashMap;
 
@@ -44,10 +46,11 @@ fn parse(file: &str) -> (Vec<String>, Vec<String>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let (towels, patterns) = parse(&file);
 
-    println!("{}", possible_designs(&towels, &patterns));
-    println!("{}", possible_design_ways(&towels, &patterns));
+    answer::part1(possible_designs(&towels, &patterns));
+    answer::part2(possible_design_ways(&towels, &patterns));
 }
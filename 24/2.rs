@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn safe(report: &[i32]) -> bool {
@@ -31,11 +33,12 @@ fn safe_dampened_count(reports: &[Vec<i32>]) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let reports: Vec<Vec<i32>> = io::stdin().lock().lines()
         .map(|line| line.unwrap()
              .split(" ")
              .map(|x| x.parse::<i32>().unwrap()).collect()
             ).collect();
-    println!("{}", safe_count(&reports));
-    println!("{}", safe_dampened_count(&reports));
+    answer::part1(safe_count(&reports));
+    answer::part2(safe_dampened_count(&reports));
 }
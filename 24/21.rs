@@ -1,5 +1,7 @@
 #![feature(let_chains)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -177,6 +179,7 @@ fn complexity_sum_b(codes: &[String]) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let codes = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect::<Vec<_>>();
@@ -185,6 +188,6 @@ fn main() {
     //println!("{:?}", complexity("179A")); // 68
     //println!("{:?}", complexity("456A")); // 64
     //println!("{:?}", complexity("379A")); // 64
-    println!("{:?}", complexity_sum(&codes));
-    println!("{:?}", complexity_sum_b(&codes));
+    answer::part1(complexity_sum(&codes));
+    answer::part2(complexity_sum_b(&codes));
 }
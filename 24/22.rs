@@ -1,5 +1,7 @@
 #![feature(array_windows)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, HashSet};
 
@@ -38,9 +40,10 @@ fn most_bananas(secrets: &[i64]) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let secrets: Vec<_> = io::stdin().lock().lines()
         .map(|line| line.unwrap().parse::<i64>().unwrap())
         .collect();
-    println!("{}", secret_sums(&secrets));
-    println!("{}", most_bananas(&secrets));
+    answer::part1(secret_sums(&secrets));
+    answer::part2(most_bananas(&secrets));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashMap, HashSet};
 
@@ -61,10 +63,11 @@ fn parse(line: &str) -> (String, String) {
 }
 
 fn main() {
+    let _session = answer::start();
     let connections: Vec<_> = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect();
     let (nodes, edges) = graphize(&connections);
-    println!("{}", three_sets_t(&nodes, &edges));
-    println!("{}", password(&nodes, &edges));
+    answer::part1(three_sets_t(&nodes, &edges));
+    answer::part2(password(&nodes, &edges));
 }
@@ -1,5 +1,7 @@
 #![feature(adt_const_params, let_chains, impl_trait_in_assoc_type)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::collections::HashMap;
 use std::marker::{PhantomData, ConstParamTy};
@@ -543,6 +545,7 @@ fn parse(file: &str) -> Device {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let device = parse(&file);
@@ -559,7 +562,7 @@ fn main() {
         }
         println!("}}");
     } else {
-        println!("{}", simulate_z(&device));
-        println!("{}", swapped_pairs(&device));
+        answer::part1(simulate_z(&device));
+        answer::part2(swapped_pairs(&device));
     }
 }
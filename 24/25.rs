@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 
 #[derive(Clone, Copy, PartialEq)]
@@ -38,10 +40,11 @@ fn parse(s: &str) -> Schematic {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let schematics = file.split("\n\n")
         .map(|s| parse(s))
         .collect::<Vec<_>>();
-    println!("{}", pairs_fit(&schematics));
+    answer::part1(pairs_fit(&schematics));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 
 extern crate regex;
@@ -20,8 +22,9 @@ fn multiplications(program: &str, use_do: bool) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
-    println!("{}", multiplications(&file, false));
-    println!("{}", multiplications(&file, true));
+    answer::part1(multiplications(&file, false));
+    answer::part2(multiplications(&file, true));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn search(map: &[Vec<char>], pos: (i32, i32), dir: (i32, i32), i: usize) -> usize {
@@ -84,10 +86,11 @@ fn x_mas_count(mut map: Vec<Vec<char>>) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let map: Vec<Vec<char>> = io::stdin().lock().lines()
         .map(
             |line| line.unwrap().chars().collect()
             ).collect();
-    println!("{}", xmas_count(&map));
-    println!("{}", x_mas_count(map));
+    answer::part1(xmas_count(&map));
+    answer::part2(x_mas_count(map));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::cmp::Ordering;
 
@@ -64,9 +66,10 @@ fn parse(file: &str) -> (Vec<Rule>, Vec<Update>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let (rules, updates) = parse(&file);
-    println!("{:?}", middles_of_corrects(&rules, &updates));
-    println!("{:?}", middles_of_repaired(&rules, &updates));
+    answer::part1(middles_of_corrects(&rules, &updates));
+    answer::part2(middles_of_repaired(&rules, &updates));
 }
@@ -1,5 +1,7 @@
 #![feature(let_chains)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -92,12 +94,13 @@ fn possible_obstructions(mut map: Map, mut visits: Visits, path: &Path) -> usize
 }
 
 fn main() {
+    let _session = answer::start();
     let map: Map = io::stdin().lock().lines()
         .map(|line| line.unwrap()
              .chars().collect()
             ).collect();
     let (counts, visits, path) = route_positions(
         &map, (start_pos(&map), (0, -1)), 0, Visits::new()).unwrap();
-    println!("{}", counts);
-    println!("{}", possible_obstructions(map, visits, &path));
+    answer::part1(counts);
+    answer::part2(possible_obstructions(map, visits, &path));
 }
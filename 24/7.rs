@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 // result and test values
@@ -33,9 +35,10 @@ fn parse(line: &str) -> Equation {
 }
 
 fn main() {
+    let _session = answer::start();
     let equations: Vec<Equation> = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap())
             ).collect();
-    println!("{}", total_calibration_result(&equations, false));
-    println!("{}", total_calibration_result(&equations, true));
+    answer::part1(total_calibration_result(&equations, false));
+    answer::part2(total_calibration_result(&equations, true));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -67,10 +69,11 @@ fn unique_resonant_antinodes(map: &Map) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let map: Map = io::stdin().lock().lines()
         .map(|line| line.unwrap()
              .chars().collect()
             ).collect();
-    println!("{}", unique_antinodes(&map));
-    println!("{}", unique_resonant_antinodes(&map));
+    answer::part1(unique_antinodes(&map));
+    answer::part2(unique_resonant_antinodes(&map));
 }
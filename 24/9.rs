@@ -1,5 +1,7 @@
 #![feature(let_chains)]
 
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn checksum(diskmap: &[usize]) -> usize {
@@ -158,12 +160,13 @@ fn defrag_checksum(diskmap: &[usize]) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let diskmap = io::stdin().lock().lines()
         .next().unwrap()
         .unwrap()
         .bytes()
         .map(|b| (b - b'0') as usize)
         .collect::<Vec<_>>();
-    println!("{}", checksum(&diskmap));
-    println!("{}", defrag_checksum(&diskmap));
+    answer::part1(checksum(&diskmap));
+    answer::part2(defrag_checksum(&diskmap));
 }
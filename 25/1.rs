@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::iter::repeat;
 
@@ -29,9 +31,10 @@ fn parse(inp: &str) -> i32 {
 }
 
 fn main() {
+    let _session = answer::start();
     let instructions: Vec<i32> = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect();
-    println!("{}", total_zeros(&instructions));
-    println!("{}", total_clicks(&instructions));
+    answer::part1(total_zeros(&instructions));
+    answer::part2(total_clicks(&instructions));
 }
@@ -1,42 +1,14 @@
+#[path = "../lib/answer.rs"]
+mod answer;
+#[path = "../lib/search.rs"]
+mod search;
 use std::io::{self, BufRead};
-use std::collections::{BinaryHeap, HashMap, HashSet};
-use std::cmp::Reverse;
 
 type Pos = u32;
 
-// pose to cost
-type Distances = HashMap<Pos, usize>;
-// backwards to start
-type Edges = HashMap<Pos, HashSet<Pos>>;
-
-fn dijkstra(buttons: &[u32], start: Pos) -> (Distances, Edges) {
-    let mut heap: BinaryHeap::<(Reverse<usize>, Pos)> = BinaryHeap::new(); // dist, pose
-    let mut distances = Distances::new();
-    let mut edges = Edges::new();
-    heap.push((Reverse(0), start));
-    distances.insert(start, 0);
-
-    while let Some(current) = heap.pop() {
-        let (Reverse(dist_i), pi) = current;
-
-        let mut run = |pj: Pos, dist: usize| {
-            if dist < *distances.get(&pj).unwrap_or(&std::usize::MAX) {
-                heap.push((Reverse(dist), pj));
-                distances.insert(pj, dist);
-                edges.entry(pj).or_insert(HashSet::new()).insert(pi);
-            }
-        };
-
-        for &button in buttons {
-            run(pi ^ button, dist_i + 1);
-        }
-    }
-    (distances, edges)
-}
-
 fn fewest_button_presses(lights: u32, buttons: &[u32]) -> usize {
-    let (distances, _edges) = dijkstra(buttons, 0);
-    *distances.get(&lights).expect("button sequence not found")
+    let search = search::bfs([0], |&pi: &Pos| buttons.iter().map(move |&button| pi ^ button), |&p| p == lights);
+    search.distance(&lights).expect("button sequence not found")
 }
 
 fn button_presses_summed(light_manual: &[(u32, Vec<u32>, Vec<u32>)]) -> usize {
@@ -70,8 +42,9 @@ fn parse(line: &str) -> (u32, Vec<u32>, Vec<u32>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let light_manual = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect::<Vec<_>>();
-    println!("{}", button_presses_summed(&light_manual));
+    answer::part1(button_presses_summed(&light_manual));
 }
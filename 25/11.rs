@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -42,9 +44,10 @@ fn parse(line: &str) -> (String, Vec<String>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let net: Net = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap())
             ).collect();
-    println!("{}", total_paths(&net));
-    println!("{}", total_svr_paths(&net));
+    answer::part1(total_paths(&net));
+    answer::part2(total_svr_paths(&net));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 
 type Pos = (i32, i32);
@@ -61,8 +63,9 @@ fn parse(file: &str) -> (Vec<Present>, Vec<Tree>) {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let (presents, trees) = parse(&file);
-    println!("{}", regions_fit(&presents, &trees));
+    answer::part1(regions_fit(&presents, &trees));
 }
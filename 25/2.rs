@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, Read};
 use std::iter;
 
@@ -50,9 +52,10 @@ fn parse(file: &str) -> Vec<(i64, i64)> {
 }
 
 fn main() {
+    let _session = answer::start();
     let mut file = String::new();
     io::stdin().read_to_string(&mut file).unwrap();
     let ranges = parse(file.trim());
-    println!("{:?}", sum_of_invalid(&ranges));
-    println!("{:?}", sum_of_silly(&ranges));
+    answer::part1(sum_of_invalid(&ranges));
+    answer::part2(sum_of_silly(&ranges));
 }
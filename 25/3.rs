@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashMap;
 
@@ -36,9 +38,10 @@ fn total_joltage(ratings: &[Vec<u8>], batteries: usize) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let ratings: Vec<Vec<u8>> = io::stdin().lock().lines()
         .map(|line| line.unwrap().bytes().map(|b| b - b'0').collect())
         .collect();
-    println!("{}", total_joltage(&ratings, 2));
-    println!("{}", total_joltage(&ratings, 12));
+    answer::part1(total_joltage(&ratings, 2));
+    answer::part2(total_joltage(&ratings, 12));
 }
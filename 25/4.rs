@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn paper(map: &[Vec<char>], pos: (i32, i32), dir: (i32, i32)) -> bool {
@@ -63,10 +65,11 @@ fn removable_paper(mut map: Vec<Vec<char>>) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let map: Vec<Vec<char>> = io::stdin().lock().lines()
         .map(
             |line| line.unwrap().chars().collect()
             ).collect();
-    println!("{}", forklift_accessible(&map));
-    println!("{}", removable_paper(map));
+    answer::part1(forklift_accessible(&map));
+    answer::part2(removable_paper(map));
 }
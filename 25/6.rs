@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 fn grand_total_human(input: &[String]) -> i64 {
@@ -51,9 +53,10 @@ fn grand_total_cephalopod(input: &[String]) -> i64 {
 }
 
 fn main() {
+    let _session = answer::start();
     let input = io::stdin().lock().lines()
         .map(|line| line.unwrap())
         .collect::<Vec<_>>();
-    println!("{}", grand_total_human(&input));
-    println!("{}", grand_total_cephalopod(&input));
+    answer::part1(grand_total_human(&input));
+    answer::part2(grand_total_cephalopod(&input));
 }
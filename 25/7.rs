@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::HashSet;
 use std::collections::HashMap;
@@ -48,9 +50,10 @@ fn total_timelines(input: &Map) -> usize {
 }
 
 fn main() {
+    let _session = answer::start();
     let input = io::stdin().lock().lines()
         .map(|line| line.unwrap().chars().collect::<Vec<_>>())
         .collect::<Vec<_>>();
-    println!("{}", total_splits(&input));
-    println!("{}", total_timelines(&input));
+    answer::part1(total_splits(&input));
+    answer::part2(total_timelines(&input));
 }
@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 use std::collections::{HashSet, HashMap};
 
@@ -97,9 +99,10 @@ fn parse(line: &str) -> Point {
 }
 
 fn main() {
+    let _session = answer::start();
     let boxes = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect::<Vec<_>>();
-    println!("{:?}", three_largest_mul(&boxes, 10));
-    println!("{:?}", last_connection_xs(&boxes));
+    answer::part1(three_largest_mul(&boxes, 10));
+    answer::part2(last_connection_xs(&boxes));
 }
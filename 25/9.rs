@@ -1,3 +1,5 @@
+#[path = "../lib/answer.rs"]
+mod answer;
 use std::io::{self, BufRead};
 
 type Pos = (i64, i64);
@@ -71,9 +73,10 @@ fn parse(line: &str) -> Pos {
 }
 
 fn main() {
+    let _session = answer::start();
     let tiles = io::stdin().lock().lines()
         .map(|line| parse(&line.unwrap()))
         .collect::<Vec<_>>();
-    println!("{}", greatest_area(&tiles));
-    println!("{}", greatest_noncrossing_area(&tiles));
+    answer::part1(greatest_area(&tiles));
+    answer::part2(greatest_noncrossing_area(&tiles));
 }
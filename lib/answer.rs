@@ -0,0 +1,101 @@
+// Puzzle answers go through here instead of plain println!s. By default they're printed as they
+// come, but with AOC_OUTPUT=json the answers are collected and printed on the last line like
+//
+// {"year":2019,"day":5,"part1":5577461,"part2":7161591,"elapsed_ms":12}
+//
+// for tooling. Other (debug) output is left alone, so take the last line. Year and day come from
+// the binary path, e.g. 19/5. When the answer is letters drawn on a screen, the answer is the
+// picture itself: '#' for lit and '.' for dark, each row ending in a newline.
+#![allow(dead_code)]
+
+use std::env;
+use std::fmt::Display;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+static ANSWERS: Mutex<[Option<String>; 2]> = Mutex::new([None, None]);
+static PREFIX: Mutex<&str> = Mutex::new("");
+
+fn json_mode() -> bool {
+    env::var("AOC_OUTPUT").map(|v| v == "json").unwrap_or(false)
+}
+
+fn report(part: usize, value: String) {
+    if json_mode() {
+        ANSWERS.lock().unwrap()[part] = Some(value);
+    } else {
+        println!("{}{}", PREFIX.lock().unwrap(), value);
+    }
+}
+
+pub fn part1<T: Display>(value: T) {
+    report(0, value.to_string());
+}
+
+pub fn part2<T: Display>(value: T) {
+    report(1, value.to_string());
+}
+
+// keep this alive in main for the json line and its timing
+pub struct Session {
+    start: Instant,
+}
+
+pub fn start() -> Session {
+    Session { start: Instant::now() }
+}
+
+// for days that print a picture or a graph; "# " keeps the answers as comments in pbm and dot
+pub fn start_commented(prefix: &'static str) -> Session {
+    *PREFIX.lock().unwrap() = prefix;
+    start()
+}
+
+// "19/5" -> (2019, 5)
+fn year_and_day() -> (Option<u32>, Option<u32>) {
+    let arg0 = env::args().next().unwrap_or_default();
+    let path = Path::new(&arg0);
+    let name = |p: Option<&Path>| p.and_then(|p| p.file_name()).and_then(|n| n.to_str())
+        .and_then(|n| n.parse::<u32>().ok());
+    (name(path.parent()).map(|y| 2000 + y), name(Some(path)))
+}
+
+fn json_value(value: &Option<String>) -> String {
+    match value {
+        None => "null".to_string(),
+        Some(v) if v.parse::<i128>().is_ok() => v.clone(),
+        Some(v) => {
+            let mut s = String::from("\"");
+            for ch in v.chars() {
+                match ch {
+                    '"' => s.push_str("\\\""),
+                    '\\' => s.push_str("\\\\"),
+                    '\n' => s.push_str("\\n"),
+                    c if (c as u32) < 0x20 => s.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => s.push(c),
+                }
+            }
+            s.push('"');
+            s
+        }
+    }
+}
+
+fn json_number(value: Option<u32>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_else(|| "null".to_string())
+}
+
+impl Drop for Session {
+    fn drop(&mut self) {
+        if !json_mode() {
+            return;
+        }
+        let (year, day) = year_and_day();
+        let answers = ANSWERS.lock().unwrap_or_else(|e| e.into_inner());
+        println!("{{\"year\":{},\"day\":{},\"part1\":{},\"part2\":{},\"elapsed_ms\":{}}}",
+            json_number(year), json_number(day),
+            json_value(&answers[0]), json_value(&answers[1]),
+            self.start.elapsed().as_millis());
+    }
+}
@@ -0,0 +1,197 @@
+// The point covered by the most shapes at once, like the spot in range of the most nanobots.
+// Branch and bound over boxes: split space in halves, count the shapes that touch each half and go
+// into a half only if that many could still beat the best point found so far. The count of a box
+// is at least the count of any point in it, so the answer is exact as long as intersects() never
+// says no to a box that the shape does touch; saying yes too often only makes it slower. Ties go
+// to the point nearest to something, given as a distance that's at most that of any cell in a box.
+#![allow(dead_code)]
+
+use crate::interval::{Block, Interval};
+
+pub type Aabb = Block<3>;
+pub type Point = [i64; 3];
+
+pub trait Shape {
+    fn contains(&self, p: Point) -> bool;
+    // if any cell of the box might be in the shape; false only when surely not
+    fn intersects(&self, b: &Aabb) -> bool;
+    // all of the shape is in here
+    fn bounds(&self) -> Aabb;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Norm {
+    // manhattan
+    L1,
+    // euclidean, squared to stay in integers
+    L2,
+    // the biggest of the axes, as a king moves
+    Linf,
+}
+
+impl Norm {
+    pub fn len(&self, d: Point) -> i64 {
+        match self {
+            Norm::L1 => d.iter().map(|x| x.abs()).sum(),
+            Norm::L2 => d.iter().map(|x| x * x).sum(),
+            Norm::Linf => d.iter().map(|x| x.abs()).max().unwrap(),
+        }
+    }
+
+    // what len() says at distance r
+    fn radius(&self, r: i64) -> i64 {
+        if *self == Norm::L2 { r * r } else { r }
+    }
+}
+
+pub fn unit(p: Point) -> Aabb {
+    Block(p.map(|x| Interval::at(x, 1)))
+}
+
+// the cell of the box closest to p; the same one in all these norms because they go axis by axis
+pub fn nearest(b: &Aabb, p: Point) -> Point {
+    [0, 1, 2].map(|a| p[a].clamp(b.0[a].lo, b.0[a].last()))
+}
+
+pub fn distance(b: &Aabb, p: Point, norm: Norm) -> i64 {
+    let q = nearest(b, p);
+    norm.len([0, 1, 2].map(|a| q[a] - p[a]))
+}
+
+// the cells at most r away from the center: an octahedron in L1, a cube in Linf, a sphere in L2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ball {
+    pub center: Point,
+    pub r: i64,
+    pub norm: Norm,
+}
+
+impl Ball {
+    pub fn octahedron(center: Point, r: i64) -> Self {
+        Ball { center, r, norm: Norm::L1 }
+    }
+
+    pub fn cube(center: Point, r: i64) -> Self {
+        Ball { center, r, norm: Norm::Linf }
+    }
+
+    pub fn sphere(center: Point, r: i64) -> Self {
+        Ball { center, r, norm: Norm::L2 }
+    }
+}
+
+impl Shape for Ball {
+    fn contains(&self, p: Point) -> bool {
+        self.norm.len([0, 1, 2].map(|a| p[a] - self.center[a])) <= self.norm.radius(self.r)
+    }
+
+    // exact: the box touches the ball iff its nearest cell is in it
+    fn intersects(&self, b: &Aabb) -> bool {
+        distance(b, self.center, self.norm) <= self.norm.radius(self.r)
+    }
+
+    fn bounds(&self) -> Aabb {
+        Block(self.center.map(|x| Interval::inclusive(x - self.r, x + self.r)))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Best {
+    pub point: Point,
+    pub count: usize,
+    pub dist: i64,
+}
+
+pub fn bounds<S: Shape>(shapes: &[S]) -> Aabb {
+    shapes.iter().map(|s| s.bounds()).reduce(|a, b| a.hull(&b)).expect("no shapes")
+}
+
+// the count and the distance negated so that bigger is better
+type Score = (usize, i64);
+
+struct Search<'a, S, F> {
+    shapes: &'a [S],
+    dist: F,
+    best: Score,
+    point: Option<Point>,
+}
+
+impl<'a, S: Shape, F: Fn(&Aabb) -> i64> Search<'a, S, F> {
+    // overly optimistic, the count of the whole box; the count of any point in it is at most this
+    fn at_most(&self, b: &Aabb) -> Score {
+        (self.shapes.iter().filter(|s| s.intersects(b)).count(), -(self.dist)(b))
+    }
+
+    // find the best point in this box if it's better than what's known already
+    fn split(&mut self, b: &Aabb) {
+        if b.0.iter().all(|iv| iv.len() == 1) {
+            let p = b.0.map(|iv| iv.lo);
+            let score = (self.shapes.iter().filter(|s| s.contains(p)).count(), -(self.dist)(b));
+            if score > self.best {
+                self.best = score;
+                self.point = Some(p);
+            }
+            return;
+        }
+        // just a spatial median split on the longest side, the first of them on a tie; effective
+        // enough. the odd middle cell goes to the lower half
+        let axis = (0..3).rev().max_by_key(|&a| b.0[a].len()).unwrap();
+        let iv = b.0[axis];
+        let (lo, hi) = b.split(axis, iv.lo + (iv.len() + 1) / 2);
+        let (lo, hi) = (lo.unwrap(), hi.unwrap());
+        let (lo_score, hi_score) = (self.at_most(&lo), self.at_most(&hi));
+        // start with the better looking guess; once that's done, the other may not be worth it
+        // anymore even if it looked good enough before
+        let halves = if lo_score >= hi_score {
+            [(lo, lo_score), (hi, hi_score)]
+        } else {
+            [(hi, hi_score), (lo, lo_score)]
+        };
+        for (half, at_most) in halves {
+            if at_most > self.best {
+                self.split(&half);
+            }
+        }
+    }
+}
+
+// none if no shape covers any point, such as when there are no shapes at all
+pub fn best_point<S: Shape>(shapes: &[S], dist: impl Fn(&Aabb) -> i64) -> Option<Best> {
+    if shapes.is_empty() {
+        return None;
+    }
+    let universe = bounds(shapes);
+    // ignore the distances first to avoid going too deep in a very small corner even when the
+    // count wouldn't be the best one in the universe
+    let mut counting = Search { shapes, dist: |_: &Aabb| 0, best: (0, 0), point: None };
+    counting.split(&universe);
+    let count = counting.best.0;
+    // now prune all but the boxes with the best possible count and figure out the distance
+    let mut nearest = Search { shapes, dist, best: (count.saturating_sub(1), 0), point: None };
+    nearest.split(&universe);
+    let point = nearest.point.filter(|_| count > 0)?;
+    assert!(nearest.best.0 == count);
+    Some(Best { point, count, dist: -nearest.best.1 })
+}
+
+// every cell within the bounds, to check the above against on small inputs
+pub fn best_point_brute<S: Shape>(shapes: &[S], dist: impl Fn(&Aabb) -> i64) -> Option<Best> {
+    if shapes.is_empty() {
+        return None;
+    }
+    let [x, y, z] = bounds(shapes).0;
+    let mut best: Option<Best> = None;
+    for px in x.lo..x.hi {
+        for py in y.lo..y.hi {
+            for pz in z.lo..z.hi {
+                let point = [px, py, pz];
+                let count = shapes.iter().filter(|s| s.contains(point)).count();
+                let d = dist(&unit(point));
+                if best.map_or(true, |b| (count, -d) > (b.count, -b.dist)) {
+                    best = Some(Best { point, count, dist: d });
+                }
+            }
+        }
+    }
+    best.filter(|b| b.count > 0)
+}
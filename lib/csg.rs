@@ -0,0 +1,300 @@
+// Solids made of axis-aligned boxes, and the boolean operations between them. A solid is kept as
+// disjoint boxes, so its volume is just a sum; adding a box adds only the parts of it that aren't
+// there yet, and cutting one out leaves at most six slabs of each box it hits. The boxes live in
+// a k-d tree that cuts them at its planes as it grows, so each leaf only ever holds boxes inside
+// it and any query only looks at a few leaves, however many regions overlap.
+#![allow(dead_code)]
+
+use crate::interval::{Block, Interval};
+use std::collections::HashSet;
+
+pub type Cuboid = Block<3>;
+
+// split a leaf once it has more than this many boxes
+const LEAF_SIZE: usize = 16;
+
+pub fn cuboid(lo: [i64; 3], hi: [i64; 3]) -> Cuboid {
+    Block([0, 1, 2].map(|a| Interval::new(lo[a], hi[a])))
+}
+
+// as the inputs usually say them, last corner included
+pub fn inclusive(lo: [i64; 3], last: [i64; 3]) -> Cuboid {
+    Block([0, 1, 2].map(|a| Interval::inclusive(lo[a], last[a])))
+}
+
+// one cell
+pub fn unit(p: [i64; 3]) -> Cuboid {
+    cuboid(p, p.map(|x| x + 1))
+}
+
+// of the side perpendicular to the axis
+pub fn face_area(b: &Cuboid, axis: usize) -> i64 {
+    (0..3).filter(|&a| a != axis).map(|a| b.0[a].len()).product()
+}
+
+pub fn surface(b: &Cuboid) -> i64 {
+    2 * (0..3).map(|axis| face_area(b, axis)).sum::<i64>()
+}
+
+// the cells just outside a side of the box; anything that overlaps this touches that side
+fn slab(b: &Cuboid, axis: usize, up: bool) -> Cuboid {
+    let mut s = *b;
+    let iv = b.0[axis];
+    s.0[axis] = if up { Interval::at(iv.hi, 1) } else { Interval::at(iv.lo - 1, 1) };
+    s
+}
+
+// all the inclusive corners, like the box was made of cells
+pub fn corners(b: &Cuboid) -> [[i64; 3]; 8] {
+    let [x, y, z] = b.0;
+    [
+        [x.lo, y.lo, z.lo], [x.last(), y.lo, z.lo], [x.last(), y.lo, z.last()], [x.lo, y.lo, z.last()],
+        [x.lo, y.last(), z.lo], [x.last(), y.last(), z.lo], [x.last(), y.last(), z.last()], [x.lo, y.last(), z.last()],
+    ]
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf(Vec<Cuboid>),
+    Split { axis: usize, at: i64, below: Box<Node>, above: Box<Node> },
+}
+
+impl Node {
+    fn add(&mut self, b: Cuboid) {
+        match self {
+            Node::Split { axis, at, below, above } => {
+                let (lo, hi) = b.split(*axis, *at);
+                if let Some(lo) = lo {
+                    below.add(lo);
+                }
+                if let Some(hi) = hi {
+                    above.add(hi);
+                }
+            },
+            Node::Leaf(boxes) => {
+                let mut parts = vec![b];
+                for old in boxes.iter() {
+                    if parts.iter().any(|p| p.intersect(old).is_some()) {
+                        parts = parts.into_iter().flat_map(|p| p.difference(old)).collect();
+                    }
+                }
+                boxes.extend(parts);
+                if boxes.len() > LEAF_SIZE {
+                    self.try_split();
+                }
+            },
+        }
+    }
+
+    // at the median of the box edges along the axis where they're spread the most; nothing to do
+    // if no plane would have boxes on both sides
+    fn try_split(&mut self) {
+        let Node::Leaf(boxes) = self else {
+            return;
+        };
+        let plane = (0..3).filter_map(|axis| {
+            let mut edges = boxes.iter().flat_map(|b| [b.0[axis].lo, b.0[axis].hi]).collect::<Vec<_>>();
+            edges.sort_unstable();
+            let (first, last) = (edges[0], edges[edges.len() - 1]);
+            let at = edges[edges.len() / 2];
+            (at > first && at < last).then(|| (last - first, axis, at))
+        }).max();
+        if let Some((_, axis, at)) = plane {
+            let mut below = Node::Leaf(Vec::new());
+            let mut above = Node::Leaf(Vec::new());
+            for b in boxes.drain(..) {
+                let (lo, hi) = b.split(axis, at);
+                // already disjoint, so these can go straight in
+                if let Some(lo) = lo {
+                    below.push(lo);
+                }
+                if let Some(hi) = hi {
+                    above.push(hi);
+                }
+            }
+            *self = Node::Split { axis, at, below: Box::new(below), above: Box::new(above) };
+        }
+    }
+
+    fn push(&mut self, b: Cuboid) {
+        if let Node::Leaf(boxes) = self {
+            boxes.push(b);
+        }
+    }
+
+    fn remove(&mut self, b: &Cuboid) {
+        match self {
+            Node::Split { axis, at, below, above } => {
+                let (lo, hi) = b.split(*axis, *at);
+                if let Some(lo) = lo {
+                    below.remove(&lo);
+                }
+                if let Some(hi) = hi {
+                    above.remove(&hi);
+                }
+            },
+            Node::Leaf(boxes) => {
+                if boxes.iter().any(|old| old.intersect(b).is_some()) {
+                    *boxes = boxes.iter().flat_map(|old| old.difference(b)).collect();
+                }
+            },
+        }
+    }
+
+    fn overlapping(&self, b: &Cuboid, out: &mut Vec<Cuboid>) {
+        match self {
+            Node::Split { axis, at, below, above } => {
+                let (lo, hi) = b.split(*axis, *at);
+                if let Some(lo) = lo {
+                    below.overlapping(&lo, out);
+                }
+                if let Some(hi) = hi {
+                    above.overlapping(&hi, out);
+                }
+            },
+            Node::Leaf(boxes) => {
+                out.extend(boxes.iter().filter(|old| old.intersect(b).is_some()));
+            },
+        }
+    }
+
+    fn boxes(&self, out: &mut Vec<Cuboid>) {
+        match self {
+            Node::Split { below, above, .. } => {
+                below.boxes(out);
+                above.boxes(out);
+            },
+            Node::Leaf(boxes) => out.extend(boxes),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Solid {
+    root: Node,
+}
+
+impl Solid {
+    pub fn new() -> Self {
+        Solid { root: Node::Leaf(Vec::new()) }
+    }
+
+    // union with one box
+    pub fn add(&mut self, b: Cuboid) {
+        if !b.is_empty() {
+            self.root.add(b);
+        }
+    }
+
+    // difference with one box
+    pub fn remove(&mut self, b: &Cuboid) {
+        self.root.remove(b);
+    }
+
+    // the pieces of this that are at least partly in b, as they're stored
+    pub fn overlapping(&self, b: &Cuboid) -> Vec<Cuboid> {
+        let mut out = Vec::new();
+        self.root.overlapping(b, &mut out);
+        out
+    }
+
+    pub fn boxes(&self) -> Vec<Cuboid> {
+        let mut out = Vec::new();
+        self.root.boxes(&mut out);
+        out
+    }
+
+    pub fn volume(&self) -> i64 {
+        self.boxes().iter().map(|b| b.volume()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.boxes().is_empty()
+    }
+
+    pub fn contains(&self, p: [i64; 3]) -> bool {
+        !self.overlapping(&unit(p)).is_empty()
+    }
+
+    pub fn bounds(&self) -> Option<Cuboid> {
+        self.boxes().into_iter().reduce(|a, b| a.hull(&b))
+    }
+
+    pub fn union(&self, other: &Solid) -> Solid {
+        let mut out = self.clone();
+        for b in other.boxes() {
+            out.add(b);
+        }
+        out
+    }
+
+    pub fn difference(&self, other: &Solid) -> Solid {
+        let mut out = self.clone();
+        for b in other.boxes() {
+            out.remove(&b);
+        }
+        out
+    }
+
+    pub fn intersection(&self, other: &Solid) -> Solid {
+        let mut out = Solid::new();
+        for b in other.boxes() {
+            for mine in self.overlapping(&b) {
+                out.add(mine.intersect(&b).unwrap());
+            }
+        }
+        out
+    }
+
+    // all the outside faces; the pieces count as one where they touch each other
+    pub fn surface_area(&self) -> i64 {
+        let boxes = self.boxes();
+        let total = boxes.iter().map(surface).sum::<i64>();
+        let touching = boxes.iter().map(|b| {
+            (0..3).map(|axis| {
+                self.overlapping(&slab(b, axis, true)).iter()
+                    .map(|other| face_area(&other.intersect(&slab(b, axis, true)).unwrap(), axis))
+                    .sum::<i64>()
+            }).sum::<i64>()
+        }).sum::<i64>();
+        // both sides of each contact
+        total - 2 * touching
+    }
+
+    // the part that can be reached from the cell p through faces; edges and corners don't count
+    pub fn connected(&self, p: [i64; 3]) -> Solid {
+        let mut seen = HashSet::new();
+        let mut stack = self.overlapping(&unit(p));
+        let mut out = Solid::new();
+        while let Some(b) = stack.pop() {
+            if !seen.insert(b) {
+                continue;
+            }
+            out.add(b);
+            for axis in 0..3 {
+                for up in [false, true] {
+                    stack.extend(self.overlapping(&slab(&b, axis, up)).into_iter().filter(|n| !seen.contains(n)));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl From<Cuboid> for Solid {
+    fn from(b: Cuboid) -> Self {
+        let mut s = Solid::new();
+        s.add(b);
+        s
+    }
+}
+
+impl FromIterator<Cuboid> for Solid {
+    fn from_iter<I: IntoIterator<Item = Cuboid>>(iter: I) -> Self {
+        let mut s = Solid::new();
+        for b in iter {
+            s.add(b);
+        }
+        s
+    }
+}
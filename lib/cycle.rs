@@ -0,0 +1,177 @@
+// Things that go around in circles, and how far ahead they can be skipped once they do. Three
+// ways to notice a loop: a signal of per-step values (like height gained) that starts repeating
+// itself after an unknown settling time, a full history of hashable states where the first
+// repeated state pins the loop down exactly, and Brent's tortoise and hare for when even keeping
+// the history around is too much.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+// the first lap begins after prefix steps and takes period steps; step prefix + period is the
+// same as step prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    pub prefix: usize,
+    pub period: usize,
+}
+
+impl Repeat {
+    // the step before the second lap that looks just like step n
+    pub fn index(&self, n: usize) -> usize {
+        if n < self.prefix + self.period {
+            n
+        } else {
+            self.prefix + (n - self.prefix) % self.period
+        }
+    }
+
+    // for a metric that grows by the same amount on every lap, like a running total; it needs to
+    // be known for steps up to and including prefix + period
+    pub fn extrapolate_to(&self, n: usize, metric: impl Fn(usize) -> i64) -> i64 {
+        if n < self.prefix + self.period {
+            return metric(n);
+        }
+        let laps = (n - self.prefix) / self.period;
+        let gain = metric(self.prefix + self.period) - metric(self.prefix);
+        metric(self.index(n)) + laps as i64 * gain
+    }
+}
+
+// every state seen so far, indexed by step
+pub struct History<S> {
+    pub states: Vec<S>,
+    seen: HashMap<S, usize>,
+    pub repeat: Option<Repeat>,
+}
+
+impl<S: Clone + Eq + Hash> History<S> {
+    pub fn new() -> Self {
+        History { states: Vec::new(), seen: HashMap::new(), repeat: None }
+    }
+
+    // all the way from the start until something repeats
+    pub fn run(x0: S, mut f: impl FnMut(&S) -> S) -> Self {
+        let mut history = Self::new();
+        let mut state = x0;
+        loop {
+            let next = f(&state);
+            if history.feed(state).is_some() {
+                return history;
+            }
+            state = next;
+        }
+    }
+
+    // the state after len() steps; the repeat is found when this has been fed before, and then
+    // it's not stored again
+    pub fn feed(&mut self, state: S) -> Option<Repeat> {
+        if let Some(&first) = self.seen.get(&state) {
+            let repeat = Repeat { prefix: first, period: self.states.len() - first };
+            self.repeat = Some(repeat);
+            return Some(repeat);
+        }
+        self.seen.insert(state.clone(), self.states.len());
+        self.states.push(state);
+        None
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn extrapolate_to(&self, n: usize) -> &S {
+        match self.repeat {
+            Some(repeat) => &self.states[repeat.index(n)],
+            None => &self.states[n],
+        }
+    }
+}
+
+// only two states in memory at a time, at the cost of stepping everything about three times
+pub fn brent<S: Clone + Eq>(x0: S, mut f: impl FnMut(&S) -> S) -> Repeat {
+    // the hare runs ahead in powers of two and the tortoise waits for it at each
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = f(&hare);
+        period += 1;
+    }
+
+    // then a period apart from the start, they meet where the first lap begins
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..period {
+        hare = f(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+    Repeat { prefix, period }
+}
+
+// a signal that settles into a loop sooner or later
+pub struct CycledSignal<T>(pub Vec<T>);
+
+// the period and the last full lap of values
+pub struct Cycle<'a, T>(pub usize, pub &'a [T]);
+
+impl<T: Eq>
+CycledSignal<T> {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn test(&self, window: usize, n: usize) -> bool {
+        if window * n == 0 || self.0.len() < window * n {
+            false
+        } else {
+            // sanity check: len 8, 4 windows of size 2 each, windows begin at 0
+            let begin = &self.0[self.0.len() - n * window..];
+            (0..n-1).all(|i| {
+                let left  = &begin[(i+0) * window..(i+1) * window];
+                let right = &begin[(i+1) * window..(i+2) * window];
+                left == right
+            })
+        }
+    }
+
+    pub fn feed_and_test<'a>(&'a mut self, v: T) -> Option<Cycle<'a, T>> {
+        // now a cycle, if any, ends at this index; another begins at len()
+        self.0.push(v);
+        // test at least a few cycles to be sure, maybe a big cycle includes subcycles?
+        // also ignore the first half to allow the signal to settle
+        let four = (self.0.len() / 2) / 4;
+        let three = (self.0.len() / 2) / 3;
+        (four..three)
+            .find(|&len| self.test(len, 3))
+            .map(|len| Cycle(len, &self.0[self.0.len() - len ..]))
+    }
+}
+
+impl<'a, T: Copy + From<i64> + std::ops::Add<Output = T> + std::ops::Mul<Output = T> + std::iter::Sum>
+Cycle<'a, T> {
+    // the sum of the part between [start, end), with start the step after the last one fed
+    pub fn extrapolate(&self, start: usize, end: usize) -> T {
+        let ncycles = (end - start) / self.0;
+        let remaining = (end - start) % self.0;
+
+        T::from(ncycles as i64) * self.1.iter().copied().sum::<T>()
+            + self.1.iter().copied().take(remaining).sum::<T>()
+    }
+
+    // just the value at end, same start
+    pub fn value_at(&self, start: usize, end: usize) -> T {
+        self.1[(end - start) % self.0]
+    }
+}
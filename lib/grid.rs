@@ -0,0 +1,193 @@
+// The rectangular map that so many days read from their input, with (x, y) positions where y
+// grows downwards. Turning right is clockwise on the screen then.
+#![allow(dead_code)]
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+pub type Pos = (i32, i32);
+
+// right, down, left, up; each is a right turn from the previous one
+pub const DIRS4: [Pos; 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+// and the diagonals in between, also in clockwise order
+pub const DIRS8: [Pos; 8] = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+
+pub fn add(a: Pos, b: Pos) -> Pos {
+    (a.0 + b.0, a.1 + b.1)
+}
+
+pub fn sub(a: Pos, b: Pos) -> Pos {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+pub fn neg(a: Pos) -> Pos {
+    (-a.0, -a.1)
+}
+
+pub fn mul(k: i32, a: Pos) -> Pos {
+    (k * a.0, k * a.1)
+}
+
+pub fn manhattan(a: Pos, b: Pos) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+pub fn right(p: Pos) -> Pos {
+    (-p.1, p.0)
+}
+
+pub fn left(p: Pos) -> Pos {
+    (p.1, -p.0)
+}
+
+// the same turns under the names some days prefer
+pub fn cw(p: Pos) -> Pos {
+    right(p)
+}
+
+pub fn ccw(p: Pos) -> Pos {
+    left(p)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Map<T = char>(pub Vec<Vec<T>>);
+
+impl Map<char> {
+    pub fn parse<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> Self {
+        Map(lines.into_iter().map(|l| l.as_ref().chars().collect()).collect())
+    }
+}
+
+impl<T> Map<T> {
+    pub fn new(v: Vec<Vec<T>>) -> Self {
+        Self(v)
+    }
+
+    pub fn filled(w: i32, h: i32, value: T) -> Self where T: Clone {
+        Map(vec![vec![value; w as usize]; h as usize])
+    }
+
+    pub fn w(&self) -> i32 {
+        self.0.first().map_or(0, |row| row.len()) as i32
+    }
+
+    pub fn h(&self) -> i32 {
+        self.0.len() as i32
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        p.0 >= 0 && p.0 < self.w() && p.1 >= 0 && p.1 < self.h()
+    }
+
+    pub fn at(&self, p: Pos) -> Option<&T> {
+        if self.contains(p) {
+            Some(&self.0[p.1 as usize][p.0 as usize])
+        } else {
+            None
+        }
+    }
+
+    pub fn at_mut(&mut self, p: Pos) -> Option<&mut T> {
+        if self.contains(p) {
+            Some(&mut self.0[p.1 as usize][p.0 as usize])
+        } else {
+            None
+        }
+    }
+
+    // as if the map repeated forever in every direction
+    pub fn at_wrapping(&self, p: Pos) -> &T {
+        &self.0[p.1.rem_euclid(self.h()) as usize][p.0.rem_euclid(self.w()) as usize]
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let (w, h) = (self.w(), self.h());
+        (0..h).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+
+    // the ones that are in the map
+    pub fn neighbors4(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS4.iter().map(move |&d| add(p, d)).filter(move |&q| self.contains(q))
+    }
+
+    pub fn neighbors8(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRS8.iter().map(move |&d| add(p, d)).filter(move |&q| self.contains(q))
+    }
+
+    pub fn map<U>(&self, f: impl Fn(Pos, &T) -> U) -> Map<U> {
+        Map(self.0.iter().enumerate().map(|(y, row)| {
+            row.iter().enumerate().map(|(x, v)| f((x as i32, y as i32), v)).collect()
+        }).collect())
+    }
+}
+
+impl<T: Clone> Map<T> {
+    // rows become columns
+    pub fn transpose(&self) -> Self {
+        Map((0..self.w() as usize).map(|x| self.0.iter().map(|row| row[x].clone()).collect()).collect())
+    }
+
+    // the top row ends up as the rightmost column
+    pub fn rotate_cw(&self) -> Self {
+        let mut t = self.transpose();
+        t.0.iter_mut().for_each(|row| row.reverse());
+        t
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        let mut t = self.transpose();
+        t.0.reverse();
+        t
+    }
+}
+
+impl<T: Copy> Map<T> {
+    pub fn get(&self, p: Pos) -> Option<T> {
+        self.at(p).copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, T)> + '_ {
+        self.0.iter().enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(x, &h)| ((x as i32, y as i32), h))
+            })
+    }
+}
+
+impl<T: Copy + PartialEq> Map<T> {
+    pub fn find(&self, value: T) -> Option<Pos> {
+        self.iter().find(|&(_, v)| v == value).map(|(p, _)| p)
+    }
+
+    pub fn find_all(&self, value: T) -> impl Iterator<Item = Pos> + '_ {
+        self.iter().filter(move |&(_, v)| v == value).map(|(p, _)| p)
+    }
+}
+
+impl<T> Index<Pos> for Map<T> {
+    type Output = T;
+    fn index(&self, p: Pos) -> &Self::Output {
+        self.at(p).unwrap()
+    }
+}
+
+impl<T> IndexMut<Pos> for Map<T> {
+    fn index_mut(&mut self, p: Pos) -> &mut T {
+        self.at_mut(p).unwrap()
+    }
+}
+
+// a line per row, each cell as its Display says; fine for chars and single digits
+impl<T: fmt::Display> fmt::Display for Map<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in &self.0 {
+            for v in row {
+                write!(f, "{}", v)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
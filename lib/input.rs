@@ -0,0 +1,19 @@
+// Puzzle input for the 2016-2018 days: stdin like the later years, or the file named on the
+// command line like these used to take it.
+#![allow(dead_code)]
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+
+pub fn reader() -> Box<dyn BufRead> {
+    match std::env::args().nth(1) {
+        Some(path) => Box::new(BufReader::new(File::open(path).unwrap())),
+        None => Box::new(io::stdin().lock()),
+    }
+}
+
+pub fn read_to_string() -> String {
+    let mut s = String::new();
+    reader().read_to_string(&mut s).unwrap();
+    s
+}
@@ -0,0 +1,284 @@
+// Ranges of integers and sets of them, for when there are too many numbers to look at one by one.
+// An interval is half-open inside, [lo, hi), because then lengths and splits need no +1 or -1;
+// the puzzles like to talk in inclusive ranges so those get their own constructor. A set keeps
+// its intervals sorted and apart, merging anything that overlaps or touches.
+#![allow(dead_code)]
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub lo: i64,
+    pub hi: i64,
+}
+
+impl Interval {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        Interval { lo, hi }
+    }
+
+    // both ends included
+    pub fn inclusive(lo: i64, last: i64) -> Self {
+        Interval { lo, hi: last + 1 }
+    }
+
+    pub fn at(start: i64, len: i64) -> Self {
+        Interval { lo: start, hi: start + len }
+    }
+
+    pub fn last(&self) -> i64 {
+        self.hi - 1
+    }
+
+    pub fn len(&self) -> i64 {
+        (self.hi - self.lo).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hi <= self.lo
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        x >= self.lo && x < self.hi
+    }
+
+    pub fn intersect(&self, other: Interval) -> Option<Interval> {
+        let both = Interval::new(self.lo.max(other.lo), self.hi.min(other.hi));
+        (!both.is_empty()).then(|| both)
+    }
+
+    // what's below x and what's from x on
+    pub fn split_at(&self, x: i64) -> (Option<Interval>, Option<Interval>) {
+        let below = Interval::new(self.lo, self.hi.min(x));
+        let above = Interval::new(self.lo.max(x), self.hi);
+        ((!below.is_empty()).then(|| below), (!above.is_empty()).then(|| above))
+    }
+
+    pub fn shift(&self, d: i64) -> Interval {
+        Interval::new(self.lo + d, self.hi + d)
+    }
+}
+
+// in the inclusive form, like the inputs
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.lo, self.last())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet(Vec<Interval>);
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        IntervalSet(Vec::new())
+    }
+
+    pub fn insert(&mut self, mut iv: Interval) {
+        if iv.is_empty() {
+            return;
+        }
+        // everything from start to end overlaps or touches the new one
+        let start = self.0.partition_point(|x| x.hi < iv.lo);
+        let end = self.0.partition_point(|x| x.lo <= iv.hi);
+        if start == end {
+            self.0.insert(start, iv);
+        } else {
+            iv.lo = iv.lo.min(self.0[start].lo);
+            iv.hi = iv.hi.max(self.0[end - 1].hi);
+            self.0[start] = iv;
+            self.0.drain(start + 1..end);
+        }
+    }
+
+    pub fn intervals(&self) -> &[Interval] {
+        &self.0
+    }
+
+    // how many numbers, not intervals
+    pub fn len(&self) -> i64 {
+        self.0.iter().map(|iv| iv.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        let i = self.0.partition_point(|iv| iv.hi <= x);
+        self.0.get(i).map_or(false, |iv| iv.contains(x))
+    }
+
+    pub fn first(&self) -> Option<i64> {
+        self.0.first().map(|iv| iv.lo)
+    }
+
+    pub fn last(&self) -> Option<i64> {
+        self.0.last().map(|iv| iv.last())
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = self.clone();
+        for &iv in &other.0 {
+            out.insert(iv);
+        }
+        out
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.0.len() && j < other.0.len() {
+            let (a, b) = (self.0[i], other.0[j]);
+            if let Some(both) = a.intersect(b) {
+                out.push(both);
+            }
+            // whichever ends first can't overlap anything else on the other side
+            if a.hi < b.hi {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet(out)
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut out = Vec::new();
+        for &a in &self.0 {
+            let mut lo = a.lo;
+            let first = other.0.partition_point(|b| b.hi <= a.lo);
+            for b in other.0[first..].iter().take_while(|b| b.lo < a.hi) {
+                if b.lo > lo {
+                    out.push(Interval::new(lo, b.lo));
+                }
+                lo = lo.max(b.hi);
+            }
+            if lo < a.hi {
+                out.push(Interval::new(lo, a.hi));
+            }
+        }
+        IntervalSet(out)
+    }
+
+    // the holes, within some bounds
+    pub fn complement(&self, within: Interval) -> IntervalSet {
+        IntervalSet::from(within).difference(self)
+    }
+}
+
+impl From<Interval> for IntervalSet {
+    fn from(iv: Interval) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(iv);
+        set
+    }
+}
+
+impl FromIterator<Interval> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for iv in iter {
+            set.insert(iv);
+        }
+        set
+    }
+}
+
+// piecewise shifts: numbers in the source of a piece move by its offset, everything else stays
+// put; the first piece wins if sources overlap
+#[derive(Debug, Clone, Default)]
+pub struct Translation(pub Vec<(Interval, i64)>);
+
+impl Translation {
+    pub fn new() -> Self {
+        Translation(Vec::new())
+    }
+
+    pub fn add(&mut self, source: Interval, offset: i64) {
+        self.0.push((source, offset));
+    }
+
+    pub fn apply(&self, x: i64) -> i64 {
+        self.0.iter()
+            .find(|(source, _)| source.contains(x))
+            .map_or(x, |&(_, offset)| x + offset)
+    }
+
+    pub fn apply_set(&self, set: &IntervalSet) -> IntervalSet {
+        let mut out = IntervalSet::new();
+        let mut rest = set.clone();
+        for &(source, offset) in &self.0 {
+            let moved = rest.intersection(&source.into());
+            for iv in moved.intervals() {
+                out.insert(iv.shift(offset));
+            }
+            rest = rest.difference(&moved);
+        }
+        out.union(&rest)
+    }
+}
+
+// an interval along each of D axes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Block<const D: usize>(pub [Interval; D]);
+
+impl<const D: usize> Block<D> {
+    pub fn volume(&self) -> i64 {
+        self.0.iter().map(|iv| iv.len()).product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(|iv| iv.is_empty())
+    }
+
+    pub fn contains(&self, p: [i64; D]) -> bool {
+        self.0.iter().zip(p).all(|(iv, x)| iv.contains(x))
+    }
+
+    pub fn intersect(&self, other: &Block<D>) -> Option<Block<D>> {
+        let mut out = *self;
+        for (iv, &o) in out.0.iter_mut().zip(&other.0) {
+            *iv = iv.intersect(o)?;
+        }
+        Some(out)
+    }
+
+    // the part below x along one axis and the part from x on
+    pub fn split(&self, axis: usize, x: i64) -> (Option<Block<D>>, Option<Block<D>>) {
+        let (below, above) = self.0[axis].split_at(x);
+        let with = |iv| {
+            let mut b = *self;
+            b.0[axis] = iv;
+            b
+        };
+        (below.map(with), above.map(with))
+    }
+
+    // what's left after taking the other out: cut off the slabs outside of it one axis at a
+    // time, so at most two pieces per axis
+    pub fn difference(&self, other: &Block<D>) -> Vec<Block<D>> {
+        if self.intersect(other).is_none() {
+            return vec![*self];
+        }
+        let mut out = Vec::new();
+        let mut rest = *self;
+        for axis in 0..D {
+            let (below, mid) = rest.split(axis, other.0[axis].lo);
+            out.extend(below);
+            let (mid, above) = mid.unwrap().split(axis, other.0[axis].hi);
+            out.extend(above);
+            rest = mid.unwrap();
+        }
+        out
+    }
+
+    // the smallest block that has both
+    pub fn hull(&self, other: &Block<D>) -> Block<D> {
+        let mut out = *self;
+        for (iv, o) in out.0.iter_mut().zip(&other.0) {
+            *iv = Interval::new(iv.lo.min(o.lo), iv.hi.max(o.hi));
+        }
+        out
+    }
+}
@@ -0,0 +1,446 @@
+// Cellular automata: on every step each cell looks at its neighbours and decides what it becomes,
+// all cells at once. A cell is anything hashable, so the same loop runs flat maps, hex floors,
+// cubes in four dimensions and recursively nested levels; only the neighbourhood function knows
+// what's next to what. The world is either a fixed rectangle (a grid::Map) or sparse, storing just
+// the cells that differ from the background, and the background may change too in an infinite
+// world where the rule turns empty space into something; that has a rule of its own, as empty
+// space is no particular cell.
+//
+// Not everything on a grid is this. The virus carrier of 17/22 is one walker flipping one cell
+// per burst, and the elves of 22/23 move where they propose unless another elf proposes the same
+// spot, which a cell can only see three steps away; both keep their own loops for now.
+#![allow(dead_code)]
+
+use crate::grid::{Map, Pos};
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+
+// the default hasher guards against keys crafted to collide, which puzzle inputs aren't; this one
+// mixes each word in with a rotate and a multiply like the Fx hash of rustc, and that's a lot
+// faster on small cells such as coordinate tuples
+#[derive(Default, Clone, Copy)]
+pub struct CellHasher(u64);
+
+impl CellHasher {
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x51_7c_c1_b7_27_22_0a_95);
+    }
+}
+
+impl Hasher for CellHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.add(b as u64);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    fn write_i32(&mut self, i: i32) {
+        self.add(i as u32 as u64);
+    }
+
+    fn write_i64(&mut self, i: i64) {
+        self.add(i as u64);
+    }
+}
+
+pub type CellMap<C, T> = HashMap<C, T, BuildHasherDefault<CellHasher>>;
+pub type CellSet<C> = HashSet<C, BuildHasherDefault<CellHasher>>;
+
+// east, northeast, northwest, west, southwest, southeast with (x, y) as (east, northeast)
+pub const HEX: [Pos; 6] = [(1, 0), (0, 1), (-1, 1), (-1, 0), (0, -1), (1, -1)];
+
+// every direction in D dimensions, diagonals included
+pub fn moore<const D: usize>() -> Vec<[i32; D]> {
+    let mut offsets = vec![[0; D]];
+    for axis in 0..D {
+        offsets = offsets.into_iter().flat_map(|o| {
+            [-1, 0, 1].into_iter().map(move |d| {
+                let mut o = o;
+                o[axis] = d;
+                o
+            })
+        }).collect();
+    }
+    offsets.retain(|o| o.iter().any(|&d| d != 0));
+    offsets
+}
+
+pub fn offset<const D: usize>(mut c: [i32; D], d: [i32; D]) -> [i32; D] {
+    for (a, b) in c.iter_mut().zip(d) {
+        *a += b;
+    }
+    c
+}
+
+pub trait World<C, T>: Clone {
+    // what's at c, or the background if nothing is stored there
+    fn get(&self, c: C, bg: T) -> T;
+    fn set(&mut self, c: C, value: T, bg: T);
+    // if c is a cell at all; a rectangle has edges
+    fn holds(&self, c: C) -> bool;
+    // every cell that can be something else than the background
+    fn cells(&self) -> Vec<C>;
+    // the cells in cells() are surrounded by infinitely many more
+    fn infinite(&self) -> bool;
+}
+
+impl<T: Copy> World<Pos, T> for Map<T> {
+    fn get(&self, p: Pos, bg: T) -> T {
+        self.at(p).copied().unwrap_or(bg)
+    }
+
+    fn set(&mut self, p: Pos, value: T, _bg: T) {
+        self[p] = value;
+    }
+
+    fn holds(&self, p: Pos) -> bool {
+        self.contains(p)
+    }
+
+    fn cells(&self) -> Vec<Pos> {
+        self.positions().collect()
+    }
+
+    fn infinite(&self) -> bool {
+        false
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sparse<C: Eq + Hash, T>(pub CellMap<C, T>);
+
+impl<C: Eq + Hash, T> Sparse<C, T> {
+    pub fn new() -> Self {
+        Sparse(CellMap::default())
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl<C: Eq + Hash, T: Copy> Sparse<C, T> {
+    // for the usual set of live cells
+    pub fn with(cells: impl IntoIterator<Item = C>, value: T) -> Self {
+        Sparse(cells.into_iter().map(|c| (c, value)).collect())
+    }
+}
+
+impl<C: Copy + Eq + Hash, T: Copy + PartialEq> World<C, T> for Sparse<C, T> {
+    fn get(&self, c: C, bg: T) -> T {
+        self.0.get(&c).copied().unwrap_or(bg)
+    }
+
+    fn set(&mut self, c: C, value: T, bg: T) {
+        if value == bg {
+            self.0.remove(&c);
+        } else {
+            self.0.insert(c, value);
+        }
+    }
+
+    fn holds(&self, _c: C) -> bool {
+        true
+    }
+
+    fn cells(&self) -> Vec<C> {
+        self.0.keys().copied().collect()
+    }
+
+    fn infinite(&self) -> bool {
+        true
+    }
+}
+
+// every cell that comes up gets a number, and so do its neighbours the first time it's looked at,
+// so that a step gathers what's around from flat arrays instead of asking the world and the
+// neighbourhood again; the world only hears about what changed. Number 0 stands for everything
+// outside a rectangle and holds the background.
+struct Cells<C, T> {
+    index: CellMap<C, usize>,
+    // cell i is cells[i - 1]
+    cells: Vec<C>,
+    values: Vec<T>,
+    // the neighbours of cell i are links[a..b] with spans[i] == (a, b), once it has been linked
+    spans: Vec<(u32, u32)>,
+    links: Vec<u32>,
+    marked: Vec<bool>,
+}
+
+const UNLINKED: (u32, u32) = (u32::MAX, u32::MAX);
+
+impl<C: Copy + Eq + Hash, T: Copy + PartialEq> Cells<C, T> {
+    fn new(bg: T) -> Self {
+        Cells {
+            index: CellMap::default(),
+            cells: Vec::new(),
+            values: vec![bg],
+            spans: vec![UNLINKED],
+            links: Vec::new(),
+            marked: vec![false],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn cell(&self, i: usize) -> C {
+        self.cells[i - 1]
+    }
+
+    fn number(&mut self, c: C, world: &impl World<C, T>, bg: T) -> usize {
+        if let Some(&i) = self.index.get(&c) {
+            return i;
+        }
+        if !world.holds(c) {
+            return 0;
+        }
+        let i = self.values.len();
+        self.index.insert(c, i);
+        self.cells.push(c);
+        self.values.push(world.get(c, bg));
+        self.spans.push(UNLINKED);
+        self.marked.push(false);
+        i
+    }
+
+    fn link<I: IntoIterator<Item = C>>(&mut self, i: usize, world: &impl World<C, T>, bg: T,
+                                       neighbors: &impl Fn(C) -> I) {
+        if self.spans[i] == UNLINKED {
+            let a = self.links.len() as u32;
+            for n in neighbors(self.cell(i)) {
+                let j = self.number(n, world, bg);
+                self.links.push(j as u32);
+            }
+            self.spans[i] = (a, self.links.len() as u32);
+        }
+    }
+
+    fn neighbors(&self, i: usize) -> &[u32] {
+        let (a, b) = self.spans[i];
+        &self.links[a as usize..b as usize]
+    }
+}
+
+pub struct Life<C, T, W, N> {
+    pub world: W,
+    cells: Cells<C, T>,
+    pub bg: T,
+    neighbors: N,
+    pub generation: usize,
+    // what the empty space becomes on each step; with no cell to ask, it's a rule of its own
+    bg_rule: Option<Box<dyn Fn(T) -> T>>,
+    // re-evaluate only around what changed in the previous step; this needs a neighbourhood where
+    // a is next to b whenever b is next to a, and the same rule on every step
+    tracking: bool,
+    active: Option<Vec<usize>>,
+    // cells looked at in the last step, to see what the tracking buys
+    pub evaluated: usize,
+    // when every cell of a rectangle has this many neighbours and the links of each come right
+    // after the ones of the cell before, a step that looks at everything can go through them all
+    // in order
+    dense: Option<usize>,
+    // buffers that live from step to step
+    candidates: Vec<usize>,
+    around: Vec<T>,
+    changes: Vec<(usize, T)>,
+}
+
+impl<C, T, W, N, I> Life<C, T, W, N>
+where C: Copy + Eq + Hash, T: Copy + PartialEq, W: World<C, T>, N: Fn(C) -> I, I: IntoIterator<Item = C> {
+    pub fn new(world: W, bg: T, neighbors: N) -> Self {
+        Life {
+            world,
+            cells: Cells::new(bg),
+            bg,
+            neighbors,
+            generation: 0,
+            bg_rule: None,
+            tracking: false,
+            active: None,
+            evaluated: 0,
+            dense: None,
+            candidates: Vec::new(),
+            around: Vec::new(),
+            changes: Vec::new(),
+        }
+    }
+
+    // the empty space follows a rule too, such as when nothing around turns into something
+    pub fn with_evolving_background(mut self, bg_rule: impl Fn(T) -> T + 'static) -> Self {
+        self.bg_rule = Some(Box::new(bg_rule));
+        self
+    }
+
+    pub fn tracking(mut self) -> Self {
+        self.tracking = true;
+        self
+    }
+
+    // look at every cell on the next step, such as when the rule is about to change
+    pub fn touch_all(&mut self) {
+        self.active = None;
+    }
+
+    pub fn get(&self, c: C) -> T {
+        self.world.get(c, self.bg)
+    }
+
+    pub fn count(&self, value: T) -> usize {
+        assert!(!(self.world.infinite() && value == self.bg), "infinitely many");
+        self.world.cells().into_iter().filter(|&c| self.get(c) == value).count()
+    }
+
+    // the cells to look at and everything around them, numbered and linked; true if that's all the
+    // cells of a rectangle
+    fn find_candidates(&mut self, bg_changes: bool) -> bool {
+        let around: Vec<usize> = match self.active.take() {
+            Some(active) if !bg_changes => active,
+            // a rectangle has all of its cells numbered after the first time, and the list of
+            // them may still be there from the previous step
+            _ if !self.world.infinite() && self.cells.len() > 1 => {
+                if self.candidates.len() != self.cells.len() - 1 {
+                    self.candidates.clear();
+                    self.candidates.extend(1..self.cells.len());
+                }
+                return true;
+            },
+            _ => self.world.cells().into_iter()
+                .map(|c| self.cells.number(c, &self.world, self.bg)).collect(),
+        };
+        self.candidates.clear();
+        for i in around {
+            self.cells.link(i, &self.world, self.bg, &self.neighbors);
+            for k in 0..=self.cells.neighbors(i).len() {
+                let j = if k == 0 { i } else { self.cells.neighbors(i)[k - 1] as usize };
+                if j != 0 && !self.cells.marked[j] {
+                    self.cells.marked[j] = true;
+                    self.candidates.push(j);
+                }
+            }
+        }
+        for &j in &self.candidates {
+            self.cells.marked[j] = false;
+            self.cells.link(j, &self.world, self.bg, &self.neighbors);
+        }
+        let len = self.cells.len();
+        if !self.world.infinite() && self.dense.is_none() && len > 1 && self.candidates.len() == len - 1 {
+            let k = self.cells.neighbors(1).len();
+            let spans = &self.cells.spans;
+            if (1..len).all(|i| spans[i] == (((i - 1) * k) as u32, (i * k) as u32)) {
+                self.dense = Some(k);
+            }
+        }
+        false
+    }
+
+    // the rule gets the cell, what's there now and what's around it in the order the neighbourhood
+    // gives them; returns how many cells changed. All cells see the world as it was before the
+    // step, as the new values wait in a list until everything has been looked at.
+    pub fn step(&mut self, mut rule: impl FnMut(C, T, &[T]) -> T) -> usize {
+        let bg = self.bg_rule.as_ref().map_or(self.bg, |bg_rule| bg_rule(self.bg));
+        let bg_changes = bg != self.bg;
+        let all = self.find_candidates(bg_changes);
+
+        self.changes.clear();
+        let cells = &self.cells;
+        match self.dense.filter(|&k| all && k > 0) {
+            // every cell in turn, its links the next k of them
+            Some(k) => {
+                if self.around.len() < k {
+                    self.around.resize(k, bg);
+                }
+                let around = &mut self.around[..k];
+                for ((i, links), &old) in cells.links.chunks_exact(k).enumerate().zip(&cells.values[1..]) {
+                    for (value, &j) in around.iter_mut().zip(links) {
+                        *value = cells.values[j as usize];
+                    }
+                    let new = rule(cells.cells[i], old, around);
+                    if new != old {
+                        self.changes.push((i + 1, new));
+                    }
+                }
+            },
+            None => for &i in &self.candidates {
+                let links = cells.neighbors(i);
+                if self.around.len() < links.len() {
+                    self.around.resize(links.len(), bg);
+                }
+                let around = &mut self.around[..links.len()];
+                for (value, &j) in around.iter_mut().zip(links) {
+                    *value = cells.values[j as usize];
+                }
+                let old = cells.values[i];
+                let new = rule(cells.cell(i), old, around);
+                if new != old {
+                    self.changes.push((i, new));
+                }
+            },
+        }
+        self.evaluated = self.candidates.len();
+        let changed = self.changes.len();
+
+        for &(i, new) in &self.changes {
+            self.cells.values[i] = new;
+            self.world.set(self.cells.cell(i), new, bg);
+        }
+        if bg_changes {
+            // whatever wasn't looked at was the background, and a sparse world stores everything
+            // the other way around now
+            for &i in &self.candidates {
+                self.cells.marked[i] = true;
+                self.world.set(self.cells.cell(i), self.cells.values[i], bg);
+            }
+            for i in 0..self.cells.len() {
+                if !self.cells.marked[i] {
+                    self.cells.values[i] = bg;
+                }
+                self.cells.marked[i] = false;
+            }
+        }
+        self.bg = bg;
+        self.generation += 1;
+
+        if self.tracking {
+            let mut active = self.active.take().unwrap_or_default();
+            active.clear();
+            if bg_changes {
+                active.extend_from_slice(&self.candidates);
+            } else {
+                active.extend(self.changes.iter().map(|&(i, _)| i));
+            }
+            self.active = Some(active);
+        }
+        changed
+    }
+
+    // steps taken, including the last one that didn't change anything anymore
+    pub fn run_until_still(&mut self, mut rule: impl FnMut(C, T, &[T]) -> T) -> usize {
+        let mut steps = 1;
+        while self.step(&mut rule) != 0 {
+            steps += 1;
+        }
+        steps
+    }
+}
@@ -0,0 +1,238 @@
+// The small register machines of several years have the same shape: a list of instructions, a
+// program counter and some state that the instructions mess with. Each day brings its own
+// instruction type and implements Execute for the state it runs on; this runs the loop, counts
+// how often each instruction runs, and parses the usual "op arg arg" lines.
+#![allow(dead_code)]
+
+use std::str::FromStr;
+
+// signed so that jumps can go before the start, which halts
+pub type Pc = i64;
+
+// what an instruction does to the control flow
+#[derive(Debug, PartialEq)]
+pub enum Effect<E> {
+    Next,
+    // relative to this instruction
+    Jump(i64),
+    // absolute
+    Goto(Pc),
+    // report something, then go on to the next instruction
+    Emit(E),
+    // took its time but isn't done, like a multicycle instruction; runs again
+    Stay,
+    // can't run now, like a receive with nothing to receive; runs again after the caller helps
+    Block,
+}
+
+pub trait Execute<S> {
+    type Event;
+
+    fn execute(&self, state: &mut S, pc: Pc) -> Effect<Self::Event>;
+}
+
+// why a run stopped
+#[derive(Debug, PartialEq)]
+pub enum Stop<E> {
+    // the program counter went outside the program
+    Halted,
+    Event(E),
+    Blocked,
+    StepLimit,
+    // with loop detection on, an instruction was about to run a second time
+    Repeat,
+}
+
+pub struct Machine<'a, I, S> {
+    program: &'a [I],
+    pub pc: Pc,
+    pub state: S,
+    pub steps: u64,
+    // execution count of each instruction
+    profile: Vec<u64>,
+    step_limit: Option<u64>,
+    detect_loops: bool,
+}
+
+impl<'a, I: Execute<S>, S> Machine<'a, I, S> {
+    pub fn new(program: &'a [I], state: S) -> Self {
+        Machine {
+            program,
+            pc: 0,
+            state,
+            steps: 0,
+            profile: vec![0; program.len()],
+            step_limit: None,
+            detect_loops: false,
+        }
+    }
+
+    pub fn with_step_limit(mut self, limit: u64) -> Self {
+        self.step_limit = Some(limit);
+        self
+    }
+
+    pub fn detecting_loops(mut self) -> Self {
+        self.detect_loops = true;
+        self
+    }
+
+    pub fn halted(&self) -> bool {
+        self.pc < 0 || self.pc >= self.program.len() as Pc
+    }
+
+    // one instruction; Some if that stopped the run
+    pub fn step(&mut self) -> Option<Stop<I::Event>> {
+        if self.halted() {
+            return Some(Stop::Halted);
+        }
+        if self.step_limit.map_or(false, |limit| self.steps >= limit) {
+            return Some(Stop::StepLimit);
+        }
+        let pc = self.pc as usize;
+        if self.detect_loops && self.profile[pc] > 0 {
+            return Some(Stop::Repeat);
+        }
+
+        let effect = self.program[pc].execute(&mut self.state, self.pc);
+        if let Effect::Block = effect {
+            return Some(Stop::Blocked);
+        }
+        self.steps += 1;
+        self.profile[pc] += 1;
+        match effect {
+            Effect::Next => self.pc += 1,
+            Effect::Jump(offset) => self.pc += offset,
+            Effect::Goto(target) => self.pc = target,
+            Effect::Emit(event) => {
+                self.pc += 1;
+                return Some(Stop::Event(event));
+            },
+            Effect::Stay => (),
+            Effect::Block => unreachable!(),
+        }
+        None
+    }
+
+    pub fn run(&mut self) -> Stop<I::Event> {
+        loop {
+            if let Some(stop) = self.step() {
+                return stop;
+            }
+        }
+    }
+
+    pub fn profile(&self) -> &[u64] {
+        &self.profile
+    }
+
+    // the n most run instructions as (index, count), most first
+    pub fn hot_spots(&self, n: usize) -> Vec<(usize, u64)> {
+        let mut counts = self.profile.iter().copied().enumerate().filter(|&(_, count)| count > 0).collect::<Vec<_>>();
+        counts.sort_by_key(|&(i, count)| (std::cmp::Reverse(count), i));
+        counts.truncate(n);
+        counts
+    }
+}
+
+// A register or an immediate number, the usual operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg {
+    Reg(usize),
+    Num(i64),
+}
+
+impl Arg {
+    pub fn value(&self, regs: &[i64]) -> i64 {
+        match *self {
+            Arg::Reg(reg) => regs[reg],
+            Arg::Num(num) => num,
+        }
+    }
+}
+
+// Parsers take a line and give what they parsed and the rest of the line, words separated by
+// spaces. These build the parser for a whole instruction set out of small pieces:
+//
+// alt(vec![
+//     op("set", pair(reg("abcd"), arg("abcd")), |(r, a)| Set(r, a)),
+//     op("jmp", num(), Jmp),
+// ])
+pub type Parser<'p, T> = Box<dyn Fn(&str) -> Option<(T, &str)> + 'p>;
+
+fn next_word(input: &str) -> Option<(&str, &str)> {
+    let input = input.trim_start();
+    if input.is_empty() {
+        return None;
+    }
+    Some(input.split_at(input.find(' ').unwrap_or(input.len())))
+}
+
+// exactly this word
+pub fn word<'p>(expected: &'p str) -> Parser<'p, ()> {
+    Box::new(move |input| match next_word(input)? {
+        (w, rest) if w == expected => Some(((), rest)),
+        _ => None,
+    })
+}
+
+pub fn num<'p, T: FromStr + 'p>() -> Parser<'p, T> {
+    Box::new(|input| {
+        let (w, rest) = next_word(input)?;
+        Some((w.parse().ok()?, rest))
+    })
+}
+
+// a single letter register name, as its index in names
+pub fn reg<'p>(names: &'p str) -> Parser<'p, usize> {
+    Box::new(move |input| {
+        let (w, rest) = next_word(input)?;
+        if w.len() != 1 {
+            return None;
+        }
+        Some((names.find(w)?, rest))
+    })
+}
+
+pub fn arg<'p>(names: &'p str) -> Parser<'p, Arg> {
+    alt(vec![map(reg(names), Arg::Reg), map(num(), Arg::Num)])
+}
+
+pub fn map<'p, A: 'p, B: 'p>(p: Parser<'p, A>, f: impl Fn(A) -> B + 'p) -> Parser<'p, B> {
+    Box::new(move |input| p(input).map(|(a, rest)| (f(a), rest)))
+}
+
+pub fn pair<'p, A: 'p, B: 'p>(pa: Parser<'p, A>, pb: Parser<'p, B>) -> Parser<'p, (A, B)> {
+    Box::new(move |input| {
+        let (a, rest) = pa(input)?;
+        let (b, rest) = pb(rest)?;
+        Some(((a, b), rest))
+    })
+}
+
+pub fn triple<'p, A: 'p, B: 'p, C: 'p>(pa: Parser<'p, A>, pb: Parser<'p, B>, pc: Parser<'p, C>) -> Parser<'p, (A, B, C)> {
+    map(pair(pa, pair(pb, pc)), |(a, (b, c))| (a, b, c))
+}
+
+// the first one that fits
+pub fn alt<'p, T: 'p>(parsers: Vec<Parser<'p, T>>) -> Parser<'p, T> {
+    Box::new(move |input| parsers.iter().find_map(|p| p(input)))
+}
+
+// an instruction: the mnemonic, then its operands
+pub fn op<'p, A: 'p, T: 'p>(name: &'p str, operands: Parser<'p, A>, f: impl Fn(A) -> T + 'p) -> Parser<'p, T> {
+    map(pair(word(name), operands), move |((), a)| f(a))
+}
+
+// nothing, for instructions without operands
+pub fn none<'p>() -> Parser<'p, ()> {
+    Box::new(|input| Some(((), input)))
+}
+
+// all of the line, or a panic that tells which line
+pub fn parse_line<T>(parser: &Parser<T>, line: &str) -> T {
+    match parser(line) {
+        Some((parsed, rest)) if rest.trim().is_empty() => parsed,
+        _ => panic!("can't parse {:?}", line),
+    }
+}
@@ -0,0 +1,211 @@
+// Shortest paths over any graph that can be described by a successor function: plain breadth
+// first for unit steps, Dijkstra for weighted ones, and A* when there's a heuristic that never
+// overestimates. All of them start from any number of sources at once, can stop as soon as a goal
+// comes out of the queue, and remember every predecessor on a shortest path so that one path or
+// all of them can be dug out afterwards. A* finishes the nodes that tie with the goal's estimate
+// before stopping, so with a consistent heuristic no path to the goal goes missing either.
+#![allow(dead_code)]
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+pub type Cost = usize;
+
+pub struct Search<N> {
+    pub dist: HashMap<N, Cost>,
+    // every predecessor that's on some shortest path; sources have none
+    pub parents: HashMap<N, Vec<N>>,
+    // the goal that stopped the search early, if any did
+    pub goal: Option<N>,
+    // nodes taken out of the queue for a look, and how many times something went in
+    pub expanded: usize,
+    pub queued: usize,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    fn new() -> Self {
+        Search { dist: HashMap::new(), parents: HashMap::new(), goal: None, expanded: 0, queued: 0 }
+    }
+
+    fn source(&mut self, node: N) -> bool {
+        self.dist.insert(node, 0).is_none()
+    }
+
+    // true if this is a new best, so next should go to the queue
+    fn relax(&mut self, from: &N, next: N, d: Cost) -> bool {
+        match self.dist.get(&next) {
+            Some(&old) if d > old => false,
+            Some(&old) if d == old => {
+                let parents = self.parents.entry(next).or_default();
+                if !parents.contains(from) {
+                    parents.push(from.clone());
+                }
+                false
+            },
+            _ => {
+                self.dist.insert(next.clone(), d);
+                self.parents.insert(next, vec![from.clone()]);
+                true
+            },
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<Cost> {
+        self.dist.get(node).copied()
+    }
+
+    // from a source to this node, both included
+    pub fn path(&self, to: &N) -> Option<Vec<N>> {
+        self.dist.get(to)?;
+        let mut path = vec![to.clone()];
+        while let Some(prev) = self.parents.get(path.last().unwrap()).and_then(|p| p.first()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    // every node that some shortest path to this one goes through
+    pub fn on_shortest_paths(&self, to: &N) -> HashSet<N> {
+        let mut seen = HashSet::new();
+        if !self.dist.contains_key(to) {
+            return seen;
+        }
+        let mut stack = vec![to.clone()];
+        seen.insert(to.clone());
+        while let Some(node) = stack.pop() {
+            for prev in self.parents.get(&node).into_iter().flatten() {
+                if seen.insert(prev.clone()) {
+                    stack.push(prev.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    // each shortest path separately; there can be exponentially many of them
+    pub fn all_paths(&self, to: &N) -> Vec<Vec<N>> {
+        if !self.dist.contains_key(to) {
+            return Vec::new();
+        }
+        let mut paths = match self.parents.get(to) {
+            Some(parents) if !parents.is_empty() => parents.iter().flat_map(|p| self.all_paths(p)).collect(),
+            _ => vec![Vec::new()],
+        };
+        for path in &mut paths {
+            path.push(to.clone());
+        }
+        paths
+    }
+}
+
+// ordered by the estimate first and then by insertion, for a deterministic search where equally
+// good nodes come out first in first out
+struct Entry<N> {
+    estimate: Cost,
+    seq: usize,
+    cost: Cost,
+    node: N,
+}
+
+impl<N> PartialEq for Entry<N> {
+    fn eq(&self, other: &Self) -> bool {
+        (self.estimate, self.seq) == (other.estimate, other.seq)
+    }
+}
+
+impl<N> Eq for Entry<N> {}
+
+impl<N> PartialOrd for Entry<N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for Entry<N> {
+    // reversed because the heap is a max-heap
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.estimate, other.seq).cmp(&(self.estimate, self.seq))
+    }
+}
+
+pub fn astar<N, I>(starts: impl IntoIterator<Item = N>,
+                   mut successors: impl FnMut(&N) -> I,
+                   mut heuristic: impl FnMut(&N) -> Cost,
+                   mut goal: impl FnMut(&N) -> bool) -> Search<N>
+where N: Clone + Eq + Hash, I: IntoIterator<Item = (N, Cost)> {
+    let mut search = Search::new();
+    let mut heap = BinaryHeap::new();
+    for node in starts {
+        if search.source(node.clone()) {
+            heap.push(Entry { estimate: heuristic(&node), seq: search.queued, cost: 0, node });
+            search.queued += 1;
+        }
+    }
+
+    // the estimate of the goal that was found; nodes with the same estimate may still be queued
+    // and be parents of it, so they get expanded too before stopping
+    let mut goal_estimate = None;
+    while let Some(Entry { estimate, cost, node, .. }) = heap.pop() {
+        if goal_estimate.map_or(false, |f| estimate > f) {
+            break;
+        }
+        if cost > search.dist[&node] {
+            // a better way here was found after this was queued
+            continue;
+        }
+        search.expanded += 1;
+        if goal_estimate.is_none() && goal(&node) {
+            search.goal = Some(node);
+            goal_estimate = Some(estimate);
+            continue;
+        }
+        for (next, step) in successors(&node) {
+            let d = cost + step;
+            if search.relax(&node, next.clone(), d) {
+                heap.push(Entry { estimate: d + heuristic(&next), seq: search.queued, cost: d, node: next });
+                search.queued += 1;
+            }
+        }
+    }
+    search
+}
+
+pub fn dijkstra<N, I>(starts: impl IntoIterator<Item = N>,
+                      successors: impl FnMut(&N) -> I,
+                      goal: impl FnMut(&N) -> bool) -> Search<N>
+where N: Clone + Eq + Hash, I: IntoIterator<Item = (N, Cost)> {
+    astar(starts, successors, |_| 0, goal)
+}
+
+// every step costs one
+pub fn bfs<N, I>(starts: impl IntoIterator<Item = N>,
+                 mut neighbors: impl FnMut(&N) -> I,
+                 mut goal: impl FnMut(&N) -> bool) -> Search<N>
+where N: Clone + Eq + Hash, I: IntoIterator<Item = N> {
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for node in starts {
+        if search.source(node.clone()) {
+            queue.push_back(node);
+            search.queued += 1;
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        search.expanded += 1;
+        if goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let d = search.dist[&node] + 1;
+        for next in neighbors(&node) {
+            if search.relax(&node, next.clone(), d) {
+                queue.push_back(next);
+                search.queued += 1;
+            }
+        }
+    }
+    search
+}
@@ -0,0 +1,212 @@
+// Regression check driven by the sample files that have their expected answers in their names,
+// such as 19/10.sample_210, 19/18.sample_b_72 or 18/15.sample2_37_982_36334. Each such file is fed
+// to its day's binary with AOC_OUTPUT=json and the answer must be that part's value on the json
+// line. With several numbers the last one is the answer and the others are intermediate details,
+// like the rounds and hit points in 18/15, that some line of the output must have as its numbers.
+//
+// usage: samplecheck [year or year/day ...]
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+// these look like answers but are just the second sample or so
+const NOT_ANSWERS: &[&str] = &[
+    "17/18.sample_2",
+    "17/20.sample_2",
+    "19/12.sample_2",
+    "20/14.sample_2",
+    "21/25.sample_2",
+    "21/8.sample_1",
+];
+
+#[derive(Debug, PartialEq)]
+struct Expectation {
+    // "16/1" or so
+    day: String,
+    sample: String,
+    part_b: bool,
+    details: Vec<String>,
+    answer: String,
+}
+
+fn is_number(token: &str) -> bool {
+    !token.is_empty() && token.chars().all(|c| c.is_ascii_digit())
+}
+
+// "10.sample_210" -> part a, 210. Words in between are just labels (21/16.sample_b_literal_2021) and
+// a trailing word means that the file is something else (19/10.sample_vapor, 19/14.sample_31.png)
+fn parse_name(year: &str, filename: &str) -> Option<Expectation> {
+    let (day, rest) = filename.split_once('.')?;
+    if !is_number(day) {
+        return None;
+    }
+    let rest = rest.strip_prefix("sample")?;
+    let mut tokens = rest.split(|c| c == '_' || c == '.').collect::<Vec<_>>();
+    // sample2_..., sample_...
+    if !tokens[0].chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    tokens.remove(0);
+
+    let part_b = tokens.first() == Some(&"b");
+    if part_b {
+        tokens.remove(0);
+    }
+
+    let labels = tokens.iter().take_while(|t| !is_number(t)).count();
+    let values = &tokens[labels..];
+    if values.is_empty() || !values.iter().all(|t| is_number(t)) {
+        return None;
+    }
+
+    let sample = format!("{}/{}", year, filename);
+    if NOT_ANSWERS.contains(&sample.as_str()) {
+        return None;
+    }
+
+    Some(Expectation {
+        day: format!("{}/{}", year, day),
+        sample,
+        part_b,
+        details: values[..values.len() - 1].iter().map(|t| t.to_string()).collect(),
+        answer: values[values.len() - 1].to_string(),
+    })
+}
+
+fn find_samples(filters: &[String]) -> Vec<Expectation> {
+    let mut years = fs::read_dir(".").unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .filter(|name| name.len() == 2 && is_number(name))
+        .collect::<Vec<_>>();
+    years.sort();
+
+    let mut samples = Vec::new();
+    for year in &years {
+        let mut names = fs::read_dir(year).unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        samples.extend(names.iter().filter_map(|name| parse_name(year, name)));
+    }
+
+    samples.retain(|s| filters.is_empty() || filters.iter().any(|f| *f == s.day || s.day.starts_with(&(f.to_string() + "/"))));
+    // numeric day order reads better than 1, 10, 11, ..., 2
+    samples.sort_by_key(|s| {
+        let (y, d) = s.day.split_once('/').unwrap();
+        (y.parse::<u32>().unwrap(), d.parse::<u32>().unwrap())
+    });
+    samples
+}
+
+fn build(day: &str) -> bool {
+    Command::new("make").arg("-s").arg(day)
+        .status().map(|st| st.success()).unwrap_or(false)
+}
+
+// Some days crash on the samples that don't have the puzzle's part b in them, so the output is
+// interesting even then
+fn run(day: &str, sample: &str) -> Option<(String, bool)> {
+    let output = Command::new(Path::new(".").join(day))
+        .env("AOC_OUTPUT", "json")
+        .stdin(fs::File::open(sample).ok()?)
+        .stderr(Stdio::null())
+        .output().ok()?;
+    Some((String::from_utf8_lossy(&output.stdout).into_owned(), output.status.success()))
+}
+
+// all whole numbers on a line; other output formats differ too much between days for anything smarter
+fn numbers(line: &str) -> Vec<&str> {
+    line.split(|c: char| !c.is_ascii_digit())
+        .filter(|t| is_number(t))
+        .collect()
+}
+
+// just enough json for what answer.rs prints: a number, a string or null
+fn json_field(line: &str, key: &str) -> Option<String> {
+    let rest = &line[line.find(&format!("\"{}\":", key))? + key.len() + 3..];
+    if let Some(rest) = rest.strip_prefix('"') {
+        let mut value = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => return Some(value),
+                '\\' => match chars.next()? {
+                    'n' => value.push('\n'),
+                    c => value.push(c),
+                },
+                c => value.push(c),
+            }
+        }
+        None
+    } else {
+        let end = rest.find(|c| c == ',' || c == '}')?;
+        Some(&rest[..end]).filter(|v| *v != "null").map(|v| v.to_string())
+    }
+}
+
+// the json line comes last, after any debug output
+fn answer(exp: &Expectation, output: &str) -> Option<String> {
+    let line = output.lines().rev().find(|l| l.starts_with('{'))?;
+    json_field(line, if exp.part_b { "part2" } else { "part1" })
+}
+
+fn details_found(exp: &Expectation, output: &str) -> bool {
+    exp.details.is_empty() || output.lines().any(|line| numbers(line) == exp.details)
+}
+
+fn check(exp: &Expectation, output: &str) -> bool {
+    answer(exp, output).as_deref() == Some(exp.answer.as_str()) && details_found(exp, output)
+}
+
+fn report_diff(exp: &Expectation, output: &str) {
+    println!("    - {}", exp.answer);
+    println!("    + {}", answer(exp, output).unwrap_or("(none)".to_string()));
+    if !details_found(exp, output) {
+        println!("    no line with just {}", exp.details.join(" "));
+    }
+}
+
+fn main() {
+    let filters = env::args().skip(1).collect::<Vec<_>>();
+    let samples = find_samples(&filters);
+
+    let mut failures = 0;
+    let mut built: Vec<String> = Vec::new();
+    for exp in &samples {
+        if !built.contains(&exp.day) {
+            if !build(&exp.day) {
+                println!("BUILD FAIL {}", exp.day);
+            }
+            built.push(exp.day.clone());
+        }
+        let part = if exp.part_b { "b" } else { "a" };
+        let details = if exp.details.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", exp.details.join(" "))
+        };
+        match run(&exp.day, &exp.sample) {
+            Some((output, success)) => {
+                let crashed = if success { "" } else { ", crashed" };
+                if check(exp, &output) {
+                    println!("pass {} ({}: {}{}{})", exp.sample, part, exp.answer, details, crashed);
+                } else {
+                    println!("FAIL {} ({}: {}{}{})", exp.sample, part, exp.answer, details, crashed);
+                    report_diff(exp, &output);
+                    failures += 1;
+                }
+            },
+            None => {
+                println!("FAIL {} (did not run)", exp.sample);
+                failures += 1;
+            }
+        }
+    }
+
+    println!("{} samples, {} failed", samples.len(), failures);
+    if failures > 0 {
+        std::process::exit(1);
+    }
+}